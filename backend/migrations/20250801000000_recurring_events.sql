@@ -0,0 +1,19 @@
+-- Recurrence rules (RFC 5545 RRULE subset) for event series
+ALTER TABLE events ADD COLUMN recurrence_rule VARCHAR(255);
+ALTER TABLE events ADD COLUMN recurrence_exdates DATE[] NOT NULL DEFAULT '{}';
+
+-- Per-occurrence cancellations and overrides; translations stay per series
+CREATE TABLE event_occurrence_overrides (
+    id SERIAL PRIMARY KEY,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    occurrence_date DATE NOT NULL,
+    cancelled BOOLEAN NOT NULL DEFAULT false,
+    event_time TIME,
+    location VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(event_id, occurrence_date)
+);
+
+CREATE INDEX idx_events_recurring ON events(event_date) WHERE recurrence_rule IS NOT NULL;
+CREATE INDEX idx_event_occurrence_overrides_event_id ON event_occurrence_overrides(event_id);
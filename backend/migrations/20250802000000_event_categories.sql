@@ -0,0 +1,74 @@
+-- Managed event categories, referenced by slug from events.category
+CREATE TABLE categories (
+    slug VARCHAR(100) PRIMARY KEY,
+    color VARCHAR(7),
+    icon VARCHAR(50),
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Per-language display names
+CREATE TABLE category_translations (
+    id SERIAL PRIMARY KEY,
+    category_slug VARCHAR(100) NOT NULL REFERENCES categories(slug) ON DELETE CASCADE ON UPDATE CASCADE,
+    language_code VARCHAR(10) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    UNIQUE(category_slug, language_code)
+);
+
+INSERT INTO categories (slug, color, icon, sort_order) VALUES
+('community', '#2563eb', 'users', 10),
+('music', '#7c3aed', 'music', 20),
+('arts', '#db2777', 'palette', 30),
+('education', '#0891b2', 'book', 40),
+('family', '#ea580c', 'baby', 50),
+('food', '#65a30d', 'utensils', 60),
+('market', '#16a34a', 'store', 70),
+('sports', '#dc2626', 'trophy', 80),
+('health', '#0d9488', 'heart', 90),
+('government', '#475569', 'landmark', 100);
+
+INSERT INTO category_translations (category_slug, language_code, name) VALUES
+('community', 'en', 'Community'),
+('music', 'en', 'Music'),
+('arts', 'en', 'Arts & Culture'),
+('education', 'en', 'Education'),
+('family', 'en', 'Family'),
+('food', 'en', 'Food & Drink'),
+('market', 'en', 'Markets'),
+('sports', 'en', 'Sports'),
+('health', 'en', 'Health'),
+('government', 'en', 'Government'),
+('community', 'es', 'Comunidad'),
+('music', 'es', 'Música'),
+('arts', 'es', 'Arte y cultura'),
+('education', 'es', 'Educación'),
+('family', 'es', 'Familia'),
+('food', 'es', 'Comida y bebida'),
+('market', 'es', 'Mercados'),
+('sports', 'es', 'Deportes'),
+('health', 'es', 'Salud'),
+('government', 'es', 'Gobierno');
+
+-- Normalize existing free-text categories ("Music", "music ") onto slugs
+UPDATE events
+SET category = NULLIF(BTRIM(LOWER(REGEXP_REPLACE(BTRIM(category), '[^a-zA-Z0-9]+', '-', 'g')), '-'), '')
+WHERE category IS NOT NULL;
+
+UPDATE events SET category = 'music' WHERE category IN ('concert', 'concerts');
+
+INSERT INTO categories (slug)
+SELECT DISTINCT category FROM events WHERE category IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+INSERT INTO category_translations (category_slug, language_code, name)
+SELECT slug, 'en', INITCAP(REPLACE(slug, '-', ' ')) FROM categories
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE events
+    ADD CONSTRAINT events_category_fkey FOREIGN KEY (category)
+    REFERENCES categories(slug) ON UPDATE CASCADE ON DELETE SET NULL;
+
+CREATE INDEX idx_events_category ON events(category);
+CREATE INDEX idx_category_translations_slug ON category_translations(category_slug);
@@ -0,0 +1,19 @@
+-- Structured venues that events can reference
+CREATE TABLE venues (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    address VARCHAR(500),
+    latitude DOUBLE PRECISION CHECK (latitude BETWEEN -90 AND 90),
+    longitude DOUBLE PRECISION CHECK (longitude BETWEEN -180 AND 180),
+    accessibility_info TEXT,
+    wheelchair_accessible BOOLEAN,
+    -- Normalized name + address, used to catch duplicate submissions of the same place
+    dedupe_key VARCHAR(800) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE events ADD COLUMN venue_id INTEGER REFERENCES venues(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_venues_lat_lon ON venues(latitude, longitude);
+CREATE INDEX idx_events_venue_id ON events(venue_id);
@@ -0,0 +1,49 @@
+-- Trigram matching for languages without usable stemming/word segmentation
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Maps our language codes onto Postgres text-search configurations; everything
+-- without a dedicated stemmer (Chinese, Arabic, Hebrew, Persian, Urdu) uses 'simple'
+CREATE OR REPLACE FUNCTION lancaster_ts_config(language_code TEXT) RETURNS regconfig AS $$
+    SELECT CASE language_code
+        WHEN 'en' THEN 'english'::regconfig
+        WHEN 'es' THEN 'spanish'::regconfig
+        WHEN 'de' THEN 'german'::regconfig
+        WHEN 'fr' THEN 'french'::regconfig
+        ELSE 'simple'::regconfig
+    END
+$$ LANGUAGE SQL IMMUTABLE;
+
+ALTER TABLE posts ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector(lancaster_ts_config(original_language), COALESCE(title, '')), 'A') ||
+    setweight(to_tsvector(lancaster_ts_config(original_language), COALESCE(content, '')), 'B')
+) STORED;
+
+ALTER TABLE post_translations ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector(lancaster_ts_config(language_code), COALESCE(title, '')), 'A') ||
+    setweight(to_tsvector(lancaster_ts_config(language_code), COALESCE(content, '')), 'B')
+) STORED;
+
+ALTER TABLE events ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector(lancaster_ts_config(original_language), COALESCE(title, '')), 'A') ||
+    setweight(to_tsvector(lancaster_ts_config(original_language), COALESCE(description, '')), 'B') ||
+    setweight(to_tsvector(lancaster_ts_config(original_language), COALESCE(location, '')), 'C')
+) STORED;
+
+ALTER TABLE event_translations ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector(lancaster_ts_config(language_code), COALESCE(title, '')), 'A') ||
+    setweight(to_tsvector(lancaster_ts_config(language_code), COALESCE(description, '')), 'B')
+) STORED;
+
+CREATE INDEX idx_posts_search ON posts USING GIN (search_vector);
+CREATE INDEX idx_post_translations_search ON post_translations USING GIN (search_vector);
+CREATE INDEX idx_events_search ON events USING GIN (search_vector);
+CREATE INDEX idx_event_translations_search ON event_translations USING GIN (search_vector);
+
+CREATE INDEX idx_posts_title_trgm ON posts USING GIN (title gin_trgm_ops);
+CREATE INDEX idx_posts_content_trgm ON posts USING GIN (content gin_trgm_ops);
+CREATE INDEX idx_post_translations_title_trgm ON post_translations USING GIN (title gin_trgm_ops);
+CREATE INDEX idx_post_translations_content_trgm ON post_translations USING GIN (content gin_trgm_ops);
+CREATE INDEX idx_events_title_trgm ON events USING GIN (title gin_trgm_ops);
+CREATE INDEX idx_events_description_trgm ON events USING GIN (description gin_trgm_ops);
+CREATE INDEX idx_event_translations_title_trgm ON event_translations USING GIN (title gin_trgm_ops);
+CREATE INDEX idx_event_translations_description_trgm ON event_translations USING GIN (description gin_trgm_ops);
@@ -0,0 +1,5 @@
+-- Keyset pagination on (created_at, id) needs a total order without NULLs
+UPDATE posts SET created_at = NOW() WHERE created_at IS NULL;
+ALTER TABLE posts ALTER COLUMN created_at SET NOT NULL;
+
+CREATE INDEX idx_posts_feed ON posts(created_at DESC, id DESC) WHERE published = true;
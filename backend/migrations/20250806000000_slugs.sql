@@ -0,0 +1,28 @@
+-- Human-readable permalinks for posts and events. Slugs are optional: items are
+-- always reachable by id, and a slug is never purely numeric so the two can't clash.
+ALTER TABLE posts ADD COLUMN slug VARCHAR(100) UNIQUE;
+ALTER TABLE events ADD COLUMN slug VARCHAR(100) UNIQUE;
+
+-- Backfill existing rows; the first item with a given title keeps the bare slug,
+-- later ones (and numeric-only titles) get the id appended
+WITH base AS (
+    SELECT id, NULLIF(TRIM(BOTH '-' FROM LEFT(LOWER(REGEXP_REPLACE(title, '[^[:alnum:]]+', '-', 'g')), 80)), '') AS slug
+    FROM posts
+), ranked AS (
+    SELECT id, slug, ROW_NUMBER() OVER (PARTITION BY slug ORDER BY id) AS n FROM base WHERE slug IS NOT NULL
+)
+UPDATE posts p
+SET slug = CASE WHEN r.n = 1 AND r.slug !~ '^[0-9]+$' THEN r.slug ELSE r.slug || '-' || r.id END
+FROM ranked r
+WHERE p.id = r.id;
+
+WITH base AS (
+    SELECT id, NULLIF(TRIM(BOTH '-' FROM LEFT(LOWER(REGEXP_REPLACE(title, '[^[:alnum:]]+', '-', 'g')), 80)), '') AS slug
+    FROM events
+), ranked AS (
+    SELECT id, slug, ROW_NUMBER() OVER (PARTITION BY slug ORDER BY id) AS n FROM base WHERE slug IS NOT NULL
+)
+UPDATE events e
+SET slug = CASE WHEN r.n = 1 AND r.slug !~ '^[0-9]+$' THEN r.slug ELSE r.slug || '-' || r.id END
+FROM ranked r
+WHERE e.id = r.id;
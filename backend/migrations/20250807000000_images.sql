@@ -0,0 +1,14 @@
+-- Uploaded images. The id is the sha256 of the uploaded file, so re-uploading the
+-- same photo reuses the stored variants; each variant key is itself a content hash.
+CREATE TABLE images (
+    id VARCHAR(64) PRIMARY KEY,
+    display_key VARCHAR(100) NOT NULL,
+    thumbnail_key VARCHAR(100) NOT NULL,
+    content_type VARCHAR(50) NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE posts ADD COLUMN image_id VARCHAR(64) REFERENCES images(id) ON DELETE SET NULL;
+ALTER TABLE events ADD COLUMN image_id VARCHAR(64) REFERENCES images(id) ON DELETE SET NULL;
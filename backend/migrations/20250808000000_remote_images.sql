@@ -0,0 +1,26 @@
+-- URLs used to be stored verbatim. Ones that aren't http(s) links are moved aside
+-- rather than served, and kept so nothing submitted is lost.
+ALTER TABLE posts ADD COLUMN legacy_link_url TEXT, ADD COLUMN legacy_image_url TEXT;
+ALTER TABLE events ADD COLUMN legacy_ticket_url TEXT;
+
+UPDATE posts SET legacy_link_url = link_url, link_url = NULL WHERE link_url !~* '^https?://[^/]';
+UPDATE posts SET legacy_image_url = image_url, image_url = NULL WHERE image_url !~* '^https?://[^/]';
+UPDATE events SET legacy_ticket_url = ticket_url, ticket_url = NULL WHERE ticket_url !~* '^https?://[^/]';
+
+-- Remote images served through /api/image-proxy. Only URLs registered here (by a
+-- submission) can be fetched, so the proxy can't be pointed at arbitrary addresses.
+-- The id is the sha256 of the URL, which lets queries derive proxy paths directly.
+CREATE TABLE remote_images (
+    id VARCHAR(64) PRIMARY KEY,
+    url VARCHAR(500) NOT NULL,
+    image_key VARCHAR(100), -- Blob store key once fetched
+    fetched_at TIMESTAMPTZ,
+    failed_at TIMESTAMPTZ, -- Last failed fetch, to avoid hammering a broken host
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO remote_images (id, url)
+SELECT DISTINCT encode(sha256(convert_to(image_url, 'UTF8')), 'hex'), image_url
+FROM posts
+WHERE image_url IS NOT NULL
+ON CONFLICT (id) DO NOTHING;
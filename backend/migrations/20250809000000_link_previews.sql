@@ -0,0 +1,22 @@
+-- Unfurled previews of link posts' link_url, filled in by a background job
+CREATE TABLE link_previews (
+    post_id INTEGER PRIMARY KEY REFERENCES posts(id) ON DELETE CASCADE,
+    url VARCHAR(500) NOT NULL, -- The link_url that was fetched
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, ready or failed
+    title VARCHAR(300),
+    description TEXT,
+    image_url VARCHAR(500), -- Served through /api/image-proxy like post images
+    site_name VARCHAR(200),
+    language VARCHAR(10), -- From the page's <html lang>, when it declares one
+    fetched_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE link_preview_translations (
+    post_id INTEGER NOT NULL REFERENCES link_previews(post_id) ON DELETE CASCADE,
+    language_code VARCHAR(10) NOT NULL,
+    title VARCHAR(300),
+    description TEXT,
+    translated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, language_code)
+);
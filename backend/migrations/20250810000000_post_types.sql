@@ -0,0 +1,11 @@
+-- post_type was free text; fold what's there onto the known types before constraining it
+UPDATE posts SET post_type = lower(trim(post_type)) WHERE post_type IS NOT NULL;
+UPDATE posts
+SET post_type = CASE WHEN link_url IS NOT NULL AND content IS NULL THEN 'link' ELSE 'text' END
+WHERE post_type IS NULL OR post_type NOT IN ('text', 'link', 'announcement', 'article');
+
+ALTER TABLE posts ALTER COLUMN post_type SET NOT NULL;
+ALTER TABLE posts ADD CONSTRAINT posts_post_type_check
+    CHECK (post_type IN ('text', 'link', 'announcement', 'article'));
+
+CREATE INDEX idx_posts_feed_by_type ON posts(post_type, created_at DESC, id DESC) WHERE published = true;
@@ -0,0 +1,9 @@
+-- Submissions may ask for "auto", but what gets stored is always resolved to ltr or rtl
+UPDATE posts SET text_direction = 'ltr' WHERE text_direction IS NULL OR text_direction NOT IN ('ltr', 'rtl');
+UPDATE events SET text_direction = 'ltr' WHERE text_direction IS NULL OR text_direction NOT IN ('ltr', 'rtl');
+
+ALTER TABLE posts ALTER COLUMN text_direction SET NOT NULL;
+ALTER TABLE posts ADD CONSTRAINT posts_text_direction_check CHECK (text_direction IN ('ltr', 'rtl'));
+
+ALTER TABLE events ALTER COLUMN text_direction SET NOT NULL;
+ALTER TABLE events ADD CONSTRAINT events_text_direction_check CHECK (text_direction IN ('ltr', 'rtl'));
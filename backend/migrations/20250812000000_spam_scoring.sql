@@ -0,0 +1,29 @@
+-- Spam assessment of submissions, filled in when they're created. Quarantined
+-- submissions wait for a moderator before anything in them (such as links) is acted on.
+ALTER TABLE posts
+    ADD COLUMN spam_score INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN spam_reasons JSONB NOT NULL DEFAULT '[]',
+    ADD COLUMN quarantined BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN content_hash VARCHAR(64), -- sha256 of the normalized title and text
+    ADD COLUMN submitter_hash VARCHAR(64); -- sha256 of the submitting address
+
+ALTER TABLE events
+    ADD COLUMN spam_score INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN spam_reasons JSONB NOT NULL DEFAULT '[]',
+    ADD COLUMN quarantined BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN content_hash VARCHAR(64),
+    ADD COLUMN submitter_hash VARCHAR(64);
+
+CREATE INDEX idx_posts_content_hash ON posts(content_hash);
+CREATE INDEX idx_posts_submitter ON posts(submitter_hash, created_at);
+CREATE INDEX idx_posts_author_email ON posts(lower(author_email), created_at);
+CREATE INDEX idx_events_content_hash ON events(content_hash);
+CREATE INDEX idx_events_submitter ON events(submitter_hash, created_at);
+CREATE INDEX idx_events_organizer_email ON events(lower(organizer_email), created_at);
+
+-- Links to these domains (or their subdomains) count heavily against a submission
+CREATE TABLE blocked_domains (
+    domain VARCHAR(255) PRIMARY KEY,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
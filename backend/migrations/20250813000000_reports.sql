@@ -0,0 +1,24 @@
+-- Residents' reports about published posts and events. Pending reports wait for a
+-- moderator, who dismisses them (the item stays up) or upholds them (it stays hidden).
+CREATE TABLE reports (
+    id SERIAL PRIMARY KEY,
+    post_id INTEGER REFERENCES posts(id) ON DELETE CASCADE,
+    event_id INTEGER REFERENCES events(id) ON DELETE CASCADE,
+    reason VARCHAR(32) NOT NULL
+        CHECK (reason IN ('misinformation', 'hate_speech', 'harassment', 'spam', 'outdated', 'other')),
+    comment TEXT,
+    reporter_hash VARCHAR(64) NOT NULL, -- sha256 of the reporting address
+    status VARCHAR(16) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'dismissed', 'upheld')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    CHECK ((post_id IS NULL) <> (event_id IS NULL))
+);
+
+-- A reporter counts once per item until the reports are resolved
+CREATE UNIQUE INDEX idx_reports_pending_post ON reports(post_id, reporter_hash) WHERE status = 'pending';
+CREATE UNIQUE INDEX idx_reports_pending_event ON reports(event_id, reporter_hash) WHERE status = 'pending';
+CREATE INDEX idx_reports_status ON reports(status, created_at);
+
+-- Hidden items are published but kept out of public listings while reports are reviewed
+ALTER TABLE posts ADD COLUMN hidden BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE events ADD COLUMN hidden BOOLEAN NOT NULL DEFAULT false;
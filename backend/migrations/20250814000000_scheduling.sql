@@ -0,0 +1,13 @@
+-- Approved posts can wait for publish_at and disappear at expires_at; the scheduler
+-- flips them and sets archived_at. Events are archived once their last date is past.
+ALTER TABLE posts
+    ADD COLUMN publish_at TIMESTAMPTZ,
+    ADD COLUMN expires_at TIMESTAMPTZ,
+    ADD COLUMN archived_at TIMESTAMPTZ,
+    ADD CONSTRAINT posts_schedule_check CHECK (publish_at IS NULL OR expires_at IS NULL OR expires_at > publish_at);
+
+ALTER TABLE events ADD COLUMN archived_at TIMESTAMPTZ;
+
+CREATE INDEX idx_posts_publish_at ON posts(publish_at) WHERE NOT published;
+CREATE INDEX idx_posts_expires_at ON posts(expires_at) WHERE archived_at IS NULL;
+CREATE INDEX idx_events_unarchived ON events(event_date) WHERE archived_at IS NULL;
@@ -0,0 +1,10 @@
+-- Pinned posts are listed ahead of the feed until pinned_until, emergencies first, then
+-- by priority. Unpinning clears all four columns.
+ALTER TABLE posts
+    ADD COLUMN pinned_at TIMESTAMPTZ,
+    ADD COLUMN pinned_until TIMESTAMPTZ,
+    ADD COLUMN pin_priority INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN emergency BOOLEAN NOT NULL DEFAULT false;
+
+CREATE INDEX idx_posts_pinned ON posts(emergency DESC, pin_priority DESC, pinned_at DESC)
+    WHERE pinned_at IS NOT NULL;
@@ -0,0 +1,26 @@
+-- Emergency alerts go live only once translated into every enabled language, and stay
+-- up until expires_at or until a moderator ends them. Severities follow CAP.
+CREATE TABLE alerts (
+    id SERIAL PRIMARY KEY,
+    title VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    severity VARCHAR(16) NOT NULL CHECK (severity IN ('minor', 'moderate', 'severe', 'extreme')),
+    original_language VARCHAR(10) NOT NULL,
+    text_direction VARCHAR(10) NOT NULL DEFAULT 'ltr',
+    expires_at TIMESTAMPTZ NOT NULL,
+    ended_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE alert_translations (
+    id SERIAL PRIMARY KEY,
+    alert_id INTEGER NOT NULL REFERENCES alerts(id) ON DELETE CASCADE,
+    language_code VARCHAR(10) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    text_direction VARCHAR(10) NOT NULL DEFAULT 'ltr',
+    translated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(alert_id, language_code)
+);
+
+CREATE INDEX idx_alerts_active ON alerts(expires_at) WHERE ended_at IS NULL;
@@ -0,0 +1,45 @@
+-- ActivityPub: every enabled language is an actor (@en@host, @ar@host, ...) that fediverse
+-- accounts can follow. All of them sign with the one key pair, created on first use.
+CREATE TABLE activitypub_keys (
+    id INTEGER PRIMARY KEY DEFAULT 1 CHECK (id = 1),
+    private_key_pem TEXT NOT NULL,
+    public_key_pem TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE activitypub_followers (
+    id SERIAL PRIMARY KEY,
+    language_code VARCHAR(10) NOT NULL REFERENCES supported_languages(code) ON DELETE CASCADE,
+    actor_id VARCHAR(500) NOT NULL,
+    inbox_url VARCHAR(500) NOT NULL,
+    shared_inbox_url VARCHAR(500),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (language_code, actor_id)
+);
+
+-- A published item is announced at most once per language, as soon as its translation
+-- into that language is ready (or the wait for one is over)
+CREATE TABLE activitypub_announcements (
+    id SERIAL PRIMARY KEY,
+    item_kind VARCHAR(16) NOT NULL CHECK (item_kind IN ('post', 'event')),
+    item_id INTEGER NOT NULL,
+    language_code VARCHAR(10) NOT NULL,
+    announced_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (item_kind, item_id, language_code)
+);
+
+-- Signed activities on their way to remote inboxes; removed once delivered or given up on
+CREATE TABLE activitypub_deliveries (
+    id SERIAL PRIMARY KEY,
+    language_code VARCHAR(10) NOT NULL,
+    inbox_url VARCHAR(500) NOT NULL,
+    activity JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_activitypub_announcements_pending ON activitypub_announcements(created_at) WHERE announced_at IS NULL;
+CREATE INDEX idx_activitypub_deliveries_due ON activitypub_deliveries(next_attempt_at);
@@ -0,0 +1,18 @@
+-- Email newsletter. Nothing but the confirmation email goes to an address until its owner
+-- follows the link in it (double opt-in); the same token then manages the subscription
+-- and unsubscribes from every digest.
+CREATE TABLE newsletter_subscribers (
+    id SERIAL PRIMARY KEY,
+    email VARCHAR(255) NOT NULL UNIQUE, -- Lowercased
+    language_code VARCHAR(10) NOT NULL REFERENCES supported_languages(code) ON DELETE CASCADE,
+    topics VARCHAR(16)[] NOT NULL, -- posts and/or events
+    categories VARCHAR(50)[] NOT NULL DEFAULT '{}', -- Event categories; empty means all of them
+    token VARCHAR(64) NOT NULL UNIQUE,
+    confirmed_at TIMESTAMPTZ,
+    confirmation_sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_digest_at TIMESTAMPTZ, -- The edition last sent
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_newsletter_subscribers_confirmed ON newsletter_subscribers(confirmed_at) WHERE confirmed_at IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Substring search also matches event locations, like titles and descriptions
+CREATE INDEX idx_events_location_trgm ON events USING GIN (location gin_trgm_ops);
@@ -0,0 +1,3 @@
+-- A translation can run longer than the title it came from, and one that didn't fit
+-- failed the whole alert
+ALTER TABLE alert_translations ALTER COLUMN title TYPE TEXT;
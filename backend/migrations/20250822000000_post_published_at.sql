@@ -0,0 +1,7 @@
+-- When a post actually went out, which for a scheduled or long-moderated post is well
+-- after it was submitted. The newsletter picks the week's posts by this.
+ALTER TABLE posts ADD COLUMN published_at TIMESTAMPTZ;
+
+UPDATE posts SET published_at = COALESCE(publish_at, created_at) WHERE published;
+
+CREATE INDEX idx_posts_published_at ON posts(published_at) WHERE published;
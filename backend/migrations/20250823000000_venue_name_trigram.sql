@@ -0,0 +1,3 @@
+-- The events location filter also matches the venue's name, which is what venue-only
+-- events are listed under
+CREATE INDEX idx_venues_name_trgm ON venues USING GIN (name gin_trgm_ops);
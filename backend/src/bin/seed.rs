@@ -156,8 +156,8 @@ async fn seed_posts(pool: &sqlx::PgPool) -> Result<(), Box<dyn std::error::Error
             r#"
             INSERT INTO posts (
                 author_name, author_email, title, content, link_url, image_url, 
-                post_type, original_language, text_direction, published, created_at, updated_at
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)
+                post_type, original_language, text_direction, published, published_at, created_at, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, CASE WHEN $10 THEN $11 END, $11, $11)
             "#
         )
         .bind(post.author_name)
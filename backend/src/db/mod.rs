@@ -1,12 +1,35 @@
 use sqlx::PgPool;
 use std::sync::Arc;
 
+use crate::{
+    middleware::client_ip::TrustedProxies,
+    services::{
+        activitypub::{ActorKeys, RemoteKeys}, challenge::ChallengeConfig, geocoding::Geocoder, mail::Mailer,
+        notices::{LocalizedChannels, Notifier}, rate_limit::RateLimiter,
+        spam::SpamScorer, storage::BlobStore,
+    },
+};
+
 #[derive(Clone)]
 pub struct AppState {
     pub pool: PgPool,
     pub redis_client: redis::Client,
     pub admin_password: String,
     pub google_api_key: Option<String>,
+    pub geocoder: Arc<dyn Geocoder>,
+    pub blob_store: Arc<dyn BlobStore>,
+    pub public_url: String, // Where the frontend is served, without a trailing slash
+    pub rate_limiter: RateLimiter,
+    pub trusted_proxies: TrustedProxies,
+    pub spam_scorer: Arc<SpamScorer>,
+    pub challenges: Arc<ChallengeConfig>,
+    pub report_hide_threshold: i64, // Independent reports that hide an item; 0 never hides
+    pub webhooks_allow_private: bool, // Lets webhooks reach addresses on our own network
+    pub notifier: Notifier, // Feeds open alert and content streams on every instance
+    pub localized_notices: LocalizedChannels, // Content notices for this instance's streams, per language
+    pub actor_keys: ActorKeys, // Signs ActivityPub requests; loaded on first use
+    pub remote_keys: RemoteKeys, // Keys of fediverse actors that signed inbox requests
+    pub mailer: Arc<dyn Mailer>, // SMTP, or only a log line when SMTP_URL is unset
 }
 
 pub type SharedState = Arc<AppState>;
\ No newline at end of file
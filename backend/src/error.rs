@@ -0,0 +1,358 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use serde_json::json;
+use validator::Validate;
+
+use crate::middleware::request_context;
+
+/// The error every handler returns. Responses carry a stable `code` for the frontend
+/// to branch on, a `message` in the request's language, per-field details for
+/// validation failures, and the request id to quote to support.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("validation failed: {0:?}")]
+    Validation(Vec<FieldError>),
+    #[error("malformed request")]
+    BadRequest,
+    #[error("authentication required")]
+    Unauthorized,
+    #[error("not found")]
+    NotFound,
+    #[error("method not allowed")]
+    MethodNotAllowed,
+    #[error("{0} is already taken")]
+    Conflict(&'static str), // The field whose value is taken
+    #[error("payload too large")]
+    PayloadTooLarge,
+    #[error("unsupported media type")]
+    UnsupportedMediaType,
+    #[error("rate limited, retry after {0}s")]
+    RateLimited(u64), // Seconds until the client may try again
+    #[error("address could not be geocoded")]
+    GeocodingFailed,
+    #[error("upstream service unavailable")]
+    BadGateway,
+    // Logged where it happened, with the context that only the caller has
+    #[error("internal error")]
+    Internal,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub code: FieldErrorCode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldErrorCode {
+    Required,
+    Invalid,
+    TooLong,
+    OutOfRange,
+    Unknown, // Refers to something that doesn't exist
+    Taken,
+}
+
+impl FieldError {
+    /// Runs a payload's declarative checks and returns the fields that failed, one
+    /// error per field, sorted by name so responses are stable.
+    pub fn check<T: Validate>(payload: &T) -> Vec<FieldError> {
+        let Err(errors) = payload.validate() else {
+            return Vec::new();
+        };
+
+        let mut fields: Vec<FieldError> = errors
+            .field_errors()
+            .into_iter()
+            .filter_map(|(field, errors)| {
+                let code = match errors.first()?.code.as_ref() {
+                    "required" => FieldErrorCode::Required,
+                    "too_long" => FieldErrorCode::TooLong,
+                    "out_of_range" | "range" => FieldErrorCode::OutOfRange,
+                    _ => FieldErrorCode::Invalid,
+                };
+                Some(FieldError { field: field.to_string(), code })
+            })
+            .collect();
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        fields
+    }
+}
+
+impl FieldErrorCode {
+    fn as_str(self) -> &'static str {
+        match self {
+            FieldErrorCode::Required => "required",
+            FieldErrorCode::Invalid => "invalid",
+            FieldErrorCode::TooLong => "too_long",
+            FieldErrorCode::OutOfRange => "out_of_range",
+            FieldErrorCode::Unknown => "unknown",
+            FieldErrorCode::Taken => "taken",
+        }
+    }
+}
+
+impl ApiError {
+    /// A validation failure on a single field.
+    pub fn field(field: &str, code: FieldErrorCode) -> Self {
+        ApiError::Validation(vec![FieldError { field: field.to_string(), code }])
+    }
+
+    /// For status codes produced outside our handlers (extractor rejections, routing).
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => ApiError::BadRequest,
+            StatusCode::UNAUTHORIZED => ApiError::Unauthorized,
+            StatusCode::NOT_FOUND => ApiError::NotFound,
+            StatusCode::METHOD_NOT_ALLOWED => ApiError::MethodNotAllowed,
+            StatusCode::PAYLOAD_TOO_LARGE => ApiError::PayloadTooLarge,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => ApiError::UnsupportedMediaType,
+            StatusCode::BAD_GATEWAY => ApiError::BadGateway,
+            _ => ApiError::Internal,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::Validation(_) | ApiError::BadRequest => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::NotFound => StatusCode::NOT_FOUND,
+            ApiError::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ApiError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::GeocodingFailed => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::BadGateway => StatusCode::BAD_GATEWAY,
+            ApiError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::Validation(_) => "validation_failed",
+            ApiError::BadRequest => "bad_request",
+            ApiError::Unauthorized => "unauthorized",
+            ApiError::NotFound => "not_found",
+            ApiError::MethodNotAllowed => "method_not_allowed",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::PayloadTooLarge => "payload_too_large",
+            ApiError::UnsupportedMediaType => "unsupported_media_type",
+            ApiError::RateLimited(_) => "rate_limited",
+            ApiError::GeocodingFailed => "geocoding_failed",
+            ApiError::BadGateway => "upstream_unavailable",
+            ApiError::Internal => "internal_error",
+        }
+    }
+
+    fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            ApiError::Validation(fields) => fields.clone(),
+            ApiError::Conflict(field) => vec![FieldError {
+                field: field.to_string(),
+                code: FieldErrorCode::Taken,
+            }],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let context = request_context::current();
+        let lang = context.as_ref().map_or("en", |c| c.language.as_str());
+
+        let fields: Vec<_> = self
+            .field_errors()
+            .into_iter()
+            .map(|f| {
+                json!({
+                    "field": f.field,
+                    "code": f.code,
+                    "message": localize(f.code.as_str(), lang)
+                })
+            })
+            .collect();
+
+        let mut body = json!({
+            "code": self.code(),
+            "message": localize(self.code(), lang),
+            "request_id": context.map(|c| c.request_id)
+        });
+        if !fields.is_empty() {
+            body["fields"] = json!(fields);
+        }
+
+        let mut response = (self.status(), Json(body)).into_response();
+        let headers = response.headers_mut();
+        headers.insert(header::CACHE_CONTROL, header::HeaderValue::from_static("no-store"));
+        if let ApiError::RateLimited(seconds) = self {
+            headers.insert(header::RETRY_AFTER, seconds.into());
+        }
+        response
+    }
+}
+
+// Languages in column order of MESSAGES; anything else is answered in English
+const MESSAGE_LANGUAGES: [&str; 6] = ["en", "es", "de", "fr", "zh", "ar"];
+
+const MESSAGES: &[(&str, [&str; 6])] = &[
+    ("validation_failed", [
+        "Some fields need attention",
+        "Algunos campos necesitan revisión",
+        "Einige Felder müssen überprüft werden",
+        "Certains champs doivent être corrigés",
+        "部分字段需要修改",
+        "بعض الحقول تحتاج إلى مراجعة",
+    ]),
+    ("bad_request", [
+        "The request could not be understood",
+        "No se pudo entender la solicitud",
+        "Die Anfrage konnte nicht verarbeitet werden",
+        "La requête n'a pas pu être comprise",
+        "无法理解该请求",
+        "تعذّر فهم الطلب",
+    ]),
+    ("unauthorized", [
+        "Please sign in again",
+        "Vuelve a iniciar sesión",
+        "Bitte melde dich erneut an",
+        "Veuillez vous reconnecter",
+        "请重新登录",
+        "يرجى تسجيل الدخول مرة أخرى",
+    ]),
+    ("not_found", [
+        "This item could not be found",
+        "No se encontró este elemento",
+        "Dieser Eintrag wurde nicht gefunden",
+        "Cet élément est introuvable",
+        "找不到该内容",
+        "تعذر العثور على هذا العنصر",
+    ]),
+    ("method_not_allowed", [
+        "This action is not supported",
+        "Esta acción no está disponible",
+        "Diese Aktion wird nicht unterstützt",
+        "Cette action n'est pas prise en charge",
+        "不支持此操作",
+        "هذا الإجراء غير مدعوم",
+    ]),
+    ("conflict", [
+        "This already exists",
+        "Esto ya existe",
+        "Das existiert bereits",
+        "Cet élément existe déjà",
+        "该内容已存在",
+        "هذا موجود بالفعل",
+    ]),
+    ("payload_too_large", [
+        "The upload is too large",
+        "El archivo es demasiado grande",
+        "Die Datei ist zu groß",
+        "Le fichier est trop volumineux",
+        "上传的文件太大",
+        "الملف المرفوع كبير جدًا",
+    ]),
+    ("unsupported_media_type", [
+        "This file type is not supported",
+        "Este tipo de archivo no es compatible",
+        "Dieser Dateityp wird nicht unterstützt",
+        "Ce type de fichier n'est pas pris en charge",
+        "不支持此文件类型",
+        "نوع الملف هذا غير مدعوم",
+    ]),
+    ("rate_limited", [
+        "Too many attempts, please wait a moment and try again",
+        "Demasiados intentos, espera un momento e inténtalo de nuevo",
+        "Zu viele Versuche, bitte warte einen Moment und versuche es erneut",
+        "Trop de tentatives, veuillez patienter un instant puis réessayer",
+        "尝试次数过多，请稍后再试",
+        "محاولات كثيرة جدًا، يرجى الانتظار قليلًا ثم المحاولة مرة أخرى",
+    ]),
+    ("geocoding_failed", [
+        "The address could not be found on the map",
+        "No se pudo encontrar la dirección en el mapa",
+        "Die Adresse wurde auf der Karte nicht gefunden",
+        "L'adresse est introuvable sur la carte",
+        "无法在地图上找到该地址",
+        "تعذر العثور على العنوان على الخريطة",
+    ]),
+    ("upstream_unavailable", [
+        "A service we depend on is unavailable, please try again later",
+        "Un servicio externo no está disponible, inténtalo más tarde",
+        "Ein benötigter Dienst ist nicht erreichbar, bitte versuche es später erneut",
+        "Un service externe est indisponible, veuillez réessayer plus tard",
+        "依赖的服务暂时不可用，请稍后再试",
+        "إحدى الخدمات غير متاحة حاليًا، يرجى المحاولة لاحقًا",
+    ]),
+    ("internal_error", [
+        "Something went wrong on our side, please try again",
+        "Algo salió mal de nuestro lado, inténtalo de nuevo",
+        "Bei uns ist etwas schiefgelaufen, bitte versuche es erneut",
+        "Une erreur s'est produite de notre côté, veuillez réessayer",
+        "服务器出错了，请重试",
+        "حدث خطأ من جانبنا، يرجى المحاولة مرة أخرى",
+    ]),
+    // Field-level messages
+    ("required", [
+        "This field is required",
+        "Este campo es obligatorio",
+        "Dieses Feld ist erforderlich",
+        "Ce champ est obligatoire",
+        "此字段为必填项",
+        "هذا الحقل مطلوب",
+    ]),
+    ("invalid", [
+        "This value is not valid",
+        "Este valor no es válido",
+        "Dieser Wert ist ungültig",
+        "Cette valeur n'est pas valide",
+        "该值无效",
+        "هذه القيمة غير صالحة",
+    ]),
+    ("too_long", [
+        "This value is too long",
+        "Este valor es demasiado largo",
+        "Dieser Wert ist zu lang",
+        "Cette valeur est trop longue",
+        "该值过长",
+        "هذه القيمة طويلة جدًا",
+    ]),
+    ("out_of_range", [
+        "This value is out of range",
+        "Este valor está fuera de rango",
+        "Dieser Wert liegt außerhalb des zulässigen Bereichs",
+        "Cette valeur est hors limites",
+        "该值超出范围",
+        "هذه القيمة خارج النطاق المسموح",
+    ]),
+    ("unknown", [
+        "This doesn't match anything we know of",
+        "No coincide con nada conocido",
+        "Dazu wurde nichts gefunden",
+        "Cela ne correspond à rien de connu",
+        "找不到匹配的内容",
+        "لا يطابق أي شيء معروف",
+    ]),
+    ("taken", [
+        "This is already in use",
+        "Ya está en uso",
+        "Wird bereits verwendet",
+        "Déjà utilisé",
+        "已被使用",
+        "مستخدم بالفعل",
+    ]),
+];
+
+fn localize(key: &str, lang: &str) -> &'static str {
+    let column = MESSAGE_LANGUAGES.iter().position(|l| *l == lang).unwrap_or(0);
+    MESSAGES
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map_or("", |(_, messages)| messages[column])
+}
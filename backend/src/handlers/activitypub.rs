@@ -0,0 +1,341 @@
+use axum::{
+    body::Bytes,
+    extract::{OriginalUri, Path, Query, State},
+    http::{header, HeaderMap, Method, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{events::fetch_recent_events, posts::fetch_recent_posts};
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldErrorCode},
+    models::Language,
+    services::{
+        activitypub::{
+            self, actor_url, create_activity, key_id, RemoteActor, ACTIVITYSTREAMS_CONTEXT, ACTIVITY_JSON,
+            SECURITY_CONTEXT,
+        },
+        notices::ContentKind,
+    },
+};
+
+const SITE_NAME: &str = "Lancaster Local";
+const JRD_JSON: &str = "application/jrd+json";
+// The outbox lists this many of the newest items and leaves older ones to the site
+const OUTBOX_SIZE: i64 = 20;
+
+#[derive(Deserialize)]
+pub struct WebFingerQuery {
+    pub resource: Option<String>,
+}
+
+/// Resolves `acct:{lang}@{host}` (or an actor's URL) to the language's actor, which is
+/// how a fediverse search for @en@host finds something to follow.
+pub async fn webfinger(
+    State(state): State<SharedState>,
+    Query(params): Query<WebFingerQuery>,
+) -> Result<Response, ApiError> {
+    let resource = params
+        .resource
+        .filter(|r| !r.is_empty())
+        .ok_or(ApiError::field("resource", FieldErrorCode::Required))?;
+    let authority = site_authority(&state.public_url).ok_or_else(|| {
+        tracing::error!("PUBLIC_URL {} has no host", state.public_url);
+        ApiError::Internal
+    })?;
+
+    let lang = match resource.strip_prefix("acct:") {
+        Some(account) => match account.rsplit_once('@') {
+            Some((user, domain)) if domain.eq_ignore_ascii_case(&authority) => user.to_string(),
+            _ => return Err(ApiError::NotFound),
+        },
+        None => resource
+            .strip_prefix(&actor_url(&state.public_url, ""))
+            .ok_or(ApiError::NotFound)?
+            .to_string(),
+    };
+    let language = fetch_actor_language(&state, &lang).await?;
+    let actor = actor_url(&state.public_url, &language.code);
+
+    let body = json!({
+        "subject": format!("acct:{}@{}", language.code, authority),
+        "aliases": [actor],
+        "links": [
+            {
+                "rel": "self",
+                "type": ACTIVITY_JSON,
+                "href": actor
+            },
+            {
+                "rel": "http://webfinger.net/rel/profile-page",
+                "type": "text/html",
+                "href": site_url(&state.public_url, &language.code)
+            }
+        ]
+    });
+    Ok(([(header::CONTENT_TYPE, JRD_JSON)], Json(body)).into_response())
+}
+
+/// The language's actor: a bot account posting everything published, in that language.
+pub async fn get_actor(
+    State(state): State<SharedState>,
+    Path(lang): Path<String>,
+) -> Result<Response, ApiError> {
+    let language = fetch_actor_language(&state, &lang).await?;
+    let keys = state.actor_keys.get(&state.pool).await.map_err(|e| {
+        tracing::error!("Failed to load the ActivityPub key: {:?}", e);
+        ApiError::Internal
+    })?;
+    let actor = actor_url(&state.public_url, &language.code);
+
+    Ok(activity_json(json!({
+        "@context": [ACTIVITYSTREAMS_CONTEXT, SECURITY_CONTEXT],
+        "id": actor,
+        "type": "Service",
+        "preferredUsername": language.code,
+        "name": format!("{} ({})", SITE_NAME, language.native_name),
+        "summary": format!(
+            "<p>Community news and events from {}, in {}.</p>",
+            SITE_NAME, language.name
+        ),
+        "url": site_url(&state.public_url, &language.code),
+        "inbox": format!("{}/inbox", actor),
+        "outbox": format!("{}/outbox", actor),
+        "followers": format!("{}/followers", actor),
+        "manuallyApprovesFollowers": false,
+        "discoverable": true,
+        "publicKey": {
+            "id": key_id(&state.public_url, &language.code),
+            "owner": actor,
+            "publicKeyPem": keys.public_key_pem
+        }
+    })))
+}
+
+/// The newest posts and events as `Create` activities, localized into the actor's language.
+pub async fn actor_outbox(
+    State(state): State<SharedState>,
+    Path(lang): Path<String>,
+) -> Result<Response, ApiError> {
+    let language = fetch_actor_language(&state, &lang).await?;
+    let posts = fetch_recent_posts(&state, &language.code, OUTBOX_SIZE).await?;
+    let events = fetch_recent_events(&state, &language.code, OUTBOX_SIZE).await?;
+
+    let mut items: Vec<_> = posts
+        .iter()
+        .map(|post| (post.created_at, activitypub::note(&state.public_url, &language.code, post)))
+        .chain(
+            events
+                .iter()
+                .map(|event| (event.created_at, activitypub::event(&state.public_url, &language.code, event))),
+        )
+        .collect();
+    items.sort_by_key(|(created_at, _)| std::cmp::Reverse(*created_at));
+    items.truncate(OUTBOX_SIZE as usize);
+
+    let total: i64 = sqlx::query_scalar(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM posts WHERE published = true AND NOT hidden AND archived_at IS NULL) +
+            (SELECT COUNT(*) FROM events WHERE published = true AND NOT hidden AND archived_at IS NULL)
+        "#,
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to count outbox items: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(activity_json(json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": format!("{}/outbox", actor_url(&state.public_url, &language.code)),
+        "type": "OrderedCollection",
+        "totalItems": total,
+        "orderedItems": items.into_iter().map(|(_, object)| create_activity(object)).collect::<Vec<_>>()
+    })))
+}
+
+/// How many follow the actor; who they are isn't published.
+pub async fn actor_followers(
+    State(state): State<SharedState>,
+    Path(lang): Path<String>,
+) -> Result<Response, ApiError> {
+    let language = fetch_actor_language(&state, &lang).await?;
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM activitypub_followers WHERE language_code = $1")
+        .bind(&language.code)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to count followers: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    Ok(activity_json(json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": format!("{}/followers", actor_url(&state.public_url, &language.code)),
+        "type": "OrderedCollection",
+        "totalItems": total
+    })))
+}
+
+pub async fn get_actor_post(
+    State(state): State<SharedState>,
+    Path((lang, id)): Path<(String, i32)>,
+) -> Result<Response, ApiError> {
+    actor_object(&state, &lang, ContentKind::Post, id).await
+}
+
+pub async fn get_actor_event(
+    State(state): State<SharedState>,
+    Path((lang, id)): Path<(String, i32)>,
+) -> Result<Response, ApiError> {
+    actor_object(&state, &lang, ContentKind::Event, id).await
+}
+
+async fn actor_object(state: &SharedState, lang: &str, kind: ContentKind, id: i32) -> Result<Response, ApiError> {
+    let language = fetch_actor_language(state, lang).await?;
+    let mut object = activitypub::localized_object(state, &language.code, kind, id)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    object["@context"] = json!(ACTIVITYSTREAMS_CONTEXT);
+    Ok(activity_json(object))
+}
+
+/// Takes Follow and Undo of a Follow; anything else is accepted and ignored. Requests
+/// must carry a valid HTTP signature from the actor they claim to come from.
+pub async fn actor_inbox(
+    State(state): State<SharedState>,
+    Path(lang): Path<String>,
+    method: Method,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, ApiError> {
+    let language = fetch_actor_language(&state, &lang).await?;
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or_else(|| uri.path());
+
+    let sender = activitypub::verify_request(&state.remote_keys, method.as_str(), path, &headers, &body)
+        .await
+        .map_err(|e| {
+            tracing::debug!("Refused a {} inbox request: {:?}", language.code, e);
+            ApiError::Unauthorized
+        })?;
+    let activity: serde_json::Value = serde_json::from_slice(&body).map_err(|_| ApiError::BadRequest)?;
+    if activity["actor"].as_str() != Some(sender.id.as_str()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    match activity["type"].as_str() {
+        Some("Follow") => follow(&state, &language.code, &sender, &activity).await?,
+        Some("Undo") if activity["object"]["type"].as_str() == Some("Follow") => {
+            unfollow(&state, &language.code, &sender).await?
+        }
+        other => tracing::debug!("Ignoring {:?} activity from {}", other, sender.id),
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn follow(
+    state: &SharedState,
+    lang: &str,
+    sender: &RemoteActor,
+    activity: &serde_json::Value,
+) -> Result<(), ApiError> {
+    let actor = actor_url(&state.public_url, lang);
+    let object = activity["object"].as_str().or(activity["object"]["id"].as_str());
+    if object != Some(actor.as_str()) {
+        return Err(ApiError::BadRequest);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO activitypub_followers (language_code, actor_id, inbox_url, shared_inbox_url)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (language_code, actor_id) DO UPDATE SET
+            inbox_url = EXCLUDED.inbox_url,
+            shared_inbox_url = EXCLUDED.shared_inbox_url
+        "#,
+    )
+    .bind(lang)
+    .bind(&sender.id)
+    .bind(&sender.inbox)
+    .bind(&sender.shared_inbox)
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to store follower: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let accept = json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": format!("{}#accepts/{}", actor, Uuid::new_v4()),
+        "type": "Accept",
+        "actor": actor,
+        "object": activity
+    });
+    activitypub::enqueue_delivery(&state.pool, lang, &sender.inbox, &accept)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to queue Accept for {}: {:?}", sender.id, e);
+            ApiError::Internal
+        })?;
+
+    tracing::info!("{} followed {}", sender.id, lang);
+    Ok(())
+}
+
+async fn unfollow(state: &SharedState, lang: &str, sender: &RemoteActor) -> Result<(), ApiError> {
+    sqlx::query("DELETE FROM activitypub_followers WHERE language_code = $1 AND actor_id = $2")
+        .bind(lang)
+        .bind(&sender.id)
+        .execute(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to remove follower: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    tracing::info!("{} unfollowed {}", sender.id, lang);
+    Ok(())
+}
+
+// Only languages the site is offered in have actors
+async fn fetch_actor_language(state: &SharedState, lang: &str) -> Result<Language, ApiError> {
+    sqlx::query_as::<_, Language>(
+        "SELECT code, name, native_name, is_rtl, text_direction, enabled FROM supported_languages WHERE code = $1 AND enabled = true",
+    )
+    .bind(lang)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch language: {:?}", e);
+        ApiError::Internal
+    })?
+    .ok_or(ApiError::NotFound)
+}
+
+// The host (and port, if any) accounts are named after: en@{authority}
+fn site_authority(public_url: &str) -> Option<String> {
+    let url = Url::parse(public_url).ok()?;
+    let host = url.host_str()?;
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+fn site_url(public_url: &str, lang: &str) -> String {
+    format!("{}/?lang={}", public_url, lang)
+}
+
+fn activity_json(body: serde_json::Value) -> Response {
+    ([(header::CONTENT_TYPE, ACTIVITY_JSON)], Json(body)).into_response()
+}
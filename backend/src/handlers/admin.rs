@@ -1,25 +1,53 @@
 use axum::{
-    extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    extract::{Path, Query, State},
+    http::HeaderMap,
     Json,
 };
 use base64::{Engine as _, engine::general_purpose};
-use chrono::{Duration, Utc};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use serde::Deserialize;
 use serde_json::json;
 
 use crate::{
     db::SharedState,
-    models::{AdminLoginRequest, AdminLoginResponse, AdminSession, Event, Post},
+    error::{ApiError, FieldError, FieldErrorCode},
+    middleware::client_ip::ClientIp,
+    models::{
+        AdminEvent, AdminLoginRequest, AdminLoginResponse, AdminPost, AdminSession, Event,
+        EventOccurrenceOverride, OccurrenceOverrideRequest, PinPostRequest, Post, WebhookEvent,
+    },
+    services::{
+        notices::{ContentChange, ContentKind},
+        recurrence::RecurrenceRule,
+        webhooks,
+    },
 };
 
+use super::posts::invalidate_published_post_counts;
+
+// After this many wrong passwords from one address, it's locked out for the window
+const MAX_LOGIN_FAILURES: u32 = 5;
+const LOGIN_FAILURE_WINDOW: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
 pub async fn admin_login(
     State(state): State<SharedState>,
+    client: ClientIp,
     Json(payload): Json<AdminLoginRequest>,
-) -> Result<Json<AdminLoginResponse>, StatusCode> {
+) -> Result<Json<AdminLoginResponse>, ApiError> {
+    let failures_key = format!("login_failures:{}", client.bucket_key());
+    if let Some((failures, retry_after)) = state.rate_limiter.count(&failures_key).await {
+        if failures >= MAX_LOGIN_FAILURES {
+            return Err(ApiError::RateLimited(retry_after));
+        }
+    }
+
     // Simple password check
     if payload.password != state.admin_password {
-        return Err(StatusCode::UNAUTHORIZED);
+        let (failures, _) = state.rate_limiter.increment(&failures_key, LOGIN_FAILURE_WINDOW).await;
+        tracing::warn!("Failed admin login from {} ({} in window)", client, failures);
+        return Err(ApiError::Unauthorized);
     }
+    state.rate_limiter.reset(&failures_key).await;
 
     // Generate session token
     let token = general_purpose::STANDARD.encode(rand::random::<[u8; 32]>());
@@ -35,7 +63,7 @@ pub async fn admin_login(
     .await
     .map_err(|e| {
         tracing::error!("Failed to create session: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        ApiError::Internal
     })?;
 
     Ok(Json(AdminLoginResponse { token }))
@@ -44,39 +72,59 @@ pub async fn admin_login(
 pub async fn admin_logout(
     State(state): State<SharedState>,
     headers: HeaderMap,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     let token = headers
         .get("Authorization")
         .and_then(|h| h.to_str().ok())
         .and_then(|h| h.strip_prefix("Bearer "))
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+        .ok_or(ApiError::Unauthorized)?;
 
     sqlx::query("DELETE FROM admin_sessions WHERE session_token = $1")
         .bind(token)
         .execute(&state.pool)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| ApiError::Internal)?;
 
     Ok(Json(json!({
         "message": "Logged out successfully"
     })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AdminQueueQuery {
+    pub sort: Option<String>, // "newest" (default) or "score", highest first
+    pub quarantined: Option<bool>,
+}
+
+impl AdminQueueQuery {
+    fn by_score(&self) -> Result<bool, ApiError> {
+        match self.sort.as_deref() {
+            None | Some("newest") => Ok(false),
+            Some("score") => Ok(true),
+            Some(_) => Err(ApiError::field("sort", FieldErrorCode::Invalid)),
+        }
+    }
+}
+
 pub async fn get_admin_posts(
     State(state): State<SharedState>,
     headers: HeaderMap,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    Query(params): Query<AdminQueueQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Verify admin session
     verify_admin_session(&state, &headers).await?;
 
-    let posts = sqlx::query_as::<_, Post>(
-        "SELECT * FROM posts ORDER BY created_at DESC"
-    )
+    let order = if params.by_score()? { "spam_score DESC, created_at DESC" } else { "created_at DESC" };
+    let posts = sqlx::query_as::<_, AdminPost>(&format!(
+        "SELECT * FROM posts WHERE ($1::boolean IS NULL OR quarantined = $1) ORDER BY {}",
+        order
+    ))
+    .bind(params.quarantined)
     .fetch_all(&state.pool)
     .await
     .map_err(|e| {
         tracing::error!("Failed to fetch posts: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        ApiError::Internal
     })?;
 
     Ok(Json(json!({
@@ -87,18 +135,26 @@ pub async fn get_admin_posts(
 pub async fn get_admin_events(
     State(state): State<SharedState>,
     headers: HeaderMap,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    Query(params): Query<AdminQueueQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Verify admin session
     verify_admin_session(&state, &headers).await?;
 
-    let events = sqlx::query_as::<_, Event>(
-        "SELECT * FROM events ORDER BY event_date DESC, event_time DESC"
-    )
+    let order = if params.by_score()? {
+        "spam_score DESC, created_at DESC"
+    } else {
+        "event_date DESC, event_time DESC"
+    };
+    let events = sqlx::query_as::<_, AdminEvent>(&format!(
+        "SELECT * FROM events WHERE ($1::boolean IS NULL OR quarantined = $1) ORDER BY {}",
+        order
+    ))
+    .bind(params.quarantined)
     .fetch_all(&state.pool)
     .await
     .map_err(|e| {
         tracing::error!("Failed to fetch events: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        ApiError::Internal
     })?;
 
     Ok(Json(json!({
@@ -110,25 +166,106 @@ pub async fn delete_post(
     State(state): State<SharedState>,
     headers: HeaderMap,
     Path(id): Path<i32>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Verify admin session
     verify_admin_session(&state, &headers).await?;
 
-    let result = sqlx::query("DELETE FROM posts WHERE id = $1")
+    let post = sqlx::query_as::<_, Post>("DELETE FROM posts WHERE id = $1 RETURNING *")
         .bind(id)
-        .execute(&state.pool)
+        .fetch_optional(&state.pool)
         .await
         .map_err(|e| {
             tracing::error!("Failed to delete post: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+            ApiError::Internal
+        })?
+        .ok_or(ApiError::NotFound)?;
+
+    invalidate_published_post_counts(&state).await;
+    state.notifier.content(ContentChange::Deleted, ContentKind::Post, id).await;
+    webhooks::enqueue_post(&state.pool, WebhookEvent::PostDeleted, &post).await;
+
+    Ok(Json(json!({
+        "message": "Post deleted successfully"
+    })))
+}
+
+pub async fn pin_post(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Json(payload): Json<PinPostRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let mut errors = FieldError::check(&payload);
+    if payload.pinned_until.is_some_and(|until| until <= Utc::now()) {
+        errors.push(FieldError { field: "pinned_until".to_string(), code: FieldErrorCode::OutOfRange });
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    // Re-pinning keeps the original pinned_at, so adjusting a pin doesn't reorder it
+    let result = sqlx::query(
+        r#"
+        UPDATE posts SET
+            pinned_at = COALESCE(pinned_at, NOW()),
+            pinned_until = $2,
+            pin_priority = $3,
+            emergency = $4
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(payload.pinned_until)
+    .bind(payload.priority)
+    .bind(payload.emergency)
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to pin post: {:?}", e);
+        ApiError::Internal
+    })?;
 
     if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::NotFound);
     }
 
+    state.notifier.content(ContentChange::Updated, ContentKind::Post, id).await;
+
     Ok(Json(json!({
-        "message": "Post deleted successfully"
+        "message": "Post pinned successfully"
+    })))
+}
+
+pub async fn unpin_post(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let result = sqlx::query(
+        "UPDATE posts SET pinned_at = NULL, pinned_until = NULL, pin_priority = 0, emergency = false WHERE id = $1",
+    )
+    .bind(id)
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to unpin post: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    state.notifier.content(ContentChange::Updated, ContentKind::Post, id).await;
+
+    Ok(Json(json!({
+        "message": "Post unpinned successfully"
     })))
 }
 
@@ -136,45 +273,144 @@ pub async fn delete_event(
     State(state): State<SharedState>,
     headers: HeaderMap,
     Path(id): Path<i32>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Verify admin session
     verify_admin_session(&state, &headers).await?;
 
-    let result = sqlx::query("DELETE FROM events WHERE id = $1")
+    let event = sqlx::query_as::<_, Event>("DELETE FROM events WHERE id = $1 RETURNING *")
         .bind(id)
-        .execute(&state.pool)
+        .fetch_optional(&state.pool)
         .await
         .map_err(|e| {
             tracing::error!("Failed to delete event: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+            ApiError::Internal
+        })?
+        .ok_or(ApiError::NotFound)?;
+
+    state.notifier.content(ContentChange::Deleted, ContentKind::Event, id).await;
+    webhooks::enqueue_event(&state.pool, WebhookEvent::EventDeleted, &event).await;
+
+    Ok(Json(json!({
+        "message": "Event deleted successfully"
+    })))
+}
+
+pub async fn upsert_occurrence_override(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path((id, date)): Path<(i32, NaiveDate)>,
+    Json(payload): Json<OccurrenceOverrideRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let event = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch event: {:?}", e);
+            ApiError::Internal
+        })?
+        .ok_or(ApiError::NotFound)?;
+
+    // Only dates the series actually produces can be overridden
+    let rule = event
+        .recurrence_rule
+        .as_deref()
+        .and_then(|r| RecurrenceRule::parse(r).ok())
+        .ok_or(ApiError::BadRequest)?;
+    if !rule.occurs_on(event.event_date, &event.recurrence_exdates, date) {
+        return Err(ApiError::NotFound);
+    }
+
+    let event_time = payload
+        .event_time
+        .as_ref()
+        .map(|t| NaiveTime::parse_from_str(t, "%H:%M:%S"))
+        .transpose()
+        .map_err(|_| ApiError::field("event_time", FieldErrorCode::Invalid))?;
+
+    let occurrence = sqlx::query_as::<_, EventOccurrenceOverride>(
+        r#"
+        INSERT INTO event_occurrence_overrides (event_id, occurrence_date, cancelled, event_time, location)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (event_id, occurrence_date)
+        DO UPDATE SET
+            cancelled = EXCLUDED.cancelled,
+            event_time = EXCLUDED.event_time,
+            location = EXCLUDED.location,
+            updated_at = NOW()
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(date)
+    .bind(payload.cancelled)
+    .bind(event_time)
+    .bind(&payload.location)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to save occurrence override: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    state.notifier.content(ContentChange::Updated, ContentKind::Event, id).await;
+
+    Ok(Json(json!({
+        "occurrence": occurrence
+    })))
+}
+
+pub async fn delete_occurrence_override(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path((id, date)): Path<(i32, NaiveDate)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let result = sqlx::query(
+        "DELETE FROM event_occurrence_overrides WHERE event_id = $1 AND occurrence_date = $2"
+    )
+    .bind(id)
+    .bind(date)
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to delete occurrence override: {:?}", e);
+        ApiError::Internal
+    })?;
 
     if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::NotFound);
     }
 
+    state.notifier.content(ContentChange::Updated, ContentKind::Event, id).await;
+
     Ok(Json(json!({
-        "message": "Event deleted successfully"
+        "message": "Occurrence override removed successfully"
     })))
 }
 
-async fn verify_admin_session(
+pub(super) async fn verify_admin_session(
     state: &SharedState,
     headers: &HeaderMap,
-) -> Result<(), StatusCode> {
+) -> Result<(), ApiError> {
     let token = headers
         .get("Authorization")
         .and_then(|h| h.to_str().ok())
         .and_then(|h| h.strip_prefix("Bearer "))
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+        .ok_or(ApiError::Unauthorized)?;
 
-    let session = sqlx::query_as::<_, AdminSession>(
+    sqlx::query_as::<_, AdminSession>(
         "SELECT * FROM admin_sessions WHERE session_token = $1 AND expires_at > NOW()"
     )
     .bind(token)
     .fetch_one(&state.pool)
     .await
-    .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    .map_err(|_| ApiError::Unauthorized)?;
 
     Ok(())
 }
\ No newline at end of file
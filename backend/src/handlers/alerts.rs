@@ -0,0 +1,317 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use chrono::Utc;
+use futures_util::{future::try_join_all, stream, Stream, StreamExt};
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::{admin::verify_admin_session, languages::is_enabled_language, posts::LangQuery};
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldError, FieldErrorCode},
+    models::{Alert, CreateAlertRequest, LocalizedAlert},
+    services::{
+        notices::Notice,
+        translation::{detect_text_direction, TranslationService},
+    },
+};
+
+// Active alerts in $1, most severe first
+const ACTIVE_ALERTS_SELECT: &str = r#"
+        SELECT
+            a.id,
+            COALESCE(at.title, a.title) AS title,
+            COALESCE(at.body, a.body) AS body,
+            a.severity,
+            a.original_language,
+            COALESCE(at.text_direction, a.text_direction) AS text_direction,
+            a.expires_at,
+            a.created_at
+        FROM alerts a
+        LEFT JOIN alert_translations at ON at.alert_id = a.id AND at.language_code = $1
+        WHERE a.ended_at IS NULL AND a.expires_at > NOW()"#;
+
+const SEVERITY_ORDER: &str =
+    "array_position(ARRAY['minor', 'moderate', 'severe', 'extreme']::varchar[], a.severity) DESC, a.created_at DESC";
+
+pub async fn get_active_alerts(
+    State(state): State<SharedState>,
+    Query(params): Query<LangQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+    let alerts = fetch_active_alerts(&state, &lang).await?;
+
+    Ok(Json(json!({
+        "alerts": alerts
+    })))
+}
+
+/// Server-Sent Events: the active alerts on connect, then `alert` as alerts go live and
+/// `alert_ended` when a moderator ends one. Expiry isn't announced; clients have `expires_at`.
+pub async fn alert_stream(
+    State(state): State<SharedState>,
+    Query(params): Query<LangQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+
+    // Subscribe first, so nothing published while the active alerts load is missed
+    let notices = state.notifier.subscribe();
+    let active = fetch_active_alerts(&state, &lang).await?;
+
+    let initial = stream::iter(active.into_iter().map(|alert| Ok(alert_event(&alert))));
+    let live = stream::unfold((state, lang, notices), |(state, lang, mut notices)| async move {
+        loop {
+            let event = match notices.recv().await {
+                Ok(Notice::AlertPublished { id }) => match fetch_active_alert(&state, &lang, id).await {
+                    Ok(Some(alert)) => alert_event(&alert),
+                    // Already ended, or logged
+                    Ok(None) | Err(_) => continue,
+                },
+                Ok(Notice::AlertEnded { id }) => Event::default().event("alert_ended").data(json!({ "id": id }).to_string()),
+                // Content changes are for /api/stream
+                Ok(Notice::Content { .. }) => continue,
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("Alert stream fell behind by {} notices", missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => return None,
+            };
+            return Some((Ok(event), (state, lang, notices)));
+        }
+    });
+
+    Ok(Sse::new(initial.chain(live)).keep_alive(KeepAlive::default()))
+}
+
+fn alert_event(alert: &LocalizedAlert) -> Event {
+    Event::default()
+        .event("alert")
+        .data(serde_json::to_string(alert).unwrap_or_default())
+}
+
+async fn fetch_active_alerts(state: &SharedState, lang: &str) -> Result<Vec<LocalizedAlert>, ApiError> {
+    sqlx::query_as::<_, LocalizedAlert>(&format!("{ACTIVE_ALERTS_SELECT} ORDER BY {SEVERITY_ORDER}"))
+        .bind(lang)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch alerts: {:?}", e);
+            ApiError::Internal
+        })
+}
+
+async fn fetch_active_alert(state: &SharedState, lang: &str, id: i32) -> Result<Option<LocalizedAlert>, ApiError> {
+    sqlx::query_as::<_, LocalizedAlert>(&format!("{ACTIVE_ALERTS_SELECT} AND a.id = $2"))
+        .bind(lang)
+        .bind(id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch alert {}: {:?}", id, e);
+            ApiError::Internal
+        })
+}
+
+pub async fn get_admin_alerts(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let alerts = sqlx::query_as::<_, Alert>("SELECT * FROM alerts ORDER BY created_at DESC LIMIT 100")
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch alerts: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    Ok(Json(json!({
+        "alerts": alerts
+    })))
+}
+
+/// Translates the alert into every enabled language and only then publishes it, so no
+/// reader gets it late or not at all.
+pub async fn create_alert(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateAlertRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let mut errors = FieldError::check(&payload);
+    let language = payload.language.clone().unwrap_or_else(|| "en".to_string());
+    if !is_enabled_language(&state, &language).await? {
+        errors.push(FieldError { field: "language".to_string(), code: FieldErrorCode::Unknown });
+    }
+    if payload.expires_at <= Utc::now() {
+        errors.push(FieldError { field: "expires_at".to_string(), code: FieldErrorCode::OutOfRange });
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let title = payload.title.trim();
+    let body = payload.body.trim();
+    let text_direction = match payload.text_direction.as_deref() {
+        Some("auto") | None => detect_text_direction(title, &language),
+        Some(direction) => direction.to_string(),
+    };
+
+    let translations = translate_alert(&state, title, body, &language).await?;
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        tracing::error!("Failed to start transaction: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let alert = sqlx::query_as::<_, Alert>(
+        r#"
+        INSERT INTO alerts (title, body, severity, original_language, text_direction, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(title)
+    .bind(body)
+    .bind(payload.severity)
+    .bind(&language)
+    .bind(&text_direction)
+    .bind(payload.expires_at)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create alert: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    for translation in &translations {
+        sqlx::query(
+            r#"
+            INSERT INTO alert_translations (alert_id, language_code, title, body, text_direction)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(alert.id)
+        .bind(&translation.language)
+        .bind(&translation.title)
+        .bind(&translation.body)
+        .bind(&translation.text_direction)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save alert translation: {:?}", e);
+            ApiError::Internal
+        })?;
+    }
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit alert: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    tracing::info!("Alert {} is live in {} languages", alert.id, translations.len() + 1);
+    state.notifier.publish(Notice::AlertPublished { id: alert.id }).await;
+
+    Ok(Json(json!({
+        "message": "Alert published successfully",
+        "alert": alert,
+        "languages": translations.len() + 1
+    })))
+}
+
+pub async fn end_alert(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let result = sqlx::query("UPDATE alerts SET ended_at = NOW() WHERE id = $1 AND ended_at IS NULL")
+        .bind(id)
+        .execute(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to end alert: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    state.notifier.publish(Notice::AlertEnded { id }).await;
+
+    Ok(Json(json!({
+        "message": "Alert ended successfully"
+    })))
+}
+
+struct AlertTranslation {
+    language: String,
+    title: String,
+    body: String,
+    text_direction: String,
+}
+
+// Every enabled language other than the original, all at once; any failure fails the lot
+async fn translate_alert(
+    state: &SharedState,
+    title: &str,
+    body: &str,
+    source: &str,
+) -> Result<Vec<AlertTranslation>, ApiError> {
+    let targets: Vec<String> =
+        sqlx::query_scalar("SELECT code FROM supported_languages WHERE enabled = true AND code != $1")
+            .bind(source)
+            .fetch_all(&state.pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to fetch languages: {:?}", e);
+                ApiError::Internal
+            })?;
+
+    if targets.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(api_key) = &state.google_api_key else {
+        tracing::error!("Alerts need GOOGLE_TRANSLATE_API_KEY while other languages are enabled");
+        return Err(ApiError::BadGateway);
+    };
+
+    let service = TranslationService::new(api_key.clone());
+    try_join_all(targets.into_iter().map(|language| {
+        let service = &service;
+        async move {
+            let mut results = service
+                .translate_batch(vec![title, body], &language, Some(source))
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to translate alert into {}: {:?}", language, e);
+                    ApiError::BadGateway
+                })?
+                .into_iter();
+            let (Some(title), Some(body)) = (results.next(), results.next()) else {
+                tracing::error!("Incomplete alert translation into {}", language);
+                return Err(ApiError::BadGateway);
+            };
+            Ok(AlertTranslation {
+                language,
+                title: title.translated_text,
+                text_direction: title.text_direction,
+                body: body.translated_text,
+            })
+        }
+    }))
+    .await
+}
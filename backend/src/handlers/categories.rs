@@ -0,0 +1,347 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+use super::admin::verify_admin_session;
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldError, FieldErrorCode},
+    models::{
+        Category, CategoryTranslation, CategoryWithTranslation, CreateCategoryRequest,
+        UpdateCategoryRequest,
+    },
+};
+
+lazy_static::lazy_static! {
+    static ref SLUG_REGEX: regex::Regex = regex::Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap();
+    static ref COLOR_REGEX: regex::Regex = regex::Regex::new(r"^#[0-9a-fA-F]{6}$").unwrap();
+}
+
+// The length of the icon column
+const MAX_ICON_CHARS: usize = 50;
+
+#[derive(Deserialize)]
+pub struct GetCategoriesQuery {
+    pub lang: Option<String>,
+}
+
+pub async fn get_categories(
+    State(state): State<SharedState>,
+    Query(params): Query<GetCategoriesQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+
+    // Fall back to the English name, then to the slug itself
+    let categories = sqlx::query_as::<_, CategoryWithTranslation>(
+        r#"
+        SELECT
+            c.slug,
+            COALESCE(ct.name, en.name, c.slug) as name,
+            c.color,
+            c.icon,
+            c.sort_order,
+            ct.id IS NOT NULL as is_translated
+        FROM categories c
+        LEFT JOIN category_translations ct ON c.slug = ct.category_slug AND ct.language_code = $1
+        LEFT JOIN category_translations en ON c.slug = en.category_slug AND en.language_code = 'en'
+        ORDER BY c.sort_order, c.slug
+        "#,
+    )
+    .bind(&lang)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch categories: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "categories": categories
+    })))
+}
+
+pub async fn get_admin_categories(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT * FROM categories ORDER BY sort_order, slug"
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch categories: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let translations = sqlx::query_as::<_, CategoryTranslation>(
+        "SELECT * FROM category_translations"
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch category translations: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let mut names: HashMap<String, HashMap<String, String>> = HashMap::new();
+    for t in translations {
+        names
+            .entry(t.category_slug)
+            .or_default()
+            .insert(t.language_code, t.name);
+    }
+
+    let categories: Vec<serde_json::Value> = categories
+        .into_iter()
+        .map(|c| {
+            let category_names = names.remove(&c.slug).unwrap_or_default();
+            json!({
+                "slug": c.slug,
+                "color": c.color,
+                "icon": c.icon,
+                "sort_order": c.sort_order,
+                "names": category_names,
+                "created_at": c.created_at,
+                "updated_at": c.updated_at,
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "categories": categories
+    })))
+}
+
+pub async fn create_category(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateCategoryRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let slug = payload.slug.trim().to_lowercase();
+    if slug.len() > 100 {
+        return Err(ApiError::field("slug", FieldErrorCode::TooLong));
+    }
+    if !SLUG_REGEX.is_match(&slug) {
+        return Err(ApiError::field("slug", FieldErrorCode::Invalid));
+    }
+    validate_color(payload.color.as_deref())?;
+    validate_icon(payload.icon.as_deref())?;
+    validate_names(&state, &payload.names).await?;
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        tracing::error!("Failed to start transaction: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let category = sqlx::query_as::<_, Category>(
+        r#"
+        INSERT INTO categories (slug, color, icon, sort_order)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (slug) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(&slug)
+    .bind(&payload.color)
+    .bind(&payload.icon)
+    .bind(payload.sort_order.unwrap_or(0))
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create category: {:?}", e);
+        ApiError::Internal
+    })?
+    .ok_or(ApiError::Conflict("slug"))?;
+
+    upsert_names(&mut tx, &slug, &payload.names).await?;
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit category: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "category": category,
+        "names": payload.names
+    })))
+}
+
+pub async fn update_category(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+    Json(payload): Json<UpdateCategoryRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    validate_color(payload.color.as_deref())?;
+    validate_icon(payload.icon.as_deref())?;
+    if let Some(names) = &payload.names {
+        validate_names(&state, names).await?;
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        tracing::error!("Failed to start transaction: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let category = sqlx::query_as::<_, Category>(
+        r#"
+        UPDATE categories SET
+            color = COALESCE($2, color),
+            icon = COALESCE($3, icon),
+            sort_order = COALESCE($4, sort_order),
+            updated_at = NOW()
+        WHERE slug = $1
+        RETURNING *
+        "#,
+    )
+    .bind(&slug)
+    .bind(&payload.color)
+    .bind(&payload.icon)
+    .bind(payload.sort_order)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update category: {:?}", e);
+        ApiError::Internal
+    })?
+    .ok_or(ApiError::NotFound)?;
+
+    if let Some(names) = &payload.names {
+        upsert_names(&mut tx, &slug, names).await?;
+    }
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit category: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "category": category
+    })))
+}
+
+pub async fn delete_category(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    // Events in this category are left uncategorized (ON DELETE SET NULL)
+    let result = sqlx::query("DELETE FROM categories WHERE slug = $1")
+        .bind(&slug)
+        .execute(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete category: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(Json(json!({
+        "message": "Category deleted successfully"
+    })))
+}
+
+fn validate_color(color: Option<&str>) -> Result<(), ApiError> {
+    match color {
+        Some(c) if !COLOR_REGEX.is_match(c) => Err(ApiError::field("color", FieldErrorCode::Invalid)),
+        _ => Ok(()),
+    }
+}
+
+fn validate_icon(icon: Option<&str>) -> Result<(), ApiError> {
+    match icon {
+        Some(i) if i.chars().count() > MAX_ICON_CHARS => Err(ApiError::field("icon", FieldErrorCode::TooLong)),
+        _ => Ok(()),
+    }
+}
+
+async fn validate_names(
+    state: &SharedState,
+    names: &HashMap<String, String>,
+) -> Result<(), ApiError> {
+    let mut errors: Vec<FieldError> = names
+        .iter()
+        .filter_map(|(code, name)| {
+            let error = if name.trim().is_empty() {
+                FieldErrorCode::Required
+            } else if name.chars().count() > 100 {
+                FieldErrorCode::TooLong
+            } else {
+                return None;
+            };
+            Some(FieldError { field: format!("names.{}", code), code: error })
+        })
+        .collect();
+
+    let codes: Vec<String> = names.keys().cloned().collect();
+    let known: Vec<String> = sqlx::query_scalar(
+        "SELECT code FROM supported_languages WHERE code = ANY($1)"
+    )
+    .bind(&codes)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to check languages: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    errors.extend(codes.iter().filter(|c| !known.contains(c)).map(|c| FieldError {
+        field: format!("names.{}", c),
+        code: FieldErrorCode::Unknown,
+    }));
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ApiError::Validation(errors))
+    }
+}
+
+async fn upsert_names(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    slug: &str,
+    names: &HashMap<String, String>,
+) -> Result<(), ApiError> {
+    for (language_code, name) in names {
+        sqlx::query(
+            r#"
+            INSERT INTO category_translations (category_slug, language_code, name)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (category_slug, language_code)
+            DO UPDATE SET name = EXCLUDED.name
+            "#,
+        )
+        .bind(slug)
+        .bind(language_code)
+        .bind(name.trim())
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save category name: {:?}", e);
+            ApiError::Internal
+        })?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldErrorCode},
+    middleware::client_ip::ClientIp,
+};
+
+// Routes whose submissions can be put behind a challenge
+const CHALLENGE_ROUTES: [&str; 2] = ["posts", "events"];
+
+#[derive(Debug, Deserialize)]
+pub struct ChallengeQuery {
+    pub route: Option<String>,
+}
+
+/// Issues a challenge for submitting to `route`, if that route requires one.
+pub async fn get_challenge(
+    State(state): State<SharedState>,
+    Query(params): Query<ChallengeQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let route = match params.route.as_deref() {
+        None => return Err(ApiError::field("route", FieldErrorCode::Required)),
+        Some(route) if !CHALLENGE_ROUTES.contains(&route) => {
+            return Err(ApiError::field("route", FieldErrorCode::Invalid))
+        }
+        Some(route) => route,
+    };
+
+    if !state.challenges.required_for(route) {
+        return Ok(Json(json!({
+            "required": false,
+            "challenge": null
+        })));
+    }
+
+    let challenge = state.challenges.provider.issue().await.map_err(|e| {
+        tracing::error!("Failed to issue challenge: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "required": true,
+        "challenge": challenge
+    })))
+}
+
+/// Rejects a submission to `route` without a solved challenge when the route requires one.
+pub(super) async fn verify_challenge(
+    state: &SharedState,
+    route: &str,
+    response: Option<&str>,
+    client: &ClientIp,
+) -> Result<(), ApiError> {
+    if !state.challenges.required_for(route) {
+        return Ok(());
+    }
+    let Some(response) = response.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Err(ApiError::field("challenge_response", FieldErrorCode::Required));
+    };
+
+    let solved = state.challenges.provider.verify(response, client.0).await.map_err(|e| {
+        tracing::error!("Failed to verify challenge: {:?}", e);
+        ApiError::BadGateway
+    })?;
+
+    if !solved {
+        tracing::info!("Unsolved challenge for {} from {}", route, client);
+        return Err(ApiError::field("challenge_response", FieldErrorCode::Invalid));
+    }
+    Ok(())
+}
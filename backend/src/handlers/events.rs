@@ -1,146 +1,456 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::HeaderMap,
     Json,
 };
-use chrono::NaiveDate;
+use chrono::{Duration, Months, NaiveDate, NaiveTime, Utc};
 use serde::Deserialize;
 use serde_json::json;
+use sqlx::{Postgres, QueryBuilder};
 
+use super::{
+    admin::verify_admin_session,
+    challenge::verify_challenge,
+    languages::is_enabled_language,
+    posts::{available_languages, LangQuery},
+    uploads::verify_image,
+};
 use crate::{
     db::SharedState,
-    models::{CreateEventRequest, Event, EventWithTranslation},
-    services::translation::detect_text_direction,
+    error::{ApiError, FieldError, FieldErrorCode},
+    middleware::client_ip::ClientIp,
+    models::{CreateEventRequest, Event, EventOccurrenceOverride, EventWithTranslation, WebhookEvent},
+    services::{
+        activitypub,
+        notices::{ContentChange, ContentKind},
+        recurrence::{expand_events, RecurrenceRule},
+        slug::assign_slug,
+        spam::{self, Submission},
+        translation::detect_text_direction,
+        urls::normalize_url,
+        webhooks,
+    },
 };
 
+// How far ahead recurring series are expanded when no month is requested
+const DEFAULT_RECURRENCE_WINDOW_DAYS: i64 = 90;
+
+const DEFAULT_EVENTS_PAGE_SIZE: i64 = 20;
+const MAX_EVENTS_PAGE_SIZE: i64 = 100;
+
+const DEFAULT_NEAR_RADIUS_KM: f64 = 5.0;
+const MAX_NEAR_RADIUS_KM: f64 = 100.0;
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
 #[derive(Deserialize)]
 pub struct GetEventsQuery {
     pub lang: Option<String>,
     pub month: Option<String>, // Format: YYYY-MM
-    pub category: Option<String>,
+    pub from: Option<NaiveDate>, // Format: YYYY-MM-DD
+    pub to: Option<NaiveDate>,   // Format: YYYY-MM-DD
+    #[serde(default)]
+    pub upcoming: bool,
+    pub is_free: Option<bool>,
+    pub location: Option<String>,
+    pub category: Option<String>, // Comma-separated list
+    pub bbox: Option<String>,     // Format: min_lon,min_lat,max_lon,max_lat
+    pub near: Option<String>,     // Format: lat,lon
+    pub radius: Option<f64>,      // Kilometers, used with near
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
 }
 
 pub async fn get_events(
     State(state): State<SharedState>,
     Query(params): Query<GetEventsQuery>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     let lang = params.lang.unwrap_or_else(|| "en".to_string());
-    
-    let mut query = r#"
-        SELECT 
-            e.id,
-            e.organizer_name,
-            COALESCE(et.title, e.title) as title,
-            COALESCE(et.description, e.description) as description,
-            e.title as original_title,
-            e.description as original_description,
-            e.event_date,
-            e.event_time,
-            e.location,
-            e.category,
-            e.is_free,
-            e.ticket_url,
-            e.original_language,
-            e.text_direction as original_text_direction,
-            COALESCE(et.text_direction, e.text_direction) as text_direction,
-            CASE WHEN et.id IS NOT NULL AND e.original_language != $1 THEN true ELSE false END as is_translated,
-            e.created_at
-        FROM events e
-        LEFT JOIN event_translations et ON e.id = et.event_id AND et.language_code = $1
-        WHERE e.published = true
-    "#.to_string();
+    let today = Utc::now().date_naive();
 
-    let mut bind_count = 1;
-    let mut conditions = vec![];
+    // Narrow the requested range by month and upcoming, whichever are given
+    let mut from = params.from;
+    let mut to = params.to;
 
     if let Some(month) = &params.month {
-        bind_count += 1;
-        conditions.push(format!("DATE_TRUNC('month', e.event_date) = DATE_TRUNC('month', ${}::date)", bind_count));
+        let month_start = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|_| ApiError::field("month", FieldErrorCode::Invalid))?;
+        let month_end = month_start + Months::new(1) - Duration::days(1);
+        from = Some(from.map_or(month_start, |f| f.max(month_start)));
+        to = Some(to.map_or(month_end, |t| t.min(month_end)));
     }
 
-    if let Some(category) = &params.category {
-        bind_count += 1;
-        conditions.push(format!("e.category = ${}", bind_count));
+    if params.upcoming {
+        from = Some(from.map_or(today, |f| f.max(today)));
     }
 
-    if !conditions.is_empty() {
-        query.push_str(" AND ");
-        query.push_str(&conditions.join(" AND "));
+    if matches!((from, to), (Some(f), Some(t)) if f > t) {
+        return Err(ApiError::field("to", FieldErrorCode::OutOfRange));
     }
 
-    query.push_str(" ORDER BY e.event_date ASC, e.event_time ASC");
+    let bbox = params
+        .bbox
+        .as_deref()
+        .map(|b| parse_coordinates::<4>(b).ok_or(ApiError::field("bbox", FieldErrorCode::Invalid)))
+        .transpose()?;
+    if let Some([min_lon, min_lat, max_lon, max_lat]) = bbox {
+        if min_lon > max_lon || min_lat > max_lat || !valid_lat_lon(min_lat, min_lon) || !valid_lat_lon(max_lat, max_lon) {
+            return Err(ApiError::field("bbox", FieldErrorCode::OutOfRange));
+        }
+    }
 
-    let mut q = sqlx::query_as::<_, EventWithTranslation>(&query).bind(&lang);
+    let near = params
+        .near
+        .as_deref()
+        .map(|n| parse_coordinates::<2>(n).ok_or(ApiError::field("near", FieldErrorCode::Invalid)))
+        .transpose()?;
+    if near.is_some_and(|[lat, lon]| !valid_lat_lon(lat, lon)) {
+        return Err(ApiError::field("near", FieldErrorCode::OutOfRange));
+    }
+    let radius = params.radius.unwrap_or(DEFAULT_NEAR_RADIUS_KM);
+    if !(radius > 0.0 && radius <= MAX_NEAR_RADIUS_KM) {
+        return Err(ApiError::field("radius", FieldErrorCode::OutOfRange));
+    }
 
-    if let Some(month) = &params.month {
-        let date = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
-            .map_err(|_| StatusCode::BAD_REQUEST)?;
-        q = q.bind(date);
+    let mut query = event_select(&lang, near);
+    query.push(" WHERE e.published = true AND NOT e.hidden AND e.archived_at IS NULL");
+
+    // Series are matched by start date only; occurrences are filtered after expansion
+    if let Some(from) = from {
+        query.push(" AND (e.recurrence_rule IS NOT NULL OR e.event_date >= ");
+        query.push_bind(from);
+        query.push(")");
     }
 
-    if let Some(category) = &params.category {
-        q = q.bind(category);
+    if let Some(to) = to {
+        query.push(" AND e.event_date <= ");
+        query.push_bind(to);
     }
 
-    let events = q.fetch_all(&state.pool).await.map_err(|e| {
-        tracing::error!("Failed to fetch events: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    if let Some([min_lon, min_lat, max_lon, max_lat]) = bbox {
+        query.push(" AND v.latitude BETWEEN ");
+        query.push_bind(min_lat);
+        query.push(" AND ");
+        query.push_bind(max_lat);
+        query.push(" AND v.longitude BETWEEN ");
+        query.push_bind(min_lon);
+        query.push(" AND ");
+        query.push_bind(max_lon);
+    }
+
+    if let Some([lat, lon]) = near {
+        // Cheap bounding-box prefilter (usable by the lat/lon index) before the exact distance
+        let lat_delta = (radius / EARTH_RADIUS_KM).to_degrees();
+        let lon_delta = lat_delta / lat.to_radians().cos().max(0.01);
+        query.push(" AND v.latitude BETWEEN ");
+        query.push_bind(lat - lat_delta);
+        query.push(" AND ");
+        query.push_bind(lat + lat_delta);
+        query.push(" AND v.longitude BETWEEN ");
+        query.push_bind(lon - lon_delta);
+        query.push(" AND ");
+        query.push_bind(lon + lon_delta);
+        query.push(" AND ");
+        push_distance_km(&mut query, lat, lon);
+        query.push(" <= ");
+        query.push_bind(radius);
+    }
+
+    if let Some(is_free) = params.is_free {
+        query.push(" AND e.is_free = ");
+        query.push_bind(is_free);
+    }
+
+    // Events at a venue may have no location of their own, and are listed under the venue's name
+    if let Some(location) = params.location.as_deref().map(str::trim).filter(|l| !l.is_empty()) {
+        let pattern = format!("%{}%", escape_like(location));
+        query.push(" AND (e.location ILIKE ");
+        query.push_bind(pattern.clone());
+        query.push(" OR v.name ILIKE ");
+        query.push_bind(pattern);
+        query.push(")");
+    }
+
+    let categories: Vec<String> = params
+        .category
+        .iter()
+        .flat_map(|c| c.split(','))
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+
+    if !categories.is_empty() {
+        query.push(" AND e.category = ANY(");
+        query.push_bind(categories);
+        query.push(")");
+    }
+
+    query.push(" ORDER BY e.event_date ASC, e.event_time ASC");
+
+    let events = query
+        .build_query_as::<EventWithTranslation>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch events: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    let window_start = from.unwrap_or(today);
+    let window_end = to.unwrap_or(window_start + Duration::days(DEFAULT_RECURRENCE_WINDOW_DAYS));
+    let events = expand_series(&state, events, window_start, window_end).await?;
+
+    // Pagination is opt-in so the calendar can keep fetching a whole month at once
+    if params.page.is_none() && params.limit.is_none() {
+        return Ok(Json(json!({
+            "events": events
+        })));
+    }
+
+    let page = params.page.unwrap_or(1);
+    let limit = params.limit.unwrap_or(DEFAULT_EVENTS_PAGE_SIZE).min(MAX_EVENTS_PAGE_SIZE);
+    if page < 1 {
+        return Err(ApiError::field("page", FieldErrorCode::OutOfRange));
+    }
+    if limit < 1 {
+        return Err(ApiError::field("limit", FieldErrorCode::OutOfRange));
+    }
+
+    // Recurring series only exist as rows after expansion, so paging happens in memory
+    let total = events.len() as i64;
+    // Saturates so an absurd page number is just past the end
+    let offset = usize::try_from((page - 1).saturating_mul(limit)).unwrap_or(usize::MAX);
+    let events: Vec<EventWithTranslation> = events
+        .into_iter()
+        .skip(offset)
+        .take(limit as usize)
+        .collect();
+
+    Ok(Json(json!({
+        "events": events,
+        "pagination": {
+            "page": page,
+            "limit": limit,
+            "total": total,
+            "total_pages": (total as f64 / limit as f64).ceil() as i64
+        }
+    })))
+}
+
+pub async fn get_event(
+    State(state): State<SharedState>,
+    Path(key): Path<String>,
+    Query(params): Query<LangQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+
+    let event = fetch_published_event(&state, &lang, &key)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let available_languages = available_languages(&state, ContentKind::Event, event.id, &event.original_language).await?;
+
+    // Series also list their upcoming dates, with per-occurrence changes applied
+    let occurrences = if event.recurrence_rule.is_some() {
+        let window_start = Utc::now().date_naive();
+        let window_end = window_start + Duration::days(DEFAULT_RECURRENCE_WINDOW_DAYS);
+        let overrides = fetch_overrides(&state, &[event.id], window_start, window_end).await?;
+
+        let occurrences: Vec<serde_json::Value> =
+            expand_events(vec![event.clone()], &overrides, window_start, window_end)
+                .into_iter()
+                .map(|o| {
+                    json!({
+                        "event_date": o.event_date,
+                        "event_time": o.event_time,
+                        "location": o.location,
+                        "is_cancelled": o.is_cancelled
+                    })
+                })
+                .collect();
+        Some(occurrences)
+    } else {
+        None
+    };
 
     Ok(Json(json!({
-        "events": events
+        "event": event,
+        "available_languages": available_languages,
+        "occurrences": occurrences
     })))
 }
 
 pub async fn create_event(
     State(state): State<SharedState>,
+    client: ClientIp,
     Json(payload): Json<CreateEventRequest>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let mut errors = FieldError::check(&payload);
+    let mut reject = |field: &str, code| errors.push(FieldError { field: field.to_string(), code });
+
     let language = payload.language.clone().unwrap_or_else(|| "en".to_string());
-    let text_direction = payload.text_direction.clone().unwrap_or_else(|| {
-        detect_text_direction(&payload.title, &language)
-    });
+    if !is_enabled_language(&state, &language).await? {
+        reject("language", FieldErrorCode::Unknown);
+    }
+
+    let event_date = NaiveDate::parse_from_str(payload.event_date.trim(), "%Y-%m-%d").ok();
+    if event_date.is_none() {
+        reject("event_date", FieldErrorCode::Invalid);
+    }
+
+    // Time inputs submit HH:MM
+    let event_time = match payload.event_time.as_deref() {
+        Some(t) => {
+            let time = NaiveTime::parse_from_str(t, "%H:%M:%S").or_else(|_| NaiveTime::parse_from_str(t, "%H:%M"));
+            if time.is_err() {
+                reject("event_time", FieldErrorCode::Invalid);
+            }
+            time.ok()
+        }
+        None => None,
+    };
+
+    let Some(event_date) = event_date.filter(|_| errors.is_empty()) else {
+        return Err(ApiError::Validation(errors));
+    };
 
-    let event_date = NaiveDate::parse_from_str(&payload.event_date, "%Y-%m-%d")
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let title = payload.title.trim();
+    let text_direction = match payload.text_direction.as_deref() {
+        Some("auto") | None => detect_text_direction(title, &language),
+        Some(direction) => direction.to_string(),
+    };
 
-    let event_time = payload
-        .event_time
-        .as_ref()
-        .map(|t| chrono::NaiveTime::parse_from_str(t, "%H:%M:%S").ok())
-        .flatten();
+    // Categories are managed by admins; submissions must use a known slug
+    let category = payload
+        .category
+        .as_deref()
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty());
 
-    let event = sqlx::query_as::<_, Event>(
+    if let Some(category) = &category {
+        let known: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM categories WHERE slug = $1)")
+            .bind(category)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to check category: {:?}", e);
+                ApiError::Internal
+            })?;
+
+        if !known {
+            return Err(ApiError::field("category", FieldErrorCode::Unknown));
+        }
+    }
+
+    if let Some(venue_id) = payload.venue_id {
+        let known: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM venues WHERE id = $1)")
+            .bind(venue_id)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to check venue: {:?}", e);
+                ApiError::Internal
+            })?;
+
+        if !known {
+            return Err(ApiError::field("venue_id", FieldErrorCode::Unknown));
+        }
+    }
+
+    let ticket_url = normalize_url(payload.ticket_url.as_deref())
+        .map_err(|_| ApiError::field("ticket_url", FieldErrorCode::Invalid))?;
+
+    verify_image(&state, payload.image_id.as_deref()).await?;
+
+    let recurrence_rule = match payload.recurrence_rule.as_deref().map(str::trim) {
+        Some(rule) if !rule.is_empty() => {
+            RecurrenceRule::parse(rule).map_err(|e| {
+                tracing::debug!("Rejected recurrence rule {:?}: {:?}", rule, e);
+                ApiError::field("recurrence_rule", FieldErrorCode::Invalid)
+            })?;
+            Some(rule.strip_prefix("RRULE:").unwrap_or(rule).to_string())
+        }
+        _ => None,
+    };
+
+    let recurrence_exdates = payload
+        .recurrence_exdates
+        .iter()
+        .flatten()
+        .map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d"))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ApiError::field("recurrence_exdates", FieldErrorCode::Invalid))?;
+
+    verify_challenge(&state, "events", payload.challenge_response.as_deref(), &client).await?;
+
+    let content_hash = spam::content_hash(title, payload.description.as_deref());
+    let submitter_hash = spam::submitter_hash(&client.bucket_key());
+    let submission = Submission {
+        title,
+        body: payload.description.as_deref(),
+        urls: ticket_url.as_deref().into_iter().collect(),
+        email: payload.organizer_email.as_deref(),
+        submitter_hash: &submitter_hash,
+        content_hash: &content_hash,
+        honeypot: payload.website.as_deref(),
+    };
+    let assessment = state.spam_scorer.assess(&state.pool, &submission).await;
+    if assessment.quarantined {
+        tracing::info!("Quarantining event from {} with spam score {}", client, assessment.score);
+    }
+
+    let mut event = sqlx::query_as::<_, Event>(
         r#"
         INSERT INTO events (
             organizer_name, organizer_email, title, description,
             event_date, event_time, location, category,
             is_free, ticket_url,
-            original_language, text_direction
+            original_language, text_direction,
+            recurrence_rule, recurrence_exdates, venue_id, image_id,
+            spam_score, spam_reasons, quarantined, content_hash, submitter_hash
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16,
+                $17, $18, $19, $20, $21)
         RETURNING *
         "#,
     )
-    .bind(&payload.organizer_name)
+    .bind(payload.organizer_name.trim())
     .bind(&payload.organizer_email)
-    .bind(&payload.title)
+    .bind(title)
     .bind(&payload.description)
     .bind(event_date)
     .bind(event_time)
     .bind(&payload.location)
-    .bind(&payload.category)
+    .bind(&category)
     .bind(payload.is_free)
-    .bind(&payload.ticket_url)
+    .bind(&ticket_url)
     .bind(&language)
     .bind(&text_direction)
+    .bind(&recurrence_rule)
+    .bind(&recurrence_exdates)
+    .bind(payload.venue_id)
+    .bind(&payload.image_id)
+    .bind(assessment.score)
+    .bind(sqlx::types::Json(&assessment.reasons))
+    .bind(assessment.quarantined)
+    .bind(&content_hash)
+    .bind(&submitter_hash)
     .fetch_one(&state.pool)
     .await
     .map_err(|e| {
         tracing::error!("Failed to create event: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        ApiError::Internal
     })?;
 
+    // A missing slug only costs the event its pretty permalink
+    match assign_slug(&state.pool, "events", event.id, &event.title).await {
+        Ok(slug) => event.slug = slug,
+        Err(e) => tracing::warn!("Failed to assign slug to event {}: {:?}", event.id, e),
+    }
+
+    // Quarantined events are announced if and when a moderator publishes them
+    if !assessment.quarantined {
+        webhooks::enqueue_event(&state.pool, WebhookEvent::EventSubmitted, &event).await;
+    }
+
     Ok(Json(json!({
         "event": event,
         "message": "Event submitted successfully and is awaiting moderation"
@@ -149,28 +459,277 @@ pub async fn create_event(
 
 pub async fn publish_event(
     State(state): State<SharedState>,
+    headers: HeaderMap,
     Path(id): Path<i32>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Update event to published
-    sqlx::query("UPDATE events SET published = true WHERE id = $1")
-        .bind(id)
-        .execute(&state.pool)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to publish event: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    // Update event to published; publishing is a moderator's verdict, so it also lifts quarantine
+    let was_published: Option<bool> = sqlx::query_scalar(
+        r#"
+        UPDATE events e SET published = true, quarantined = false
+        FROM (SELECT id, published FROM events WHERE id = $1 FOR UPDATE) old
+        WHERE e.id = old.id
+        RETURNING old.published
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to publish event: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let Some(was_published) = was_published else {
+        return Err(ApiError::NotFound);
+    };
+    // Already announced
+    if was_published {
+        return Ok(Json(json!({
+            "message": "Event published successfully"
+        })));
+    }
+
+    state.notifier.content(ContentChange::Published, ContentKind::Event, id).await;
+    webhooks::enqueue_event_id(&state.pool, WebhookEvent::EventPublished, id).await;
+    activitypub::enqueue(&state.pool, ContentKind::Event, id).await;
 
     // Trigger translation job
     if let Some(api_key) = &state.google_api_key {
         let pool = std::sync::Arc::new(state.pool.clone());
+        let notifier = state.notifier.clone();
         let api_key = api_key.clone();
         tokio::spawn(async move {
-            crate::services::background::translate_event(pool, id, api_key).await;
+            crate::services::background::translate_event(pool, notifier, id, api_key).await;
         });
     }
 
     Ok(Json(json!({
         "message": "Event published successfully"
     })))
+}
+
+/// Looks a published event up by id or slug, localized into `lang`.
+pub(crate) async fn fetch_published_event(
+    state: &SharedState,
+    lang: &str,
+    key: &str,
+) -> Result<Option<EventWithTranslation>, ApiError> {
+    // Numeric keys are ids, anything else is a slug (slugs are never purely numeric)
+    let mut query = event_select(lang, None);
+    query.push(" WHERE e.published = true AND NOT e.hidden AND e.archived_at IS NULL AND ");
+    match key.parse::<i32>() {
+        Ok(id) => {
+            query.push("e.id = ");
+            query.push_bind(id);
+        }
+        Err(_) => {
+            query.push("e.slug = ");
+            query.push_bind(key.to_string());
+        }
+    }
+
+    query
+        .build_query_as::<EventWithTranslation>()
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch event: {:?}", e);
+            ApiError::Internal
+        })
+}
+
+/// The most recently submitted published events localized into `lang`, past or upcoming.
+pub(super) async fn fetch_recent_events(
+    state: &SharedState,
+    lang: &str,
+    limit: i64,
+) -> Result<Vec<EventWithTranslation>, ApiError> {
+    let mut query = event_select(lang, None);
+    query.push(" WHERE e.published = true AND NOT e.hidden AND e.archived_at IS NULL ORDER BY e.created_at DESC, e.id DESC LIMIT ");
+    query.push_bind(limit);
+
+    query
+        .build_query_as::<EventWithTranslation>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch recent events: {:?}", e);
+            ApiError::Internal
+        })
+}
+
+/// Published events localized into `lang` taking place between `from` and `to`
+/// inclusive, with recurring series expanded into their occurrences. An empty
+/// `categories` means any category.
+pub(crate) async fn fetch_events_between(
+    state: &SharedState,
+    lang: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    categories: &[String],
+) -> Result<Vec<EventWithTranslation>, ApiError> {
+    let mut query = event_select(lang, None);
+    query.push(" WHERE e.published = true AND NOT e.hidden AND e.archived_at IS NULL");
+    query.push(" AND (e.recurrence_rule IS NOT NULL OR e.event_date >= ");
+    query.push_bind(from);
+    query.push(") AND e.event_date <= ");
+    query.push_bind(to);
+    if !categories.is_empty() {
+        query.push(" AND e.category = ANY(");
+        query.push_bind(categories.to_vec());
+        query.push(")");
+    }
+    query.push(" ORDER BY e.event_date ASC, e.event_time ASC");
+
+    let events = query
+        .build_query_as::<EventWithTranslation>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch events between {} and {}: {:?}", from, to, e);
+            ApiError::Internal
+        })?;
+
+    expand_series(state, events, from, to).await
+}
+
+// Expands recurring series into their occurrences within the window, with any
+// per-occurrence overrides applied
+async fn expand_series(
+    state: &SharedState,
+    events: Vec<EventWithTranslation>,
+    window_start: NaiveDate,
+    window_end: NaiveDate,
+) -> Result<Vec<EventWithTranslation>, ApiError> {
+    let series_ids: Vec<i32> = events
+        .iter()
+        .filter(|e| e.recurrence_rule.is_some())
+        .map(|e| e.id)
+        .collect();
+
+    let overrides = fetch_overrides(state, &series_ids, window_start, window_end).await?;
+
+    Ok(expand_events(events, &overrides, window_start, window_end))
+}
+
+async fn fetch_overrides(
+    state: &SharedState,
+    series_ids: &[i32],
+    window_start: NaiveDate,
+    window_end: NaiveDate,
+) -> Result<Vec<EventOccurrenceOverride>, ApiError> {
+    if series_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    sqlx::query_as::<_, EventOccurrenceOverride>(
+        "SELECT * FROM event_occurrence_overrides 
+         WHERE event_id = ANY($1) AND occurrence_date BETWEEN $2 AND $3"
+    )
+    .bind(series_ids)
+    .bind(window_start)
+    .bind(window_end)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch occurrence overrides: {:?}", e);
+        ApiError::Internal
+    })
+}
+
+// Events localized into `lang` with venue and category details; `near` adds distance_km
+fn event_select(lang: &str, near: Option<[f64; 2]>) -> QueryBuilder<'static, Postgres> {
+    let mut query = QueryBuilder::<Postgres>::new(
+        r#"
+        SELECT 
+            e.id,
+            e.slug,
+            e.organizer_name,
+            COALESCE(et.title, e.title) as title,
+            COALESCE(et.description, e.description) as description,
+            e.title as original_title,
+            e.description as original_description,
+            e.event_date,
+            e.event_time,
+            COALESCE(e.location, v.name) as location,
+            e.venue_id,
+            v.name as venue_name,
+            v.address as venue_address,
+            v.latitude,
+            v.longitude,
+            e.category,
+            COALESCE(ct.name, cten.name, c.slug) as category_name,
+            c.color as category_color,
+            e.is_free,
+            e.ticket_url,
+            '/api/images/' || i.display_key as image_url,
+            '/api/images/' || i.thumbnail_key as thumbnail_url,
+            e.original_language,
+            e.text_direction as original_text_direction,
+            COALESCE(et.text_direction, e.text_direction) as text_direction,
+            CASE WHEN et.id IS NOT NULL AND e.original_language != et.language_code THEN true ELSE false END as is_translated,
+            e.recurrence_rule,
+            e.recurrence_exdates,
+            e.created_at,
+        "#,
+    );
+
+    match near {
+        Some([lat, lon]) => push_distance_km(&mut query, lat, lon),
+        None => {
+            query.push("NULL::float8");
+        }
+    }
+
+    query.push(
+        r#" as distance_km
+        FROM events e
+        LEFT JOIN venues v ON v.id = e.venue_id
+        LEFT JOIN images i ON i.id = e.image_id
+        LEFT JOIN categories c ON c.slug = e.category
+        LEFT JOIN category_translations cten ON cten.category_slug = e.category AND cten.language_code = 'en'
+        LEFT JOIN event_translations et ON e.id = et.event_id AND et.language_code = "#,
+    );
+    query.push_bind(lang.to_string());
+    query.push(" LEFT JOIN category_translations ct ON ct.category_slug = e.category AND ct.language_code = ");
+    query.push_bind(lang.to_string());
+
+    query
+}
+
+// Escapes LIKE wildcards so user input only ever matches literally
+pub(super) fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+// Great-circle (haversine) distance between the event's venue and the given point. Rounding
+// can push the root just past 1 for antipodal points, which ASIN rejects.
+fn push_distance_km(query: &mut QueryBuilder<'_, Postgres>, lat: f64, lon: f64) {
+    query.push("(");
+    query.push_bind(EARTH_RADIUS_KM);
+    query.push(" * 2 * ASIN(LEAST(1, SQRT(POWER(SIN(RADIANS(v.latitude - ");
+    query.push_bind(lat);
+    query.push(") / 2), 2) + COS(RADIANS(");
+    query.push_bind(lat);
+    query.push(")) * COS(RADIANS(v.latitude)) * POWER(SIN(RADIANS(v.longitude - ");
+    query.push_bind(lon);
+    query.push(") / 2), 2)))))");
+}
+
+fn parse_coordinates<const N: usize>(value: &str) -> Option<[f64; N]> {
+    let values: Vec<f64> = value
+        .split(',')
+        .map(|v| v.trim().parse::<f64>().ok().filter(|f| f.is_finite()))
+        .collect::<Option<_>>()?;
+    values.try_into().ok()
+}
+
+fn valid_lat_lon(lat: f64, lon: f64) -> bool {
+    (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon)
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
-use axum::{http::StatusCode, Json};
+use axum::Json;
 use serde_json::json;
 
-pub async fn health_check() -> Result<Json<serde_json::Value>, StatusCode> {
+use crate::error::ApiError;
+
+pub async fn health_check() -> Result<Json<serde_json::Value>, ApiError> {
     Ok(Json(json!({
         "status": "healthy",
         "timestamp": chrono::Utc::now().to_rfc3339()
@@ -1,11 +1,11 @@
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{extract::State, Json};
 use serde_json::json;
 
-use crate::{db::SharedState, models::Language};
+use crate::{db::SharedState, error::ApiError, models::Language};
 
 pub async fn get_supported_languages(
     State(state): State<SharedState>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     let languages = sqlx::query_as::<_, Language>(
         "SELECT code, name, native_name, is_rtl, text_direction, enabled 
          FROM supported_languages 
@@ -16,10 +16,22 @@ pub async fn get_supported_languages(
     .await
     .map_err(|e| {
         tracing::error!("Failed to fetch languages: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        ApiError::Internal
     })?;
 
     Ok(Json(json!({
         "languages": languages
     })))
-}
\ No newline at end of file
+}
+
+/// Whether `code` is a language the site is currently offered in.
+pub(super) async fn is_enabled_language(state: &SharedState, code: &str) -> Result<bool, ApiError> {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM supported_languages WHERE code = $1 AND enabled = true)")
+        .bind(code)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check language: {:?}", e);
+            ApiError::Internal
+        })
+}
@@ -1,11 +1,37 @@
+mod activitypub;
 mod admin;
+mod alerts;
+mod categories;
+mod challenge;
 mod events;
 mod health;
 mod languages;
+mod newsletter;
 mod posts;
+mod reports;
+mod search;
+mod share;
+mod spam;
+mod stream;
+mod uploads;
+mod venues;
+mod webhooks;
 
+pub use activitypub::*;
 pub use admin::*;
+pub use alerts::*;
+pub use categories::*;
+pub use challenge::*;
 pub use events::*;
 pub use health::*;
 pub use languages::*;
-pub use posts::*;
\ No newline at end of file
+pub use newsletter::*;
+pub use posts::*;
+pub use reports::*;
+pub use search::*;
+pub use share::*;
+pub use spam::*;
+pub use stream::*;
+pub use uploads::*;
+pub use venues::*;
+pub use webhooks::*;
\ No newline at end of file
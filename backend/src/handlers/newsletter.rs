@@ -0,0 +1,389 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{admin::verify_admin_session, languages::is_enabled_language};
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldError, FieldErrorCode},
+    models::{NewsletterPreferencesRequest, NewsletterSubscribeRequest, NewsletterSubscriber, NewsletterTopic},
+    services::{
+        mail::Email,
+        newsletter::{self, CONFIRMATION_RESEND_MINUTES},
+    },
+};
+
+// Whether or not the address was already subscribed, so the response doesn't tell
+const SUBSCRIBE_MESSAGE: &str = "Check your inbox to confirm your subscription";
+
+#[derive(Deserialize)]
+pub struct TokenQuery {
+    pub token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PreviewQuery {
+    pub lang: Option<String>,
+    pub format: Option<String>, // html (default) or text
+}
+
+#[derive(sqlx::FromRow)]
+struct PendingEmail {
+    token: String,
+    language_code: String,
+    due: bool, // Whether an email should go out now
+}
+
+/// Starts a subscription, or replaces the preferences of one not yet confirmed. Nothing
+/// but a confirmation email is sent until the link in it is followed.
+pub async fn subscribe_newsletter(
+    State(state): State<SharedState>,
+    Json(payload): Json<NewsletterSubscribeRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let mut errors = FieldError::check(&payload);
+    let language = payload.language.clone().unwrap_or_else(|| "en".to_string());
+    let (topics, categories) = check_preferences(&state, &language, &payload.topics, &payload.categories, &mut errors).await?;
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let email = payload.email.trim().to_lowercase();
+    let token = hex::encode(rand::random::<[u8; 32]>());
+
+    // An unconfirmed subscription takes the new preferences; a confirmation goes out
+    // unless one just did
+    let pending = sqlx::query_as::<_, PendingEmail>(
+        r#"
+        INSERT INTO newsletter_subscribers (email, language_code, topics, categories, token)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (email) DO UPDATE SET
+            language_code = EXCLUDED.language_code,
+            topics = EXCLUDED.topics,
+            categories = EXCLUDED.categories,
+            confirmation_sent_at = CASE
+                WHEN newsletter_subscribers.confirmation_sent_at < NOW() - make_interval(mins => $6) THEN NOW()
+                ELSE newsletter_subscribers.confirmation_sent_at
+            END,
+            updated_at = NOW()
+        WHERE newsletter_subscribers.confirmed_at IS NULL
+        RETURNING token, language_code, confirmation_sent_at = NOW() AS due
+        "#,
+    )
+    .bind(&email)
+    .bind(&language)
+    .bind(&topics)
+    .bind(&categories)
+    .bind(&token)
+    .bind(CONFIRMATION_RESEND_MINUTES as i32)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to store newsletter subscription: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    match pending {
+        Some(pending) if pending.due => send_in_background(
+            &state,
+            newsletter::confirmation_email(&state.public_url, &email, &pending.language_code, &pending.token),
+        ),
+        Some(_) => {}
+        // A confirmed subscription keeps its preferences; its owner is sent the link to
+        // change them instead, which is also how a lost link is recovered
+        None => {
+            let subscribed = sqlx::query_as::<_, PendingEmail>(
+                r#"
+                UPDATE newsletter_subscribers SET confirmation_sent_at = NOW()
+                WHERE email = $1 AND confirmation_sent_at < NOW() - make_interval(mins => $2)
+                RETURNING token, language_code, true AS due
+                "#,
+            )
+            .bind(&email)
+            .bind(CONFIRMATION_RESEND_MINUTES as i32)
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to update newsletter subscription: {:?}", e);
+                ApiError::Internal
+            })?;
+
+            if let Some(subscribed) = subscribed {
+                send_in_background(
+                    &state,
+                    newsletter::subscribed_email(&state.public_url, &email, &subscribed.language_code, &subscribed.token),
+                );
+            }
+        }
+    }
+
+    Ok(Json(json!({
+        "message": SUBSCRIBE_MESSAGE
+    })))
+}
+
+pub async fn confirm_newsletter(
+    State(state): State<SharedState>,
+    Query(params): Query<TokenQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let token = required_token(&params)?;
+
+    let subscriber = sqlx::query_as::<_, NewsletterSubscriber>(
+        r#"
+        UPDATE newsletter_subscribers SET confirmed_at = COALESCE(confirmed_at, NOW()), updated_at = NOW()
+        WHERE token = $1
+        RETURNING *
+        "#,
+    )
+    .bind(token)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to confirm newsletter subscription: {:?}", e);
+        ApiError::Internal
+    })?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(json!({
+        "subscription": subscriber
+    })))
+}
+
+pub async fn get_newsletter_subscription(
+    State(state): State<SharedState>,
+    Query(params): Query<TokenQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let token = required_token(&params)?;
+
+    let subscriber = sqlx::query_as::<_, NewsletterSubscriber>("SELECT * FROM newsletter_subscribers WHERE token = $1")
+        .bind(token)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch newsletter subscription: {:?}", e);
+            ApiError::Internal
+        })?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(json!({
+        "subscription": subscriber
+    })))
+}
+
+pub async fn update_newsletter_subscription(
+    State(state): State<SharedState>,
+    Query(params): Query<TokenQuery>,
+    Json(payload): Json<NewsletterPreferencesRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let token = required_token(&params)?;
+
+    let mut errors = FieldError::check(&payload);
+    let (topics, categories) = check_preferences(&state, &payload.language, &payload.topics, &payload.categories, &mut errors).await?;
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let subscriber = sqlx::query_as::<_, NewsletterSubscriber>(
+        r#"
+        UPDATE newsletter_subscribers
+        SET language_code = $2, topics = $3, categories = $4, updated_at = NOW()
+        WHERE token = $1
+        RETURNING *
+        "#,
+    )
+    .bind(token)
+    .bind(&payload.language)
+    .bind(&topics)
+    .bind(&categories)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update newsletter subscription: {:?}", e);
+        ApiError::Internal
+    })?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(json!({
+        "subscription": subscriber
+    })))
+}
+
+/// Also the target of mail clients' one-click unsubscribe (RFC 8058), which POSTs a
+/// form body this ignores. Succeeds for unknown tokens, so repeating it is harmless.
+pub async fn unsubscribe_newsletter(
+    State(state): State<SharedState>,
+    Query(params): Query<TokenQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let token = required_token(&params)?;
+
+    sqlx::query("DELETE FROM newsletter_subscribers WHERE token = $1")
+        .bind(token)
+        .execute(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to unsubscribe from the newsletter: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    Ok(Json(json!({
+        "message": "Unsubscribed successfully"
+    })))
+}
+
+pub async fn get_newsletter_stats(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let (confirmed, pending): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*) FILTER (WHERE confirmed_at IS NOT NULL), COUNT(*) FILTER (WHERE confirmed_at IS NULL)
+        FROM newsletter_subscribers
+        "#,
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to count newsletter subscribers: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let by_language: Vec<(String, i64)> = sqlx::query_as(
+        r#"
+        SELECT language_code, COUNT(*) FROM newsletter_subscribers
+        WHERE confirmed_at IS NOT NULL
+        GROUP BY language_code
+        ORDER BY COUNT(*) DESC, language_code
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to count newsletter subscribers: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let latest_edition = newsletter::latest_edition(Utc::now());
+    Ok(Json(json!({
+        "confirmed": confirmed,
+        "pending": pending,
+        "by_language": by_language
+            .into_iter()
+            .map(|(language, count)| json!({ "language": language, "count": count }))
+            .collect::<Vec<_>>(),
+        "latest_edition": latest_edition,
+        "next_edition": latest_edition + Duration::days(7)
+    })))
+}
+
+/// Renders the latest edition as a subscriber to every topic and category would get it.
+pub async fn preview_newsletter(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(params): Query<PreviewQuery>,
+) -> Result<Response, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+    if !is_enabled_language(&state, &lang).await? {
+        return Err(ApiError::field("lang", FieldErrorCode::Unknown));
+    }
+    let text = match params.format.as_deref() {
+        None | Some("html") => false,
+        Some("text") => true,
+        Some(_) => return Err(ApiError::field("format", FieldErrorCode::Invalid)),
+    };
+
+    let edition = newsletter::latest_edition(Utc::now());
+    let digest = newsletter::build_digest(&state, &lang, &[], edition).await?;
+    let now = Utc::now();
+    let preview = NewsletterSubscriber {
+        id: 0,
+        email: String::new(),
+        language_code: lang,
+        topics: vec![NewsletterTopic::Posts.as_str().to_string(), NewsletterTopic::Events.as_str().to_string()],
+        categories: Vec::new(),
+        token: "preview".to_string(),
+        confirmed_at: Some(now),
+        confirmation_sent_at: now,
+        last_digest_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+    let email = newsletter::digest_email(&state.public_url, &preview, &digest, edition);
+
+    Ok(if text {
+        ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], email.text).into_response()
+    } else {
+        ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], email.html).into_response()
+    })
+}
+
+fn required_token(params: &TokenQuery) -> Result<&str, ApiError> {
+    params
+        .token
+        .as_deref()
+        .filter(|t| !t.is_empty())
+        .ok_or(ApiError::field("token", FieldErrorCode::Required))
+}
+
+// Topics and categories as stored, deduplicated; problems are added to errors
+async fn check_preferences(
+    state: &SharedState,
+    language: &str,
+    topics: &[NewsletterTopic],
+    categories: &[String],
+    errors: &mut Vec<FieldError>,
+) -> Result<(Vec<String>, Vec<String>), ApiError> {
+    if !is_enabled_language(state, language).await? {
+        errors.push(FieldError { field: "language".to_string(), code: FieldErrorCode::Unknown });
+    }
+
+    let mut topics: Vec<String> = topics.iter().map(|t| t.as_str().to_string()).collect();
+    topics.sort();
+    topics.dedup();
+
+    let mut categories: Vec<String> = categories
+        .iter()
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+    categories.sort();
+    categories.dedup();
+
+    if !categories.is_empty() {
+        let known: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM categories WHERE slug = ANY($1)")
+            .bind(&categories)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to check categories: {:?}", e);
+                ApiError::Internal
+            })?;
+
+        if known != categories.len() as i64 {
+            errors.push(FieldError { field: "categories".to_string(), code: FieldErrorCode::Unknown });
+        }
+    }
+
+    Ok((topics, categories))
+}
+
+// Sending can take a while and shouldn't hold up (or time) the response
+fn send_in_background(state: &SharedState, email: Email) {
+    let mailer = state.mailer.clone();
+    tokio::spawn(async move {
+        let subject = email.subject.clone();
+        if let Err(e) = mailer.send(email).await {
+            tracing::warn!("Failed to send {:?}: {:?}", subject, e);
+        }
+    });
+}
@@ -1,120 +1,349 @@
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::HeaderMap,
     Json,
 };
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use serde_json::json;
 
+use super::{
+    admin::verify_admin_session,
+    challenge::verify_challenge,
+    languages::is_enabled_language,
+    uploads::{register_remote_image, verify_image},
+};
 use crate::{
     db::SharedState,
-    models::{CreatePostRequest, Post, PostWithTranslation},
-    services::translation::detect_text_direction,
+    error::{ApiError, FieldError, FieldErrorCode},
+    middleware::client_ip::ClientIp,
+    models::{CreatePostRequest, Post, PostType, PostWithTranslation, PublishPostRequest, WebhookEvent},
+    services::{
+        activitypub,
+        cache,
+        notices::{ContentChange, ContentKind},
+        slug::assign_slug,
+        spam::{self, Submission},
+        translation::detect_text_direction,
+        urls::normalize_url,
+        webhooks,
+    },
 };
 
-#[derive(Deserialize)]
-pub struct GetPostsQuery {
-    pub lang: Option<String>,
-    pub page: Option<i64>,
-    pub limit: Option<i64>,
-}
+const DEFAULT_POSTS_PAGE_SIZE: i64 = 20;
+const MAX_POSTS_PAGE_SIZE: i64 = 50;
 
-pub async fn get_posts(
-    State(state): State<SharedState>,
-    Query(params): Query<GetPostsQuery>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    let lang = params.lang.unwrap_or_else(|| "en".to_string());
-    let page = params.page.unwrap_or(1);
-    let limit = params.limit.unwrap_or(20);
-    let offset = (page - 1) * limit;
+const PUBLISHED_POST_COUNT_KEY: &str = "posts:published_count";
+const PUBLISHED_POST_COUNT_TTL_SECONDS: u64 = 60;
 
-    // Get posts with translations
-    let posts = sqlx::query_as::<_, PostWithTranslation>(
-        r#"
+// Posts localized into $1, falling back to the original text where no translation exists.
+// pin.pinned is whether the post is pinned now: a pin lapses at pinned_until even before
+// the scheduler clears it.
+const POST_WITH_TRANSLATION_SELECT: &str = r#"
         SELECT 
             p.id,
+            p.slug,
             p.author_name,
             COALESCE(pt.title, p.title) as title,
             COALESCE(pt.content, p.content) as content,
             p.title as original_title,
             p.content as original_content,
             p.link_url,
-            p.image_url,
+            COALESCE(
+                '/api/images/' || i.display_key,
+                '/api/image-proxy/' || encode(sha256(convert_to(p.image_url, 'UTF8')), 'hex')
+            ) as image_url,
+            '/api/images/' || i.thumbnail_key as thumbnail_url,
+            CASE WHEN lp.status = 'ready' THEN json_build_object(
+                'url', lp.url,
+                'title', COALESCE(lpt.title, lp.title),
+                'description', COALESCE(lpt.description, lp.description),
+                'image_url', '/api/image-proxy/' || encode(sha256(convert_to(lp.image_url, 'UTF8')), 'hex'),
+                'site_name', lp.site_name,
+                'is_translated', lpt.post_id IS NOT NULL
+            ) END as link_preview,
             p.post_type,
             p.original_language,
             p.text_direction as original_text_direction,
             COALESCE(pt.text_direction, p.text_direction) as text_direction,
             CASE WHEN pt.id IS NOT NULL AND p.original_language != $1 THEN true ELSE false END as is_translated,
+            pin.pinned,
+            p.emergency AND pin.pinned as emergency,
             p.created_at
         FROM posts p
-        LEFT JOIN post_translations pt ON p.id = pt.post_id AND pt.language_code = $1
-        WHERE p.published = true
-        ORDER BY p.created_at DESC
-        LIMIT $2 OFFSET $3
-        "#,
-    )
+        CROSS JOIN LATERAL (
+            SELECT p.pinned_at IS NOT NULL AND (p.pinned_until IS NULL OR p.pinned_until > NOW()) AS pinned
+        ) pin
+        LEFT JOIN images i ON i.id = p.image_id
+        LEFT JOIN link_previews lp ON lp.post_id = p.id
+        LEFT JOIN link_preview_translations lpt ON lpt.post_id = p.id AND lpt.language_code = $1
+        LEFT JOIN post_translations pt ON p.id = pt.post_id AND pt.language_code = $1"#;
+
+#[derive(Deserialize)]
+pub struct LangQuery {
+    pub lang: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct GetPostsQuery {
+    pub lang: Option<String>,
+    pub cursor: Option<String>, // Opaque, from the previous page's next_cursor
+    pub limit: Option<i64>,
+    #[serde(default)]
+    pub include_total: bool,
+    #[serde(rename = "type")]
+    pub post_type: Option<PostType>,
+}
+
+pub async fn get_posts(
+    State(state): State<SharedState>,
+    Query(params): Query<GetPostsQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+    let limit = params.limit.unwrap_or(DEFAULT_POSTS_PAGE_SIZE);
+    if limit < 1 {
+        return Err(ApiError::field("limit", FieldErrorCode::OutOfRange));
+    }
+    let limit = limit.min(MAX_POSTS_PAGE_SIZE);
+
+    let cursor = params
+        .cursor
+        .as_deref()
+        .map(|c| decode_cursor(c).ok_or(ApiError::field("cursor", FieldErrorCode::Invalid)))
+        .transpose()?;
+    let (cursor_created_at, cursor_id) = cursor.unzip();
+
+    // Pinned posts are listed apart, ahead of the first page, and left out of the pages.
+    // All of them, since one left off the list would be in neither.
+    let pinned = if cursor.is_none() {
+        sqlx::query_as::<_, PostWithTranslation>(&format!(
+            r#"
+            {POST_WITH_TRANSLATION_SELECT}
+            WHERE p.published = true AND NOT p.hidden AND p.archived_at IS NULL AND pin.pinned
+              AND ($2::varchar IS NULL OR p.post_type = $2)
+            ORDER BY p.emergency DESC, p.pin_priority DESC, p.pinned_at DESC
+            "#
+        ))
+        .bind(&lang)
+        .bind(params.post_type)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch pinned posts: {:?}", e);
+            ApiError::Internal
+        })?
+    } else {
+        Vec::new()
+    };
+
+    // Get posts with translations; one extra row tells us whether there is a next page
+    let mut posts = sqlx::query_as::<_, PostWithTranslation>(&format!(
+        r#"
+        {POST_WITH_TRANSLATION_SELECT}
+        WHERE p.published = true AND NOT p.hidden AND p.archived_at IS NULL AND NOT pin.pinned
+          AND ($2::timestamptz IS NULL OR (p.created_at, p.id) < ($2, $3))
+          AND ($5::varchar IS NULL OR p.post_type = $5)
+        ORDER BY p.created_at DESC, p.id DESC
+        LIMIT $4
+        "#
+    ))
     .bind(&lang)
-    .bind(limit)
-    .bind(offset)
+    .bind(cursor_created_at)
+    .bind(cursor_id)
+    .bind(limit + 1)
+    .bind(params.post_type)
     .fetch_all(&state.pool)
     .await
     .map_err(|e| {
         tracing::error!("Failed to fetch posts: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        ApiError::Internal
     })?;
 
-    // Get total count
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE published = true")
-        .fetch_one(&state.pool)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let has_more = posts.len() as i64 > limit;
+    posts.truncate(limit as usize);
+    let next_cursor = if has_more {
+        posts.last().map(|p| encode_cursor(p.created_at, p.id))
+    } else {
+        None
+    };
+
+    let mut pagination = json!({
+        "limit": limit,
+        "next_cursor": next_cursor,
+        "has_more": has_more
+    });
+
+    // The total is optional and cached, since counting gets slow as the table grows
+    if params.include_total {
+        let count_key = published_post_count_key(params.post_type);
+        let total = match cache::get_i64(&state.redis_client, &count_key).await {
+            Some(total) => total,
+            None => {
+                let total: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM posts WHERE published = true AND NOT hidden AND archived_at IS NULL AND ($1::varchar IS NULL OR post_type = $1)"
+                )
+                .bind(params.post_type)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(|_| ApiError::Internal)?;
+                cache::set_i64(
+                    &state.redis_client,
+                    &count_key,
+                    total,
+                    PUBLISHED_POST_COUNT_TTL_SECONDS,
+                )
+                .await;
+                total
+            }
+        };
+        pagination["total"] = json!(total);
+    }
 
     Ok(Json(json!({
+        "pinned": pinned,
         "posts": posts,
-        "pagination": {
-            "page": page,
-            "limit": limit,
-            "total": total,
-            "total_pages": (total as f64 / limit as f64).ceil() as i64
-        }
+        "pagination": pagination
+    })))
+}
+
+pub async fn get_post(
+    State(state): State<SharedState>,
+    Path(key): Path<String>,
+    Query(params): Query<LangQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+
+    let post = fetch_published_post(&state, &lang, &key)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let available_languages = available_languages(&state, ContentKind::Post, post.id, &post.original_language).await?;
+
+    Ok(Json(json!({
+        "post": post,
+        "available_languages": available_languages
     })))
 }
 
 pub async fn create_post(
     State(state): State<SharedState>,
+    client: ClientIp,
     Json(payload): Json<CreatePostRequest>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let mut errors = FieldError::check(&payload);
+
     let language = payload.language.clone().unwrap_or_else(|| "en".to_string());
-    let text_direction = payload.text_direction.clone().unwrap_or_else(|| {
-        detect_text_direction(&payload.title, &language)
-    });
+    if !is_enabled_language(&state, &language).await? {
+        errors.push(FieldError { field: "language".to_string(), code: FieldErrorCode::Unknown });
+    }
+
+    let title = payload.title.trim();
+    let text_direction = match payload.text_direction.as_deref() {
+        Some("auto") | None => detect_text_direction(title, &language),
+        Some(direction) => direction.to_string(),
+    };
+
+    let link_url = normalize_url(payload.link_url.as_deref());
+    let image_url = normalize_url(payload.image_url.as_deref());
+    let content = payload.content.as_deref().map(str::trim).filter(|c| !c.is_empty());
+
+    // Each type has the fields it can't do without
+    let mut reject = |field: &str, code| errors.push(FieldError { field: field.to_string(), code });
+    match &link_url {
+        Err(_) => reject("link_url", FieldErrorCode::Invalid),
+        Ok(None) if payload.post_type.requires_link() => reject("link_url", FieldErrorCode::Required),
+        Ok(_) => {}
+    }
+    if image_url.is_err() {
+        reject("image_url", FieldErrorCode::Invalid);
+    }
+    if payload.post_type.requires_content() && content.is_none() {
+        reject("content", FieldErrorCode::Required);
+    }
+    let (Ok(link_url), Ok(image_url)) = (link_url, image_url) else {
+        return Err(ApiError::Validation(errors));
+    };
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    verify_image(&state, payload.image_id.as_deref()).await?;
+
+    // Checked once the submission is known to be valid, and before anything is stored
+    verify_challenge(&state, "posts", payload.challenge_response.as_deref(), &client).await?;
+
+    if let Some(image_url) = &image_url {
+        register_remote_image(&state, image_url).await?;
+    }
+
+    let content_hash = spam::content_hash(title, content);
+    let submitter_hash = spam::submitter_hash(&client.bucket_key());
+    let submission = Submission {
+        title,
+        body: content,
+        urls: [link_url.as_deref(), image_url.as_deref()].into_iter().flatten().collect(),
+        email: payload.author_email.as_deref(),
+        submitter_hash: &submitter_hash,
+        content_hash: &content_hash,
+        honeypot: payload.website.as_deref(),
+    };
+    let assessment = state.spam_scorer.assess(&state.pool, &submission).await;
+    if assessment.quarantined {
+        tracing::info!("Quarantining post from {} with spam score {}", client, assessment.score);
+    }
 
-    let post = sqlx::query_as::<_, Post>(
+    let mut post = sqlx::query_as::<_, Post>(
         r#"
         INSERT INTO posts (
             author_name, author_email, title, content, link_url, 
-            image_url, post_type, original_language, text_direction
+            image_url, post_type, original_language, text_direction,
+            image_id, spam_score, spam_reasons, quarantined,
+            content_hash, submitter_hash
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
         RETURNING *
         "#,
     )
-    .bind(&payload.author_name)
+    .bind(payload.author_name.trim())
     .bind(&payload.author_email)
-    .bind(&payload.title)
-    .bind(&payload.content)
-    .bind(&payload.link_url)
-    .bind(&payload.image_url)
-    .bind(&payload.post_type)
+    .bind(title)
+    .bind(content)
+    .bind(&link_url)
+    .bind(&image_url)
+    .bind(payload.post_type)
     .bind(&language)
     .bind(&text_direction)
+    .bind(&payload.image_id)
+    .bind(assessment.score)
+    .bind(sqlx::types::Json(&assessment.reasons))
+    .bind(assessment.quarantined)
+    .bind(&content_hash)
+    .bind(&submitter_hash)
     .fetch_one(&state.pool)
     .await
     .map_err(|e| {
         tracing::error!("Failed to create post: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        ApiError::Internal
     })?;
 
+    // A missing slug only costs the post its pretty permalink
+    match assign_slug(&state.pool, "posts", post.id, &post.title).await {
+        Ok(slug) => post.slug = slug,
+        Err(e) => tracing::warn!("Failed to assign slug to post {}: {:?}", post.id, e),
+    }
+
+    // Unfurl a link post's link while the post waits for moderation. Links in quarantined
+    // posts aren't visited unless a moderator lets the post through.
+    if post.post_type == PostType::Link && post.link_url.is_some() && !assessment.quarantined {
+        spawn_unfurl(&state, post.id);
+    }
+    // Quarantined posts are announced if and when a moderator publishes them
+    if !assessment.quarantined {
+        webhooks::enqueue_post(&state.pool, WebhookEvent::PostSubmitted, &post).await;
+    }
+
+    // The response doesn't reveal the assessment to whoever is probing it
     Ok(Json(json!({
         "post": post,
         "message": "Post submitted successfully and is awaiting moderation"
@@ -123,28 +352,239 @@ pub async fn create_post(
 
 pub async fn publish_post(
     State(state): State<SharedState>,
+    headers: HeaderMap,
     Path(id): Path<i32>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Update post to published
-    sqlx::query("UPDATE posts SET published = true, updated_at = NOW() WHERE id = $1")
-        .bind(id)
-        .execute(&state.pool)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to publish post: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
 
-    // Trigger translation job
-    if let Some(api_key) = &state.google_api_key {
+    let schedule: PublishPostRequest = if body.is_empty() {
+        PublishPostRequest::default()
+    } else {
+        serde_json::from_slice(&body).map_err(|e| {
+            tracing::debug!("Rejected publish request: {:?}", e);
+            ApiError::BadRequest
+        })?
+    };
+
+    // A publish_at in the past means now
+    let now = Utc::now();
+    let publish_at = schedule.publish_at.filter(|at| *at > now);
+    if schedule.expires_at.is_some_and(|expires_at| expires_at <= publish_at.unwrap_or(now)) {
+        return Err(ApiError::field("expires_at", FieldErrorCode::OutOfRange));
+    }
+
+    // Publishing is a moderator's verdict, so it also lifts quarantine. A post scheduled
+    // for later stays unpublished until the scheduler gets to it, and one already out
+    // keeps the time it was first published.
+    let released: Option<(bool, bool, PostType, Option<String>)> = sqlx::query_as(
+        r#"
+        UPDATE posts p SET
+            published = $2::timestamptz IS NULL,
+            published_at = CASE
+                WHEN $2::timestamptz IS NOT NULL THEN NULL
+                WHEN old.published THEN p.published_at
+                ELSE NOW()
+            END,
+            publish_at = $2,
+            expires_at = $3,
+            archived_at = NULL,
+            quarantined = false,
+            updated_at = NOW()
+        FROM (SELECT id, published, quarantined FROM posts WHERE id = $1 FOR UPDATE) old
+        WHERE p.id = old.id
+        RETURNING old.published, old.quarantined, p.post_type, p.link_url
+        "#,
+    )
+    .bind(id)
+    .bind(publish_at)
+    .bind(schedule.expires_at)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to publish post: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let Some((was_published, was_quarantined, post_type, link_url)) = released else {
+        return Err(ApiError::NotFound);
+    };
+
+    // Quarantined links were never unfurled
+    if was_quarantined && post_type == PostType::Link && link_url.is_some() {
+        spawn_unfurl(&state, id);
+    }
+
+    invalidate_published_post_counts(&state).await;
+    // Publishing a live post again only changes its schedule, which isn't announced
+    match (was_published, publish_at) {
+        (false, None) => {
+            state.notifier.content(ContentChange::Published, ContentKind::Post, id).await;
+            webhooks::enqueue_post_id(&state.pool, WebhookEvent::PostPublished, id).await;
+            activitypub::enqueue(&state.pool, ContentKind::Post, id).await;
+        }
+        // Down again until the new publish_at
+        (true, Some(_)) => state.notifier.content(ContentChange::Deleted, ContentKind::Post, id).await,
+        _ => {}
+    }
+
+    // Trigger translation job; scheduled posts are translated now, so they go live in
+    // every language at once
+    if let (false, Some(api_key)) = (was_published, &state.google_api_key) {
         let pool = std::sync::Arc::new(state.pool.clone());
+        let notifier = state.notifier.clone();
         let api_key = api_key.clone();
         tokio::spawn(async move {
-            crate::services::background::translate_post(pool, id, api_key).await;
+            crate::services::background::translate_post(pool, notifier, id, api_key).await;
         });
     }
 
-    Ok(Json(json!({
-        "message": "Post published successfully"
-    })))
+    Ok(Json(match publish_at {
+        Some(publish_at) => json!({
+            "message": "Post scheduled successfully",
+            "publish_at": publish_at
+        }),
+        None => json!({
+            "message": "Post published successfully"
+        }),
+    }))
+}
+
+fn spawn_unfurl(state: &SharedState, post_id: i32) {
+    let pool = std::sync::Arc::new(state.pool.clone());
+    let api_key = state.google_api_key.clone();
+    tokio::spawn(async move {
+        crate::services::background::unfurl_link(pool, post_id, api_key).await;
+    });
+}
+
+// Counts are cached per type filter, with the unfiltered count under the bare key
+fn published_post_count_key(post_type: Option<PostType>) -> String {
+    match post_type {
+        Some(post_type) => format!("{}:{}", PUBLISHED_POST_COUNT_KEY, post_type.as_str()),
+        None => PUBLISHED_POST_COUNT_KEY.to_string(),
+    }
+}
+
+pub(crate) async fn invalidate_published_post_counts(state: &SharedState) {
+    let keys = std::iter::once(None).chain(PostType::ALL.into_iter().map(Some));
+    for key in keys.map(published_post_count_key) {
+        cache::invalidate(&state.redis_client, &key).await;
+    }
+}
+
+// Cursor is the (created_at, id) of the last post on the page, as "<micros>:<id>"
+fn encode_cursor(created_at: DateTime<Utc>, id: i32) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(format!("{}:{}", created_at.timestamp_micros(), id))
+}
+
+fn decode_cursor(cursor: &str) -> Option<(DateTime<Utc>, i32)> {
+    let decoded = general_purpose::URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (micros, id) = decoded.split_once(':')?;
+    let created_at = DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+    Some((created_at, id.parse().ok()?))
+}
+
+/// Looks a published post up by id or slug, localized into `lang`.
+pub(crate) async fn fetch_published_post(
+    state: &SharedState,
+    lang: &str,
+    key: &str,
+) -> Result<Option<PostWithTranslation>, ApiError> {
+    // Numeric keys are ids, anything else is a slug (slugs are never purely numeric)
+    let id = key.parse::<i32>().ok();
+
+    sqlx::query_as::<_, PostWithTranslation>(&format!(
+        "{} WHERE p.published = true AND NOT p.hidden AND p.archived_at IS NULL AND (p.id = $2 OR p.slug = $3)",
+        POST_WITH_TRANSLATION_SELECT
+    ))
+    .bind(lang)
+    .bind(id)
+    .bind(id.is_none().then_some(key))
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch post: {:?}", e);
+        ApiError::Internal
+    })
+}
+
+/// The newest published posts localized into `lang`, pinned or not.
+pub(super) async fn fetch_recent_posts(
+    state: &SharedState,
+    lang: &str,
+    limit: i64,
+) -> Result<Vec<PostWithTranslation>, ApiError> {
+    sqlx::query_as::<_, PostWithTranslation>(&format!(
+        r#"
+        {POST_WITH_TRANSLATION_SELECT}
+        WHERE p.published = true AND NOT p.hidden AND p.archived_at IS NULL
+        ORDER BY p.created_at DESC, p.id DESC
+        LIMIT $2
+        "#
+    ))
+    .bind(lang)
+    .bind(limit)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch recent posts: {:?}", e);
+        ApiError::Internal
+    })
+}
+
+/// Published posts localized into `lang` that came out in `[from, to)`, newest first.
+pub(crate) async fn fetch_posts_published_between(
+    state: &SharedState,
+    lang: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<PostWithTranslation>, ApiError> {
+    sqlx::query_as::<_, PostWithTranslation>(&format!(
+        r#"
+        {POST_WITH_TRANSLATION_SELECT}
+        WHERE p.published = true AND NOT p.hidden AND p.archived_at IS NULL
+            AND p.published_at >= $2 AND p.published_at < $3
+        ORDER BY p.published_at DESC, p.id DESC
+        LIMIT $4
+        "#
+    ))
+    .bind(lang)
+    .bind(from)
+    .bind(to)
+    .bind(limit)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch posts published between {} and {}: {:?}", from, to, e);
+        ApiError::Internal
+    })
+}
+
+/// The original language first, then every language a translation exists for.
+pub(super) async fn available_languages(
+    state: &SharedState,
+    kind: ContentKind,
+    id: i32,
+    original_language: &str,
+) -> Result<Vec<String>, ApiError> {
+    let query = match kind {
+        ContentKind::Post => "SELECT language_code FROM post_translations WHERE post_id = $1 ORDER BY language_code",
+        ContentKind::Event => "SELECT language_code FROM event_translations WHERE event_id = $1 ORDER BY language_code",
+    };
+    let translated: Vec<String> = sqlx::query_scalar(query)
+        .bind(id)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch available languages: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    let mut languages = vec![original_language.to_string()];
+    languages.extend(translated.into_iter().filter(|l| l != original_language));
+    Ok(languages)
 }
\ No newline at end of file
@@ -0,0 +1,309 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use serde_json::json;
+
+use super::{admin::verify_admin_session, posts::invalidate_published_post_counts};
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldError},
+    middleware::client_ip::ClientIp,
+    models::{ReportRequest, ReportResolution, ReportedItem, ResolveReportsRequest},
+    services::{
+        notices::{ContentChange, ContentKind},
+        spam::submitter_hash,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReportTarget {
+    Post,
+    Event,
+}
+
+impl ReportTarget {
+    fn table(self) -> &'static str {
+        match self {
+            ReportTarget::Post => "posts",
+            ReportTarget::Event => "events",
+        }
+    }
+
+    fn kind(self) -> ContentKind {
+        match self {
+            ReportTarget::Post => ContentKind::Post,
+            ReportTarget::Event => ContentKind::Event,
+        }
+    }
+
+    // The column in reports pointing at the item
+    fn column(self) -> &'static str {
+        match self {
+            ReportTarget::Post => "post_id",
+            ReportTarget::Event => "event_id",
+        }
+    }
+}
+
+pub async fn report_post(
+    State(state): State<SharedState>,
+    client: ClientIp,
+    Path(id): Path<i32>,
+    Json(payload): Json<ReportRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    file_report(&state, ReportTarget::Post, id, &client, &payload).await
+}
+
+pub async fn report_event(
+    State(state): State<SharedState>,
+    client: ClientIp,
+    Path(id): Path<i32>,
+    Json(payload): Json<ReportRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    file_report(&state, ReportTarget::Event, id, &client, &payload).await
+}
+
+async fn file_report(
+    state: &SharedState,
+    target: ReportTarget,
+    id: i32,
+    client: &ClientIp,
+    payload: &ReportRequest,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let errors = FieldError::check(payload);
+    if !errors.is_empty() {
+        return Err(ApiError::Validation(errors));
+    }
+
+    let published: bool = sqlx::query_scalar(&format!(
+        "SELECT EXISTS(SELECT 1 FROM {} WHERE id = $1 AND published = true AND archived_at IS NULL)",
+        target.table()
+    ))
+    .bind(id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to look up reported item: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    if !published {
+        return Err(ApiError::NotFound);
+    }
+
+    // Reporting the same item again before it's reviewed changes nothing
+    let reporter_hash = submitter_hash(&client.bucket_key());
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO reports ({}, reason, comment, reporter_hash)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING
+        "#,
+        target.column()
+    ))
+    .bind(id)
+    .bind(payload.reason)
+    .bind(&payload.comment)
+    .bind(&reporter_hash)
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to file report: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    if state.report_hide_threshold > 0 {
+        hide_if_reported(state, target, id).await?;
+    }
+
+    Ok(Json(json!({
+        "message": "Report received and will be reviewed"
+    })))
+}
+
+// Enough independent reporters take the item down until a moderator looks at it
+async fn hide_if_reported(state: &SharedState, target: ReportTarget, id: i32) -> Result<(), ApiError> {
+    let reporters: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(DISTINCT reporter_hash) FROM reports WHERE {} = $1 AND status = 'pending'",
+        target.column()
+    ))
+    .bind(id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to count reports: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    if reporters < state.report_hide_threshold {
+        return Ok(());
+    }
+
+    let result = sqlx::query(&format!("UPDATE {} SET hidden = true WHERE id = $1 AND NOT hidden", target.table()))
+        .bind(id)
+        .execute(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to hide reported item: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    if result.rows_affected() > 0 {
+        tracing::info!("Hid {} {} after reports from {} addresses", target.table(), id, reporters);
+        if target == ReportTarget::Post {
+            invalidate_published_post_counts(state).await;
+        }
+        state.notifier.content(ContentChange::Deleted, target.kind(), id).await;
+    }
+    Ok(())
+}
+
+pub async fn get_reports(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let items = sqlx::query_as::<_, ReportedItem>(&format!(
+        "{} UNION ALL {} ORDER BY reporter_count DESC, last_reported_at DESC",
+        reported_items_query(ReportTarget::Post),
+        reported_items_query(ReportTarget::Event)
+    ))
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch reports: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "items": items
+    })))
+}
+
+fn reported_items_query(target: ReportTarget) -> String {
+    let (table, column) = (target.table(), target.column());
+    let item_type = match target {
+        ReportTarget::Post => "post",
+        ReportTarget::Event => "event",
+    };
+
+    format!(
+        r#"
+        SELECT
+            '{item_type}' AS item_type,
+            i.id AS item_id,
+            i.title,
+            i.slug,
+            i.hidden,
+            COUNT(*) AS report_count,
+            COUNT(DISTINCT r.reporter_hash) AS reporter_count,
+            (
+                SELECT jsonb_object_agg(reason, n)
+                FROM (
+                    SELECT reason, COUNT(*) AS n FROM reports
+                    WHERE {column} = i.id AND status = 'pending'
+                    GROUP BY reason
+                ) counts
+            ) AS reasons,
+            array_remove(array_agg(r.comment ORDER BY r.created_at), NULL) AS comments,
+            MIN(r.created_at) AS first_reported_at,
+            MAX(r.created_at) AS last_reported_at
+        FROM reports r
+        JOIN {table} i ON i.id = r.{column}
+        WHERE r.status = 'pending'
+        GROUP BY i.id
+        "#
+    )
+}
+
+pub async fn resolve_post_reports(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Json(payload): Json<ResolveReportsRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    resolve_reports(&state, ReportTarget::Post, id, payload.action).await
+}
+
+pub async fn resolve_event_reports(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Json(payload): Json<ResolveReportsRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    resolve_reports(&state, ReportTarget::Event, id, payload.action).await
+}
+
+// Closes every pending report on the item and shows or hides it accordingly
+async fn resolve_reports(
+    state: &SharedState,
+    target: ReportTarget,
+    id: i32,
+    action: ReportResolution,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let (status, hidden) = match action {
+        ReportResolution::Dismiss => ("dismissed", false),
+        ReportResolution::Uphold => ("upheld", true),
+    };
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        tracing::error!("Failed to start transaction: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let resolved = sqlx::query(&format!(
+        "UPDATE reports SET status = $2, resolved_at = NOW() WHERE {} = $1 AND status = 'pending'",
+        target.column()
+    ))
+    .bind(id)
+    .bind(status)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to resolve reports: {:?}", e);
+        ApiError::Internal
+    })?
+    .rows_affected();
+
+    if resolved == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    sqlx::query(&format!("UPDATE {} SET hidden = $2 WHERE id = $1", target.table()))
+        .bind(id)
+        .bind(hidden)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to update reported item: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit report resolution: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    if target == ReportTarget::Post {
+        invalidate_published_post_counts(state).await;
+    }
+    // A dismissed item that was hidden reappears; the stream skips it if it was never hidden
+    let change = if hidden { ContentChange::Deleted } else { ContentChange::Published };
+    state.notifier.content(change, target.kind(), id).await;
+
+    Ok(Json(json!({
+        "message": "Reports resolved successfully",
+        "resolved": resolved,
+        "hidden": hidden
+    })))
+}
@@ -0,0 +1,242 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::events::escape_like;
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldErrorCode},
+    models::SearchResult,
+    services::html::escape_html,
+};
+
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+const MAX_SEARCH_LIMIT: i64 = 50;
+const MAX_QUERY_LENGTH: usize = 200;
+
+// Content in languages whose text-search config ('simple') can't segment or stem words
+// is also matched by substring, using the trigram indexes
+const TRIGRAM_FALLBACK_LANGUAGES: &[&str] = &["zh", "ar"];
+
+// ts_headline markers; the snippet is HTML-escaped before these become <mark> tags
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_STOP: char = '\u{3}';
+const SNIPPET_CONTEXT_CHARS: usize = 80;
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub lang: Option<String>,
+    #[serde(rename = "type")]
+    pub result_type: Option<String>, // posts, events or all
+    pub limit: Option<i64>,
+}
+
+pub async fn search(
+    State(state): State<SharedState>,
+    Query(params): Query<SearchQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+    let q = params.q.trim();
+    if q.is_empty() {
+        return Err(ApiError::field("q", FieldErrorCode::Required));
+    }
+    if q.chars().count() > MAX_QUERY_LENGTH {
+        return Err(ApiError::field("q", FieldErrorCode::TooLong));
+    }
+
+    let limit = params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    if limit < 1 {
+        return Err(ApiError::field("limit", FieldErrorCode::OutOfRange));
+    }
+    let limit = limit.min(MAX_SEARCH_LIMIT);
+
+    let (search_posts, search_events) = match params.result_type.as_deref().unwrap_or("all") {
+        "all" => (true, true),
+        "posts" => (true, false),
+        "events" => (false, true),
+        _ => return Err(ApiError::field("type", FieldErrorCode::Invalid)),
+    };
+
+    let fallback_languages: Vec<String> = TRIGRAM_FALLBACK_LANGUAGES.iter().map(|l| l.to_string()).collect();
+    let pattern = format!("%{}%", escape_like(q));
+    let headline_options = format!(
+        "StartSel={}, StopSel={}, MaxWords=35, MinWords=15, MaxFragments=2",
+        HIGHLIGHT_START, HIGHLIGHT_STOP
+    );
+
+    let mut results = Vec::new();
+
+    if search_posts {
+        let posts = sqlx::query_as::<_, SearchResult>(
+            r#"
+            SELECT
+                'post' as result_type,
+                p.id,
+                p.slug,
+                COALESCE(pt.title, p.title) as title,
+                ts_headline(
+                    lancaster_ts_config(COALESCE(pt.language_code, p.original_language)),
+                    COALESCE(pt.content, p.content, ''),
+                    websearch_to_tsquery(lancaster_ts_config(COALESCE(pt.language_code, p.original_language)), $2),
+                    $3
+                ) as snippet,
+                COALESCE(pt.content, p.content) as body,
+                COALESCE(pt.text_direction, p.text_direction) as text_direction,
+                CASE WHEN pt.id IS NOT NULL AND p.original_language != $1 THEN true ELSE false END as is_translated,
+                NULL::date as event_date,
+                GREATEST(
+                    ts_rank(p.search_vector, websearch_to_tsquery(lancaster_ts_config(p.original_language), $2)),
+                    COALESCE(ts_rank(pt.search_vector, websearch_to_tsquery(lancaster_ts_config(pt.language_code), $2)), 0),
+                    CASE WHEN COALESCE(pt.language_code, p.original_language) = ANY($4)
+                        THEN similarity(COALESCE(pt.title, p.title), $2) ELSE 0 END
+                )::float4 as rank,
+                p.created_at
+            FROM posts p
+            LEFT JOIN post_translations pt ON p.id = pt.post_id AND pt.language_code = $1
+            WHERE p.published = true AND NOT p.hidden AND p.archived_at IS NULL
+              AND (
+                p.search_vector @@ websearch_to_tsquery(lancaster_ts_config(p.original_language), $2)
+                OR pt.search_vector @@ websearch_to_tsquery(lancaster_ts_config(pt.language_code), $2)
+                OR p.id IN (
+                    SELECT id FROM posts
+                    WHERE original_language = ANY($4) AND (title ILIKE $5 OR content ILIKE $5)
+                )
+                OR pt.id IN (
+                    SELECT id FROM post_translations
+                    WHERE language_code = $1 AND language_code = ANY($4) AND (title ILIKE $5 OR content ILIKE $5)
+                )
+              )
+            ORDER BY rank DESC, p.created_at DESC
+            LIMIT $6
+            "#,
+        )
+        .bind(&lang)
+        .bind(q)
+        .bind(&headline_options)
+        .bind(&fallback_languages)
+        .bind(&pattern)
+        .bind(limit)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to search posts: {:?}", e);
+            ApiError::Internal
+        })?;
+
+        results.extend(posts);
+    }
+
+    if search_events {
+        let events = sqlx::query_as::<_, SearchResult>(
+            r#"
+            SELECT
+                'event' as result_type,
+                e.id,
+                e.slug,
+                COALESCE(et.title, e.title) as title,
+                ts_headline(
+                    lancaster_ts_config(COALESCE(et.language_code, e.original_language)),
+                    COALESCE(et.description, e.description, ''),
+                    websearch_to_tsquery(lancaster_ts_config(COALESCE(et.language_code, e.original_language)), $2),
+                    $3
+                ) as snippet,
+                COALESCE(et.description, e.description) as body,
+                COALESCE(et.text_direction, e.text_direction) as text_direction,
+                CASE WHEN et.id IS NOT NULL AND e.original_language != $1 THEN true ELSE false END as is_translated,
+                e.event_date,
+                GREATEST(
+                    ts_rank(e.search_vector, websearch_to_tsquery(lancaster_ts_config(e.original_language), $2)),
+                    COALESCE(ts_rank(et.search_vector, websearch_to_tsquery(lancaster_ts_config(et.language_code), $2)), 0),
+                    CASE WHEN COALESCE(et.language_code, e.original_language) = ANY($4)
+                        THEN similarity(COALESCE(et.title, e.title), $2) ELSE 0 END
+                )::float4 as rank,
+                e.created_at
+            FROM events e
+            LEFT JOIN event_translations et ON e.id = et.event_id AND et.language_code = $1
+            WHERE e.published = true AND NOT e.hidden AND e.archived_at IS NULL
+              AND (
+                e.search_vector @@ websearch_to_tsquery(lancaster_ts_config(e.original_language), $2)
+                OR et.search_vector @@ websearch_to_tsquery(lancaster_ts_config(et.language_code), $2)
+                OR e.id IN (
+                    SELECT id FROM events
+                    WHERE original_language = ANY($4) AND (title ILIKE $5 OR description ILIKE $5 OR location ILIKE $5)
+                )
+                OR et.id IN (
+                    SELECT id FROM event_translations
+                    WHERE language_code = $1 AND language_code = ANY($4) AND (title ILIKE $5 OR description ILIKE $5)
+                )
+              )
+            ORDER BY rank DESC, e.event_date ASC
+            LIMIT $6
+            "#,
+        )
+        .bind(&lang)
+        .bind(q)
+        .bind(&headline_options)
+        .bind(&fallback_languages)
+        .bind(&pattern)
+        .bind(limit)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to search events: {:?}", e);
+            ApiError::Internal
+        })?;
+
+        results.extend(events);
+    }
+
+    results.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+    results.truncate(limit as usize);
+
+    for result in &mut results {
+        result.snippet = render_snippet(result, q);
+    }
+
+    Ok(Json(json!({
+        "results": results,
+        "query": q,
+        "language": lang
+    })))
+}
+
+fn render_snippet(result: &SearchResult, q: &str) -> String {
+    if result.snippet.contains(HIGHLIGHT_START) {
+        return escape_html(&result.snippet)
+            .replace(HIGHLIGHT_START, "<mark>")
+            .replace(HIGHLIGHT_STOP, "</mark>");
+    }
+
+    // Full-text search found nothing to highlight (e.g. unsegmented Chinese), so
+    // cut a window around the literal match instead
+    let body = result.body.as_deref().unwrap_or("");
+    let matcher = regex::RegexBuilder::new(&regex::escape(q))
+        .case_insensitive(true)
+        .build();
+
+    let found = matcher.ok().and_then(|m| m.find(body));
+    let Some(found) = found else {
+        return escape_html(&body.chars().take(SNIPPET_CONTEXT_CHARS * 2).collect::<String>());
+    };
+
+    let before: String = body[..found.start()]
+        .chars()
+        .rev()
+        .take(SNIPPET_CONTEXT_CHARS)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let after: String = body[found.end()..].chars().take(SNIPPET_CONTEXT_CHARS).collect();
+
+    format!(
+        "{}<mark>{}</mark>{}",
+        escape_html(&before),
+        escape_html(found.as_str()),
+        escape_html(&after)
+    )
+}
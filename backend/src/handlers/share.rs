@@ -0,0 +1,275 @@
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{Html, IntoResponse, Response},
+};
+use chrono::{Duration, Utc};
+
+use super::{
+    events::fetch_published_event,
+    languages::is_enabled_language,
+    posts::{available_languages, fetch_published_post},
+};
+use crate::{
+    db::SharedState,
+    error::ApiError,
+    services::{
+        html::escape_html, notices::ContentKind, recurrence::RecurrenceRule, translation::content_language,
+    },
+};
+
+const SITE_NAME: &str = "Lancaster Local";
+const DESCRIPTION_MAX_CHARS: usize = 200;
+const NEXT_OCCURRENCE_LOOKAHEAD_DAYS: i64 = 366;
+
+// Crawlers fetch a link once per share; a short cache absorbs a post going round a group chat
+const SHARE_PAGE_CACHE_CONTROL: &str = "public, max-age=300";
+
+struct SharePage<'a> {
+    kind: &'a str, // "posts" or "events", as in the share and SPA paths
+    key: String,
+    requested_language: &'a str,
+    content_language: &'a str,
+    text_direction: &'a str,
+    title: &'a str,
+    description: String,
+    image_url: Option<&'a str>,
+    og_type: &'a str,
+    published_time: Option<String>,
+    available_languages: Vec<String>,
+    original_language: &'a str,
+}
+
+pub async fn share_post(
+    State(state): State<SharedState>,
+    Path((lang, key)): Path<(String, String)>,
+) -> Result<Response, ApiError> {
+    verify_language(&state, &lang).await?;
+
+    let post = fetch_published_post(&state, &lang, &key)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let available_languages = available_languages(&state, ContentKind::Post, post.id, &post.original_language).await?;
+
+    let page = SharePage {
+        kind: "posts",
+        key: post.slug.clone().unwrap_or_else(|| post.id.to_string()),
+        requested_language: &lang,
+        content_language: content_language(&lang, &post.original_language, post.is_translated),
+        text_direction: &post.text_direction,
+        title: &post.title,
+        description: summarize(post.content.as_deref().unwrap_or("")),
+        image_url: post.image_url.as_deref(),
+        og_type: "article",
+        published_time: Some(post.created_at.to_rfc3339()),
+        available_languages,
+        original_language: &post.original_language,
+    };
+
+    Ok(render(&state.public_url, &page))
+}
+
+pub async fn share_event(
+    State(state): State<SharedState>,
+    Path((lang, key)): Path<(String, String)>,
+) -> Result<Response, ApiError> {
+    verify_language(&state, &lang).await?;
+
+    let event = fetch_published_event(&state, &lang, &key)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+    let available_languages = available_languages(&state, ContentKind::Event, event.id, &event.original_language).await?;
+
+    // Previews are often all people read, so lead with when and where; for a
+    // series that's the next date rather than the day it started
+    let today = Utc::now().date_naive();
+    let next_date = event
+        .recurrence_rule
+        .as_deref()
+        .and_then(|rule| RecurrenceRule::parse(rule).ok())
+        .and_then(|rule| {
+            rule.occurrences(
+                event.event_date,
+                &event.recurrence_exdates,
+                today,
+                today + Duration::days(NEXT_OCCURRENCE_LOOKAHEAD_DAYS),
+            )
+            .first()
+            .copied()
+        })
+        .unwrap_or(event.event_date);
+
+    let mut details = vec![next_date.format("%Y-%m-%d").to_string()];
+    if let Some(time) = event.event_time {
+        details.push(time.format("%H:%M").to_string());
+    }
+    if let Some(location) = &event.location {
+        details.push(location.clone());
+    }
+    let description = format!(
+        "{} — {}",
+        details.join(" · "),
+        summarize(event.description.as_deref().unwrap_or(""))
+    );
+
+    let page = SharePage {
+        kind: "events",
+        key: event.slug.clone().unwrap_or_else(|| event.id.to_string()),
+        requested_language: &lang,
+        content_language: content_language(&lang, &event.original_language, event.is_translated),
+        text_direction: &event.text_direction,
+        title: &event.title,
+        description: description.trim_end_matches([' ', '—']).to_string(),
+        image_url: None,
+        og_type: "website",
+        published_time: None,
+        available_languages,
+        original_language: &event.original_language,
+    };
+
+    Ok(render(&state.public_url, &page))
+}
+
+async fn verify_language(state: &SharedState, lang: &str) -> Result<(), ApiError> {
+    if is_enabled_language(state, lang).await? {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound)
+    }
+}
+
+fn summarize(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= DESCRIPTION_MAX_CHARS {
+        return text;
+    }
+
+    let truncated: String = text.chars().take(DESCRIPTION_MAX_CHARS - 1).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+// Open Graph wants language_TERRITORY; only the languages we ship are mapped
+fn og_locale(lang: &str) -> Option<&'static str> {
+    Some(match lang {
+        "en" => "en_US",
+        "es" => "es_ES",
+        "de" => "de_DE",
+        "fr" => "fr_FR",
+        "zh" => "zh_CN",
+        "ar" => "ar_AR",
+        "he" => "he_IL",
+        "fa" => "fa_IR",
+        "ur" => "ur_PK",
+        _ => return None,
+    })
+}
+
+fn render(public_url: &str, page: &SharePage) -> Response {
+    let share_url = |lang: &str| format!("{}/share/{}/{}/{}", public_url, lang, page.kind, page.key);
+    let app_url = format!(
+        "{}/{}/{}?lang={}",
+        public_url, page.kind, page.key, page.requested_language
+    );
+
+    // Untranslated languages render the original text, so the original is canonical for them
+    let canonical = if page.available_languages.iter().any(|l| l == page.requested_language) {
+        share_url(page.requested_language)
+    } else {
+        share_url(page.original_language)
+    };
+
+    let mut meta = vec![
+        format!(r#"<link rel="canonical" href="{}">"#, escape_html(&canonical)),
+        format!(r#"<meta name="description" content="{}">"#, escape_html(&page.description)),
+        format!(r#"<meta property="og:site_name" content="{}">"#, SITE_NAME),
+        format!(r#"<meta property="og:type" content="{}">"#, page.og_type),
+        format!(r#"<meta property="og:title" content="{}">"#, escape_html(page.title)),
+        format!(r#"<meta property="og:description" content="{}">"#, escape_html(&page.description)),
+        format!(r#"<meta property="og:url" content="{}">"#, escape_html(&canonical)),
+    ];
+
+    if let Some(locale) = og_locale(page.content_language) {
+        meta.push(format!(r#"<meta property="og:locale" content="{}">"#, locale));
+    }
+
+    for lang in &page.available_languages {
+        meta.push(format!(
+            r#"<link rel="alternate" hreflang="{}" href="{}">"#,
+            escape_html(lang),
+            escape_html(&share_url(lang))
+        ));
+        if lang != page.content_language {
+            if let Some(locale) = og_locale(lang) {
+                meta.push(format!(r#"<meta property="og:locale:alternate" content="{}">"#, locale));
+            }
+        }
+    }
+    meta.push(format!(
+        r#"<link rel="alternate" hreflang="x-default" href="{}">"#,
+        escape_html(&share_url(page.original_language))
+    ));
+
+    if let Some(published_time) = &page.published_time {
+        meta.push(format!(r#"<meta property="article:published_time" content="{}">"#, published_time));
+    }
+
+    // Relative image paths are served from our own origin; anything else isn't previewable
+    let image_url = page.image_url.and_then(|url| {
+        if url.starts_with("https://") || url.starts_with("http://") {
+            Some(url.to_string())
+        } else if url.starts_with('/') {
+            Some(format!("{}{}", public_url, url))
+        } else {
+            None
+        }
+    });
+
+    match &image_url {
+        Some(url) => {
+            meta.push(format!(r#"<meta property="og:image" content="{}">"#, escape_html(url)));
+            meta.push(r#"<meta name="twitter:card" content="summary_large_image">"#.to_string());
+            meta.push(format!(r#"<meta name="twitter:image" content="{}">"#, escape_html(url)));
+        }
+        None => meta.push(r#"<meta name="twitter:card" content="summary">"#.to_string()),
+    }
+    meta.push(format!(r#"<meta name="twitter:title" content="{}">"#, escape_html(page.title)));
+    meta.push(format!(r#"<meta name="twitter:description" content="{}">"#, escape_html(&page.description)));
+
+    // Crawlers don't run scripts, so they stay on this page; browsers are sent on to the app
+    let redirect = serde_json::to_string(&app_url)
+        .unwrap_or_default()
+        .replace("</", "<\\/");
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="{lang}" dir="{dir}">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title} · {site}</title>
+{meta}
+<script>window.location.replace({redirect});</script>
+</head>
+<body>
+<h1>{title}</h1>
+<p>{description}</p>
+<p><a href="{app_url}">{site}</a></p>
+</body>
+</html>
+"#,
+        lang = escape_html(page.content_language),
+        dir = escape_html(page.text_direction),
+        title = escape_html(page.title),
+        site = SITE_NAME,
+        meta = meta.join("\n"),
+        redirect = redirect,
+        description = escape_html(&page.description),
+        app_url = escape_html(&app_url),
+    );
+
+    (
+        [(header::CACHE_CONTROL, SHARE_PAGE_CACHE_CONTROL)],
+        Html(html),
+    )
+        .into_response()
+}
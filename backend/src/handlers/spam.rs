@@ -0,0 +1,104 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use serde_json::json;
+
+use super::admin::verify_admin_session;
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldErrorCode},
+    models::{BlockedDomain, BlockedDomainRequest},
+    services::spam::normalize_domain,
+};
+
+lazy_static::lazy_static! {
+    static ref DOMAIN_REGEX: regex::Regex =
+        regex::Regex::new(r"^([a-z0-9]([a-z0-9-]*[a-z0-9])?\.)+[a-z0-9-]{2,}$").unwrap();
+}
+
+pub async fn get_blocked_domains(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let domains = sqlx::query_as::<_, BlockedDomain>("SELECT * FROM blocked_domains ORDER BY domain")
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch blocked domains: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    Ok(Json(json!({
+        "domains": domains
+    })))
+}
+
+pub async fn create_blocked_domain(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(payload): Json<BlockedDomainRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let domain = normalize_domain(&payload.domain);
+    if domain.is_empty() {
+        return Err(ApiError::field("domain", FieldErrorCode::Required));
+    }
+    if domain.len() > 255 || !DOMAIN_REGEX.is_match(&domain) {
+        return Err(ApiError::field("domain", FieldErrorCode::Invalid));
+    }
+
+    let blocked = sqlx::query_as::<_, BlockedDomain>(
+        r#"
+        INSERT INTO blocked_domains (domain, note)
+        VALUES ($1, $2)
+        ON CONFLICT (domain) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(&domain)
+    .bind(payload.note.as_deref().map(str::trim).filter(|n| !n.is_empty()))
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to block domain: {:?}", e);
+        ApiError::Internal
+    })?
+    .ok_or(ApiError::Conflict("domain"))?;
+
+    Ok(Json(json!({
+        "domain": blocked
+    })))
+}
+
+pub async fn delete_blocked_domain(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(domain): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let result = sqlx::query("DELETE FROM blocked_domains WHERE domain = $1")
+        .bind(normalize_domain(&domain))
+        .execute(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to unblock domain: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(Json(json!({
+        "message": "Domain unblocked successfully"
+    })))
+}
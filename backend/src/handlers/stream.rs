@@ -0,0 +1,118 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::{stream, Stream};
+use serde_json::json;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use super::{
+    events::fetch_published_event,
+    languages::is_enabled_language,
+    posts::{fetch_published_post, LangQuery},
+};
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldErrorCode},
+    services::notices::{ContentChange, ContentKind, LocalizedNotice, Notice},
+};
+
+/// Server-Sent Events: `published`, `updated` and `deleted` as posts and events change,
+/// from any instance. Published and updated items come localized into `lang`, so an
+/// `updated` follows once a new item's translation is ready. Nothing is replayed on
+/// connect; clients load the feed first and apply changes on top.
+pub async fn content_stream(
+    State(state): State<SharedState>,
+    Query(params): Query<LangQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let lang = params.lang.unwrap_or_else(|| "en".to_string());
+    // Each language gets a channel, so only enabled ones are accepted
+    if !is_enabled_language(&state, &lang).await? {
+        return Err(ApiError::field("lang", FieldErrorCode::Unknown));
+    }
+
+    let (notices, new_channel) = state.localized_notices.subscribe(&lang);
+    if let Some(sender) = new_channel {
+        let raw = state.notifier.subscribe();
+        tokio::spawn(localize_notices(state.clone(), lang, raw, sender));
+    }
+
+    let live = stream::unfold(notices, |mut notices| async move {
+        loop {
+            match notices.recv().await {
+                Ok(notice) => return Some((Ok(content_event(notice)), notices)),
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("Content stream fell behind by {} notices", missed);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(live).keep_alive(KeepAlive::default()))
+}
+
+/// Feeds one language's channel, looking each content notice up once for all of its
+/// streams, until none are left.
+async fn localize_notices(
+    state: SharedState,
+    lang: String,
+    mut notices: broadcast::Receiver<Notice>,
+    sender: broadcast::Sender<LocalizedNotice>,
+) {
+    loop {
+        let (change, kind, id) = match notices.recv().await {
+            Ok(Notice::Content { change, kind, id }) => (change, kind, id),
+            // Alerts are for /api/alerts/stream
+            Ok(_) => continue,
+            Err(RecvError::Lagged(missed)) => {
+                tracing::warn!("Localized {} notices fell behind by {}", lang, missed);
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+        if state.localized_notices.close_if_unused(&lang) {
+            return;
+        }
+
+        let item = match change {
+            ContentChange::Deleted => None,
+            ContentChange::Published | ContentChange::Updated => match localized_item(&state, &lang, kind, id).await {
+                Ok(Some(item)) => Some(item),
+                // No longer visible, or logged
+                Ok(None) | Err(_) => continue,
+            },
+        };
+        // The last stream may have closed during the lookup
+        let _ = sender.send(LocalizedNotice { change, kind, id, item });
+    }
+}
+
+async fn localized_item(
+    state: &SharedState,
+    lang: &str,
+    kind: ContentKind,
+    id: i32,
+) -> Result<Option<serde_json::Value>, ApiError> {
+    let key = id.to_string();
+    Ok(match kind {
+        ContentKind::Post => fetch_published_post(state, lang, &key).await?.map(|post| json!(post)),
+        ContentKind::Event => fetch_published_event(state, lang, &key).await?.map(|event| json!(event)),
+    })
+}
+
+fn content_event(notice: LocalizedNotice) -> Event {
+    let LocalizedNotice { change, kind, id, item } = notice;
+    let name = match change {
+        ContentChange::Published => "published",
+        ContentChange::Updated => "updated",
+        ContentChange::Deleted => "deleted",
+    };
+    let mut data = json!({ "type": kind, "id": id });
+    if let Some(item) = item {
+        data["item"] = item;
+    }
+    Event::default().event(name).data(data.to_string())
+}
@@ -0,0 +1,272 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde_json::json;
+
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldErrorCode},
+    models::{Image, RemoteImage},
+    services::{
+        images::{
+            self, process_remote, process_upload, sha256_hex, Variant, ACCEPTED_CONTENT_TYPES, MAX_UPLOAD_BYTES,
+        },
+        remote::fetch_public,
+    },
+};
+
+// Variants are content-addressed, so a URL's bytes never change
+const IMAGE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+// The remote original may change, so proxied images are only cached for a day
+const PROXIED_IMAGE_CACHE_CONTROL: &str = "public, max-age=86400";
+const MAX_REMOTE_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+const REMOTE_IMAGE_RETRY_MINUTES: i64 = 15;
+
+lazy_static::lazy_static! {
+    static ref IMAGE_KEY_REGEX: regex::Regex = regex::Regex::new(r"^[0-9a-f]{64}\.(jpg|png)$").unwrap();
+    static ref REMOTE_IMAGE_ID_REGEX: regex::Regex = regex::Regex::new(r"^[0-9a-f]{64}$").unwrap();
+}
+
+pub async fn upload_image(
+    State(state): State<SharedState>,
+    mut multipart: Multipart,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let mut upload = None;
+
+    while let Some(mut field) = multipart.next_field().await.map_err(|e| ApiError::from_status(e.status()))? {
+        if field.name() != Some("image") {
+            continue;
+        }
+
+        // The declared type is only a first filter; the bytes are sniffed when decoding
+        let content_type = field.content_type().unwrap_or("").to_lowercase();
+        if !ACCEPTED_CONTENT_TYPES.contains(&content_type.as_str()) {
+            return Err(ApiError::UnsupportedMediaType);
+        }
+
+        let mut bytes = Vec::new();
+        // Body-limit violations surface here as 413
+        while let Some(chunk) = field.chunk().await.map_err(|e| ApiError::from_status(e.status()))? {
+            if bytes.len() + chunk.len() > MAX_UPLOAD_BYTES {
+                return Err(ApiError::PayloadTooLarge);
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        upload = Some(bytes);
+        break;
+    }
+
+    let bytes = upload.filter(|b| !b.is_empty()).ok_or(ApiError::field("image", FieldErrorCode::Required))?;
+    let id = sha256_hex(&bytes);
+
+    // The same photo uploaded again reuses what's already stored
+    if let Some(image) = find_image(&state, &id).await? {
+        return Ok(Json(image_json(&image)));
+    }
+
+    let processed = tokio::task::spawn_blocking(move || process_upload(&bytes))
+        .await
+        .map_err(|e| {
+            tracing::error!("Image processing task failed: {:?}", e);
+            ApiError::Internal
+        })?
+        .map_err(|e| {
+            tracing::debug!("Rejected image upload: {:?}", e);
+            ApiError::UnsupportedMediaType
+        })?;
+
+    for variant in [&processed.display, &processed.thumbnail] {
+        state
+            .blob_store
+            .put(&variant.key, variant.bytes.clone(), variant.content_type)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to store image {}: {:?}", variant.key, e);
+                ApiError::Internal
+            })?;
+    }
+
+    let image = sqlx::query_as::<_, Image>(
+        r#"
+        INSERT INTO images (id, display_key, thumbnail_key, content_type, width, height)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (id) DO UPDATE SET id = EXCLUDED.id
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .bind(&processed.display.key)
+    .bind(&processed.thumbnail.key)
+    .bind(processed.display.content_type)
+    .bind(processed.width as i32)
+    .bind(processed.height as i32)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to save image: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(image_json(&image)))
+}
+
+pub async fn get_image(
+    State(state): State<SharedState>,
+    Path(key): Path<String>,
+) -> Result<Response, ApiError> {
+    if !IMAGE_KEY_REGEX.is_match(&key) {
+        return Err(ApiError::NotFound);
+    }
+
+    let bytes = state
+        .blob_store
+        .get(&key)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read image {}: {:?}", key, e);
+            ApiError::Internal
+        })?
+        .ok_or(ApiError::NotFound)?;
+
+    Ok(image_response(&key, bytes, IMAGE_CACHE_CONTROL))
+}
+
+/// Serves a remote image registered by a submission, fetching and re-encoding it on
+/// first use, so readers' browsers never contact the third-party host.
+pub async fn proxy_image(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> Result<Response, ApiError> {
+    if !REMOTE_IMAGE_ID_REGEX.is_match(&id) {
+        return Err(ApiError::NotFound);
+    }
+
+    let remote = sqlx::query_as::<_, RemoteImage>("SELECT * FROM remote_images WHERE id = $1")
+        .bind(&id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to look up remote image: {:?}", e);
+            ApiError::Internal
+        })?
+        .ok_or(ApiError::NotFound)?;
+
+    if let Some(key) = &remote.image_key {
+        match state.blob_store.get(key).await {
+            Ok(Some(bytes)) => return Ok(image_response(key, bytes, PROXIED_IMAGE_CACHE_CONTROL)),
+            Ok(None) => {} // Lost from storage; fetch it again
+            Err(e) => tracing::warn!("Failed to read proxied image {}: {:?}", key, e),
+        }
+    }
+
+    let retry_after = Utc::now() - Duration::minutes(REMOTE_IMAGE_RETRY_MINUTES);
+    if remote.failed_at.is_some_and(|failed_at| failed_at > retry_after) {
+        return Err(ApiError::BadGateway);
+    }
+
+    let variant = match fetch_remote_image(&remote.url).await {
+        Ok(variant) => variant,
+        Err(e) => {
+            tracing::warn!("Failed to proxy {}: {:?}", remote.url, e);
+            sqlx::query("UPDATE remote_images SET failed_at = NOW() WHERE id = $1")
+                .bind(&id)
+                .execute(&state.pool)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to record remote image failure: {:?}", e);
+                    ApiError::Internal
+                })?;
+            return Err(ApiError::BadGateway);
+        }
+    };
+
+    state
+        .blob_store
+        .put(&variant.key, variant.bytes.clone(), variant.content_type)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to store image {}: {:?}", variant.key, e);
+            ApiError::Internal
+        })?;
+
+    sqlx::query(
+        "UPDATE remote_images SET image_key = $2, fetched_at = NOW(), failed_at = NULL WHERE id = $1"
+    )
+    .bind(&id)
+    .bind(&variant.key)
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to save remote image: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(image_response(&variant.key, variant.bytes, PROXIED_IMAGE_CACHE_CONTROL))
+}
+
+async fn fetch_remote_image(url: &str) -> anyhow::Result<Variant> {
+    let response = fetch_public(url, "image/*", MAX_REMOTE_IMAGE_BYTES).await?;
+    if !response.content_type.as_deref().unwrap_or("").starts_with("image/") {
+        anyhow::bail!("Not an image: {:?}", response.content_type);
+    }
+
+    // Re-encoding means we only ever serve pixels, never whatever else the file held
+    tokio::task::spawn_blocking(move || process_remote(&response.bytes)).await?
+}
+
+/// Makes a submitted image URL available through the proxy.
+pub(super) async fn register_remote_image(state: &SharedState, url: &str) -> Result<(), ApiError> {
+    images::register_remote_image(&state.pool, url).await.map_err(|e| {
+        tracing::error!("Failed to register remote image: {:?}", e);
+        ApiError::Internal
+    })
+}
+
+fn image_response(key: &str, bytes: Vec<u8>, cache_control: &'static str) -> Response {
+    let content_type = if key.ends_with(".png") { "image/png" } else { "image/jpeg" };
+
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, cache_control),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        ],
+        bytes,
+    )
+        .into_response()
+}
+
+/// Rejects submissions that reference an image that was never uploaded.
+pub(super) async fn verify_image(state: &SharedState, image_id: Option<&str>) -> Result<(), ApiError> {
+    match image_id {
+        Some(id) if find_image(state, id).await?.is_none() => Err(ApiError::field("image_id", FieldErrorCode::Unknown)),
+        _ => Ok(()),
+    }
+}
+
+async fn find_image(state: &SharedState, id: &str) -> Result<Option<Image>, ApiError> {
+    sqlx::query_as::<_, Image>("SELECT * FROM images WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to look up image: {:?}", e);
+            ApiError::Internal
+        })
+}
+
+fn image_json(image: &Image) -> serde_json::Value {
+    json!({
+        "image_id": image.id,
+        "image_url": format!("/api/images/{}", image.display_key),
+        "thumbnail_url": format!("/api/images/{}", image.thumbnail_key),
+        "width": image.width,
+        "height": image.height
+    })
+}
@@ -0,0 +1,347 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use serde_json::json;
+
+use super::admin::verify_admin_session;
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldError, FieldErrorCode},
+    models::{MergeVenueRequest, Venue, VenueRequest},
+    services::geocoding::{venue_dedupe_key, GeocodeResult},
+};
+
+pub async fn get_venues(
+    State(state): State<SharedState>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let venues = sqlx::query_as::<_, Venue>("SELECT * FROM venues ORDER BY name")
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch venues: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    Ok(Json(json!({
+        "venues": venues
+    })))
+}
+
+pub async fn create_venue(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(payload): Json<VenueRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    validate_venue(&payload)?;
+    let dedupe_key = venue_dedupe_key(&payload.name, payload.address.as_deref());
+
+    // The same place entered twice resolves to the existing venue
+    if let Some(existing) = find_by_dedupe_key(&state, &dedupe_key).await? {
+        return Ok(Json(json!({
+            "venue": existing,
+            "duplicate": true
+        })));
+    }
+
+    let (latitude, longitude) = resolve_coordinates(&state, &payload).await;
+
+    let venue = sqlx::query_as::<_, Venue>(
+        r#"
+        INSERT INTO venues (
+            name, address, latitude, longitude,
+            accessibility_info, wheelchair_accessible, dedupe_key
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+    )
+    .bind(payload.name.trim())
+    .bind(payload.address.as_deref().map(str::trim))
+    .bind(latitude)
+    .bind(longitude)
+    .bind(&payload.accessibility_info)
+    .bind(payload.wheelchair_accessible)
+    .bind(&dedupe_key)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create venue: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "venue": venue,
+        "duplicate": false
+    })))
+}
+
+pub async fn update_venue(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Json(payload): Json<VenueRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    validate_venue(&payload)?;
+    let dedupe_key = venue_dedupe_key(&payload.name, payload.address.as_deref());
+
+    if find_by_dedupe_key(&state, &dedupe_key)
+        .await?
+        .is_some_and(|existing| existing.id != id)
+    {
+        return Err(ApiError::Conflict("name"));
+    }
+
+    let (latitude, longitude) = resolve_coordinates(&state, &payload).await;
+
+    let venue = sqlx::query_as::<_, Venue>(
+        r#"
+        UPDATE venues SET
+            name = $2,
+            address = $3,
+            latitude = $4,
+            longitude = $5,
+            accessibility_info = $6,
+            wheelchair_accessible = $7,
+            dedupe_key = $8,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(payload.name.trim())
+    .bind(payload.address.as_deref().map(str::trim))
+    .bind(latitude)
+    .bind(longitude)
+    .bind(&payload.accessibility_info)
+    .bind(payload.wheelchair_accessible)
+    .bind(&dedupe_key)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update venue: {:?}", e);
+        ApiError::Internal
+    })?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(json!({
+        "venue": venue
+    })))
+}
+
+pub async fn delete_venue(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    // Events at this venue keep their free-text location (ON DELETE SET NULL)
+    let result = sqlx::query("DELETE FROM venues WHERE id = $1")
+        .bind(id)
+        .execute(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete venue: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(Json(json!({
+        "message": "Venue deleted successfully"
+    })))
+}
+
+pub async fn geocode_venue(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let venue = sqlx::query_as::<_, Venue>("SELECT * FROM venues WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch venue: {:?}", e);
+            ApiError::Internal
+        })?
+        .ok_or(ApiError::NotFound)?;
+
+    let address = venue.address.as_deref().ok_or(ApiError::field("address", FieldErrorCode::Required))?;
+    let result = geocode(&state, address)
+        .await
+        .ok_or(ApiError::GeocodingFailed)?;
+
+    let venue = sqlx::query_as::<_, Venue>(
+        "UPDATE venues SET latitude = $2, longitude = $3, updated_at = NOW() WHERE id = $1 RETURNING *"
+    )
+    .bind(id)
+    .bind(result.latitude)
+    .bind(result.longitude)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update venue coordinates: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "venue": venue,
+        "display_name": result.display_name
+    })))
+}
+
+pub async fn merge_venue(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Json(payload): Json<MergeVenueRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    if payload.into_venue_id == id {
+        return Err(ApiError::field("into_venue_id", FieldErrorCode::Invalid));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        tracing::error!("Failed to start transaction: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    let target_exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM venues WHERE id = $1)")
+        .bind(payload.into_venue_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch venue: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    if !target_exists {
+        return Err(ApiError::NotFound);
+    }
+
+    let moved = sqlx::query("UPDATE events SET venue_id = $2 WHERE venue_id = $1")
+        .bind(id)
+        .bind(payload.into_venue_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to move events between venues: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    let deleted = sqlx::query("DELETE FROM venues WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete merged venue: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    if deleted.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    tx.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit venue merge: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "message": "Venues merged successfully",
+        "events_moved": moved.rows_affected()
+    })))
+}
+
+fn validate_venue(payload: &VenueRequest) -> Result<(), ApiError> {
+    let mut errors = Vec::new();
+    let mut reject = |field: &str, code| errors.push(FieldError { field: field.to_string(), code });
+
+    let name = payload.name.trim();
+    if name.is_empty() {
+        reject("name", FieldErrorCode::Required);
+    } else if name.chars().count() > 255 {
+        reject("name", FieldErrorCode::TooLong);
+    }
+
+    if payload.address.as_ref().is_some_and(|a| a.chars().count() > 500) {
+        reject("address", FieldErrorCode::TooLong);
+    }
+
+    // Coordinates come as a pair or not at all
+    match (payload.latitude, payload.longitude) {
+        (None, None) => {}
+        (None, Some(_)) => reject("latitude", FieldErrorCode::Required),
+        (Some(_), None) => reject("longitude", FieldErrorCode::Required),
+        (Some(lat), Some(lon)) => {
+            if !(-90.0..=90.0).contains(&lat) {
+                reject("latitude", FieldErrorCode::OutOfRange);
+            }
+            if !(-180.0..=180.0).contains(&lon) {
+                reject("longitude", FieldErrorCode::OutOfRange);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ApiError::Validation(errors))
+    }
+}
+
+async fn find_by_dedupe_key(
+    state: &SharedState,
+    dedupe_key: &str,
+) -> Result<Option<Venue>, ApiError> {
+    sqlx::query_as::<_, Venue>("SELECT * FROM venues WHERE dedupe_key = $1")
+        .bind(dedupe_key)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to look up venue: {:?}", e);
+            ApiError::Internal
+        })
+}
+
+// Explicit coordinates win; otherwise the address is geocoded (best effort)
+async fn resolve_coordinates(state: &SharedState, payload: &VenueRequest) -> (Option<f64>, Option<f64>) {
+    if payload.latitude.is_some() {
+        return (payload.latitude, payload.longitude);
+    }
+
+    match payload.address.as_deref() {
+        Some(address) => geocode(state, address)
+            .await
+            .map_or((None, None), |r| (Some(r.latitude), Some(r.longitude))),
+        None => (None, None),
+    }
+}
+
+async fn geocode(state: &SharedState, address: &str) -> Option<GeocodeResult> {
+    match state.geocoder.geocode(address).await {
+        Ok(result) => result,
+        Err(e) => {
+            tracing::warn!("Failed to geocode {:?}: {:?}", address, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::admin::verify_admin_session;
+use crate::{
+    db::SharedState,
+    error::{ApiError, FieldError, FieldErrorCode},
+    models::{Webhook, WebhookDelivery, WebhookDeliveryStatus, WebhookRequest},
+    services::{urls::normalize_url, webhooks},
+};
+
+const DEFAULT_DELIVERIES_PAGE_SIZE: i64 = 50;
+const MAX_DELIVERIES_PAGE_SIZE: i64 = 200;
+
+#[derive(Deserialize)]
+pub struct DeliveriesQuery {
+    pub status: Option<WebhookDeliveryStatus>,
+    pub limit: Option<i64>,
+}
+
+pub async fn get_webhooks(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let webhooks = sqlx::query_as::<_, Webhook>("SELECT * FROM webhooks ORDER BY created_at")
+        .fetch_all(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch webhooks: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    Ok(Json(json!({
+        "webhooks": webhooks
+    })))
+}
+
+/// The secret is only ever returned here, and from an update that sets a new one.
+pub async fn create_webhook(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Json(payload): Json<WebhookRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let (url, events) = validate_webhook(&payload)?;
+    let secret = payload
+        .secret
+        .clone()
+        .unwrap_or_else(|| hex::encode(rand::random::<[u8; 32]>()));
+
+    let webhook = sqlx::query_as::<_, Webhook>(
+        r#"
+        INSERT INTO webhooks (url, secret, events, description, enabled)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(&url)
+    .bind(&secret)
+    .bind(&events)
+    .bind(&payload.description)
+    .bind(payload.enabled.unwrap_or(true))
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create webhook: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "webhook": webhook,
+        "secret": secret
+    })))
+}
+
+pub async fn update_webhook(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Json(payload): Json<WebhookRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let (url, events) = validate_webhook(&payload)?;
+
+    let webhook = sqlx::query_as::<_, Webhook>(
+        r#"
+        UPDATE webhooks SET
+            url = $2,
+            secret = COALESCE($3, secret),
+            events = $4,
+            description = $5,
+            enabled = $6,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(&url)
+    .bind(&payload.secret)
+    .bind(&events)
+    .bind(&payload.description)
+    .bind(payload.enabled.unwrap_or(true))
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update webhook: {:?}", e);
+        ApiError::Internal
+    })?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(Json(json!({
+        "webhook": webhook,
+        "secret": payload.secret
+    })))
+}
+
+pub async fn delete_webhook(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    // Its deliveries go with it
+    let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+        .bind(id)
+        .execute(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete webhook: {:?}", e);
+            ApiError::Internal
+        })?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound);
+    }
+
+    Ok(Json(json!({
+        "message": "Webhook deleted successfully"
+    })))
+}
+
+/// The webhook's delivery log, newest first.
+pub async fn get_webhook_deliveries(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+    Query(params): Query<DeliveriesQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    fetch_webhook(&state, id).await?;
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_DELIVERIES_PAGE_SIZE)
+        .clamp(1, MAX_DELIVERIES_PAGE_SIZE);
+
+    let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        SELECT * FROM webhook_deliveries
+        WHERE webhook_id = $1 AND ($2::varchar IS NULL OR status = $2)
+        ORDER BY created_at DESC, id DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(id)
+    .bind(params.status)
+    .bind(limit)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch webhook deliveries: {:?}", e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "deliveries": deliveries
+    })))
+}
+
+/// Sends the webhook a `ping` right away. A failed ping is still a successful test: the
+/// delivery says what went wrong.
+pub async fn test_webhook(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Path(id): Path<i32>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Verify admin session
+    verify_admin_session(&state, &headers).await?;
+
+    let webhook = fetch_webhook(&state, id).await?;
+    let delivery = webhooks::test_fire(&state, &webhook).await.map_err(|e| {
+        tracing::error!("Failed to test webhook {}: {:?}", id, e);
+        ApiError::Internal
+    })?;
+
+    Ok(Json(json!({
+        "delivery": delivery
+    })))
+}
+
+async fn fetch_webhook(state: &SharedState, id: i32) -> Result<Webhook, ApiError> {
+    sqlx::query_as::<_, Webhook>("SELECT * FROM webhooks WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch webhook: {:?}", e);
+            ApiError::Internal
+        })?
+        .ok_or(ApiError::NotFound)
+}
+
+// The normalized URL and the distinct event names
+fn validate_webhook(payload: &WebhookRequest) -> Result<(String, Vec<String>), ApiError> {
+    let mut errors = FieldError::check(payload);
+    let url = match normalize_url(Some(&payload.url)) {
+        Ok(Some(url)) => Some(url),
+        Ok(None) => {
+            errors.push(FieldError { field: "url".to_string(), code: FieldErrorCode::Required });
+            None
+        }
+        Err(_) => {
+            errors.push(FieldError { field: "url".to_string(), code: FieldErrorCode::Invalid });
+            None
+        }
+    };
+    let Some(url) = url.filter(|_| errors.is_empty()) else {
+        return Err(ApiError::Validation(errors));
+    };
+
+    let mut events: Vec<String> = payload.events.iter().map(|e| e.as_str().to_string()).collect();
+    events.sort();
+    events.dedup();
+    Ok((url, events))
+}
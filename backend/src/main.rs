@@ -1,15 +1,24 @@
 use axum::{
-    routing::{get, post, delete},
+    extract::DefaultBodyLimit,
+    routing::{get, post, put, delete},
     Router,
 };
 use sqlx::postgres::PgPoolOptions;
 use std::net::SocketAddr;
 use tower_http::cors::{Any, CorsLayer};
 use http::header::{AUTHORIZATION, CONTENT_TYPE};
+use middleware::{
+    rate_limit::{
+        rate_limit, RouteLimit, INBOX_QUOTA, LOGIN_QUOTA, NEWSLETTER_QUOTA, REPORT_QUOTA, STREAM_QUOTA, SUBMISSION_QUOTA, UPLOAD_QUOTA,
+    },
+    request_context::{request_context, REQUEST_ID_HEADER},
+};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod db;
+mod error;
 mod handlers;
+mod middleware;
 mod models;
 mod services;
 
@@ -44,13 +53,58 @@ async fn main() {
     let redis_client = redis::Client::open(redis_url).expect("Failed to connect to Redis");
 
     // Create app state
+    let rate_limiter = services::rate_limit::RateLimiter::new(redis_client.clone());
+    let notifier = services::notices::Notifier::new(redis_client.clone());
     let app_state = std::sync::Arc::new(db::AppState {
         pool,
+        challenges: std::sync::Arc::new(services::challenge::challenge_config_from_env(&rate_limiter)),
+        rate_limiter,
         redis_client,
         admin_password: std::env::var("ADMIN_PASSWORD").expect("ADMIN_PASSWORD must be set"),
         google_api_key: std::env::var("GOOGLE_TRANSLATE_API_KEY").ok(),
+        geocoder: services::geocoding::geocoder_from_env(),
+        blob_store: services::storage::blob_store_from_env(),
+        public_url: std::env::var("PUBLIC_URL")
+            .ok()
+            .filter(|u| !u.is_empty())
+            .unwrap_or_else(|| "http://localhost:5173".to_string())
+            .trim_end_matches('/')
+            .to_string(),
+        trusted_proxies: middleware::client_ip::TrustedProxies::from_env(),
+        spam_scorer: std::sync::Arc::new(services::spam::spam_scorer_from_env()),
+        report_hide_threshold: std::env::var("REPORT_HIDE_THRESHOLD")
+            .ok()
+            .map(|t| t.parse().expect("Invalid REPORT_HIDE_THRESHOLD"))
+            .unwrap_or(3),
+        webhooks_allow_private: std::env::var("WEBHOOK_ALLOW_PRIVATE")
+            .is_ok_and(|v| v == "true" || v == "1"),
+        notifier,
+        localized_notices: services::notices::LocalizedChannels::default(),
+        actor_keys: services::activitypub::ActorKeys::default(),
+        remote_keys: services::activitypub::RemoteKeys::default(),
+        mailer: services::mail::mailer_from_env(),
     });
 
+    // Streams on this instance also hear about changes made on the others
+    tokio::spawn(app_state.notifier.clone().relay());
+
+    // Sends queued webhook deliveries and retries failed ones
+    tokio::spawn(services::webhooks::run(app_state.clone()));
+
+    // Announces published items to fediverse followers and sends queued activities
+    tokio::spawn(services::activitypub::run(app_state.clone()));
+
+    // Sends the weekly newsletter digest
+    tokio::spawn(services::newsletter::run(app_state.clone()));
+
+    // Publishes scheduled posts, archives expired posts and past events and clears lapsed pins
+    tokio::spawn(services::scheduler::run(app_state.clone()));
+
+    // Unauthenticated writes are limited per client address
+    let limited = |name, quota| {
+        axum::middleware::from_fn_with_state(RouteLimit::new(&app_state, name, quota), rate_limit)
+    };
+
     // Build router
     let app = Router::new()
         // Health check
@@ -59,29 +113,128 @@ async fn main() {
         // Language endpoints
         .route("/api/languages", get(handlers::get_supported_languages))
         
+        // Challenge for anonymous submissions (routes from CHALLENGE_ROUTES)
+        .route("/api/challenge", get(handlers::get_challenge))
+        
         // Post endpoints
         .route("/api/posts", get(handlers::get_posts))
-        .route("/api/posts", post(handlers::create_post))
-        .route("/api/posts/:id/publish", post(handlers::publish_post))
+        .route("/api/posts", post(handlers::create_post).layer(limited("posts", SUBMISSION_QUOTA)))
+        .route("/api/posts/:id", get(handlers::get_post))
+        .route("/api/posts/:id/report", post(handlers::report_post).layer(limited("reports", REPORT_QUOTA)))
+        
+        // Image endpoints
+        .route(
+            "/api/uploads/images",
+            post(handlers::upload_image)
+                // Room for the multipart framing around a maximum-size file
+                .layer(DefaultBodyLimit::max(services::images::MAX_UPLOAD_BYTES + 64 * 1024))
+                .layer(limited("uploads", UPLOAD_QUOTA)),
+        )
+        .route("/api/images/:key", get(handlers::get_image))
+        .route("/api/image-proxy/:id", get(handlers::proxy_image))
+        
+        // Search endpoint
+        .route("/api/search", get(handlers::search))
+        
+        // Category endpoints
+        .route("/api/categories", get(handlers::get_categories))
+        
+        // Venue endpoints
+        .route("/api/venues", get(handlers::get_venues))
         
         // Event endpoints
         .route("/api/events", get(handlers::get_events))
-        .route("/api/events", post(handlers::create_event))
-        .route("/api/events/:id/publish", post(handlers::publish_event))
+        .route("/api/events", post(handlers::create_event).layer(limited("events", SUBMISSION_QUOTA)))
+        .route("/api/events/:id", get(handlers::get_event))
+        .route("/api/events/:id/report", post(handlers::report_event).layer(limited("reports", REPORT_QUOTA)))
+        
+        // Live published/updated/deleted notices for posts and events
+        .route("/api/stream", get(handlers::content_stream).layer(limited("streams", STREAM_QUOTA)))
+        
+        // Emergency alerts
+        .route("/api/alerts/active", get(handlers::get_active_alerts))
+        .route("/api/alerts/stream", get(handlers::alert_stream).layer(limited("streams", STREAM_QUOTA)))
+        
+        // Share pages (Open Graph previews that redirect into the SPA)
+        .route("/share/:lang/posts/:id", get(handlers::share_post))
+        .route("/share/:lang/events/:id", get(handlers::share_event))
+        
+        // Email newsletter (double opt-in; the token in each email manages the subscription)
+        .route(
+            "/api/newsletter/subscribe",
+            post(handlers::subscribe_newsletter).layer(limited("newsletter", NEWSLETTER_QUOTA)),
+        )
+        .route("/api/newsletter/confirm", post(handlers::confirm_newsletter))
+        .route(
+            "/api/newsletter/subscription",
+            get(handlers::get_newsletter_subscription).put(handlers::update_newsletter_subscription),
+        )
+        .route("/api/newsletter/unsubscribe", post(handlers::unsubscribe_newsletter))
+        
+        // ActivityPub: one followable actor per language
+        .route("/.well-known/webfinger", get(handlers::webfinger))
+        .route("/ap/actors/:lang", get(handlers::get_actor))
+        .route("/ap/actors/:lang/inbox", post(handlers::actor_inbox).layer(limited("inbox", INBOX_QUOTA)))
+        .route("/ap/actors/:lang/outbox", get(handlers::actor_outbox))
+        .route("/ap/actors/:lang/followers", get(handlers::actor_followers))
+        .route("/ap/actors/:lang/posts/:id", get(handlers::get_actor_post))
+        .route("/ap/actors/:lang/events/:id", get(handlers::get_actor_event))
         
         // Admin endpoints
-        .route("/api/admin/login", post(handlers::admin_login))
+        .route("/api/admin/login", post(handlers::admin_login).layer(limited("login", LOGIN_QUOTA)))
         .route("/api/admin/logout", post(handlers::admin_logout))
         .route("/api/admin/posts", get(handlers::get_admin_posts))
         .route("/api/admin/events", get(handlers::get_admin_events))
         .route("/api/admin/posts/:id", delete(handlers::delete_post))
+        .route("/api/admin/posts/:id/publish", post(handlers::publish_post))
+        .route("/api/admin/posts/:id/pin", put(handlers::pin_post).delete(handlers::unpin_post))
         .route("/api/admin/events/:id", delete(handlers::delete_event))
-        
+        .route("/api/admin/events/:id/publish", post(handlers::publish_event))
+        .route(
+            "/api/admin/categories",
+            get(handlers::get_admin_categories).post(handlers::create_category),
+        )
+        .route(
+            "/api/admin/categories/:slug",
+            put(handlers::update_category).delete(handlers::delete_category),
+        )
+        .route("/api/admin/venues", post(handlers::create_venue))
+        .route(
+            "/api/admin/venues/:id",
+            put(handlers::update_venue).delete(handlers::delete_venue),
+        )
+        .route("/api/admin/venues/:id/geocode", post(handlers::geocode_venue))
+        .route("/api/admin/venues/:id/merge", post(handlers::merge_venue))
+        .route(
+            "/api/admin/events/:id/occurrences/:date",
+            put(handlers::upsert_occurrence_override).delete(handlers::delete_occurrence_override),
+        )
+        .route(
+            "/api/admin/spam/blocked-domains",
+            get(handlers::get_blocked_domains).post(handlers::create_blocked_domain),
+        )
+        .route("/api/admin/spam/blocked-domains/:domain", delete(handlers::delete_blocked_domain))
+        .route("/api/admin/reports", get(handlers::get_reports))
+        .route("/api/admin/alerts", get(handlers::get_admin_alerts).post(handlers::create_alert))
+        .route("/api/admin/alerts/:id", delete(handlers::end_alert))
+        .route("/api/admin/reports/posts/:id/resolve", post(handlers::resolve_post_reports))
+        .route("/api/admin/reports/events/:id/resolve", post(handlers::resolve_event_reports))
+        .route("/api/admin/webhooks", get(handlers::get_webhooks).post(handlers::create_webhook))
+        .route(
+            "/api/admin/webhooks/:id",
+            put(handlers::update_webhook).delete(handlers::delete_webhook),
+        )
+        .route("/api/admin/webhooks/:id/deliveries", get(handlers::get_webhook_deliveries))
+        .route("/api/admin/webhooks/:id/test", post(handlers::test_webhook))
+        .route("/api/admin/newsletter", get(handlers::get_newsletter_stats))
+        .route("/api/admin/newsletter/preview", get(handlers::preview_newsletter))
+        .layer(axum::middleware::from_fn(request_context))
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
                 .allow_methods(Any)
-                .allow_headers([AUTHORIZATION, CONTENT_TYPE]),
+                .allow_headers([AUTHORIZATION, CONTENT_TYPE, REQUEST_ID_HEADER])
+                .expose_headers([REQUEST_ID_HEADER]),
         )
         .with_state(app_state);
 
@@ -94,5 +247,7 @@ async fn main() {
     tracing::info!("listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
 }
\ No newline at end of file
@@ -0,0 +1,206 @@
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{request::Parts, Extensions, HeaderMap},
+};
+use std::{
+    convert::Infallible,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+};
+
+use crate::db::SharedState;
+
+/// Addresses allowed to tell us who the client is through `X-Forwarded-For`, such as
+/// the nginx in front of the API. Anyone else could put anything in that header.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Vec<(IpAddr, u8)>); // Network address and prefix length
+
+impl TrustedProxies {
+    /// Reads TRUSTED_PROXIES, a comma-separated list of addresses and CIDR ranges.
+    pub fn from_env() -> Self {
+        let value = std::env::var("TRUSTED_PROXIES").unwrap_or_default();
+        let networks: Vec<_> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| parse_network(v).unwrap_or_else(|| panic!("Invalid TRUSTED_PROXIES entry: {}", v)))
+            .collect();
+
+        if !networks.is_empty() {
+            tracing::info!("Trusting X-Forwarded-For from {}", value);
+        }
+        TrustedProxies(networks)
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = canonical(ip);
+        self.0.iter().any(|(network, prefix)| in_network(ip, *network, *prefix))
+    }
+}
+
+/// The address of the client that sent the request.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIp(pub IpAddr);
+
+impl ClientIp {
+    /// The connecting address, or, when that's a trusted proxy, the nearest address
+    /// in `X-Forwarded-For` that isn't one.
+    pub fn resolve(extensions: &Extensions, headers: &HeaderMap, trusted: &TrustedProxies) -> Self {
+        let Some(peer) = extensions.get::<ConnectInfo<SocketAddr>>().map(|c| canonical(c.0.ip())) else {
+            return ClientIp(IpAddr::from([0, 0, 0, 0]));
+        };
+        if !trusted.contains(peer) {
+            return ClientIp(peer);
+        }
+
+        // Each proxy appends the address it got the request from, so the chain is read
+        // from the right, through trusted hops, up to the first address that isn't one
+        let forwarded: Vec<Option<IpAddr>> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(|v| v.trim().parse::<IpAddr>().ok().map(canonical))
+            .collect();
+
+        let mut client = peer;
+        for hop in forwarded.iter().rev() {
+            let Some(ip) = hop else { break };
+            client = *ip;
+            if !trusted.contains(client) {
+                break;
+            }
+        }
+        ClientIp(client)
+    }
+
+    /// What limits are counted against. IPv6 users usually get a whole /64, so
+    /// addresses within one count as the same client.
+    pub fn bucket_key(&self) -> String {
+        match self.0 {
+            IpAddr::V4(ip) => ip.to_string(),
+            IpAddr::V6(ip) => {
+                let s = ip.segments();
+                format!("{}/64", Ipv6Addr::new(s[0], s[1], s[2], s[3], 0, 0, 0, 0))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<SharedState> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &SharedState) -> Result<Self, Self::Rejection> {
+        Ok(ClientIp::resolve(&parts.extensions, &parts.headers, &state.trusted_proxies))
+    }
+}
+
+impl std::fmt::Display for ClientIp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+fn parse_network(value: &str) -> Option<(IpAddr, u8)> {
+    let (address, prefix) = match value.split_once('/') {
+        Some((address, prefix)) => (address.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+        None => (value.parse::<IpAddr>().ok()?, None),
+    };
+    let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max_prefix);
+    (prefix <= max_prefix).then_some((address, prefix))
+}
+
+fn in_network(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+// Dual-stack sockets report IPv4 clients as ::ffff:a.b.c.d
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        v4 => v4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn trusted(entries: &[&str]) -> TrustedProxies {
+        TrustedProxies(entries.iter().map(|e| parse_network(e).unwrap()).collect())
+    }
+
+    fn resolve(peer: &str, forwarded: &[&str], proxies: &TrustedProxies) -> IpAddr {
+        let mut extensions = Extensions::new();
+        extensions.insert(ConnectInfo(SocketAddr::new(ip(peer), 443)));
+        let mut headers = HeaderMap::new();
+        for value in forwarded {
+            headers.append("x-forwarded-for", value.parse().unwrap());
+        }
+        ClientIp::resolve(&extensions, &headers, proxies).0
+    }
+
+    #[test]
+    fn in_network_matches_prefixes() {
+        assert!(in_network(ip("10.1.2.3"), ip("10.0.0.0"), 8));
+        assert!(!in_network(ip("11.1.2.3"), ip("10.0.0.0"), 8));
+        assert!(in_network(ip("192.168.1.7"), ip("192.168.1.7"), 32));
+        assert!(!in_network(ip("192.168.1.8"), ip("192.168.1.7"), 32));
+        assert!(in_network(ip("2001:db8::1"), ip("2001:db8::"), 32));
+        assert!(!in_network(ip("2001:db9::1"), ip("2001:db8::"), 32));
+    }
+
+    #[test]
+    fn in_network_zero_prefix_matches_everything_of_that_family() {
+        assert!(in_network(ip("203.0.113.9"), ip("0.0.0.0"), 0));
+        assert!(in_network(ip("2001:db8::1"), ip("::"), 0));
+        assert!(!in_network(ip("203.0.113.9"), ip("::"), 0));
+    }
+
+    #[test]
+    fn resolve_ignores_forwarded_for_from_untrusted_peers() {
+        let proxies = trusted(&["10.0.0.1"]);
+        assert_eq!(resolve("203.0.113.9", &["198.51.100.1"], &proxies), ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn resolve_reads_forwarded_for_through_trusted_hops() {
+        let proxies = trusted(&["10.0.0.0/8"]);
+        // The client-supplied left end is never reached past an untrusted hop
+        assert_eq!(
+            resolve("10.0.0.1", &["192.0.2.66, 198.51.100.1, 10.0.0.2"], &proxies),
+            ip("198.51.100.1")
+        );
+        // Headers repeated by several proxies read as one chain
+        assert_eq!(resolve("10.0.0.1", &["198.51.100.1", "10.0.0.2"], &proxies), ip("198.51.100.1"));
+    }
+
+    #[test]
+    fn resolve_stops_at_unparseable_hops() {
+        let proxies = trusted(&["10.0.0.0/8"]);
+        assert_eq!(resolve("10.0.0.1", &["198.51.100.1, junk"], &proxies), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn resolve_canonicalizes_ipv4_mapped_peers() {
+        let proxies = trusted(&["10.0.0.1"]);
+        assert_eq!(resolve("::ffff:10.0.0.1", &["198.51.100.1"], &proxies), ip("198.51.100.1"));
+        assert_eq!(resolve("::ffff:203.0.113.9", &[], &proxies), ip("203.0.113.9"));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod client_ip;
+pub mod rate_limit;
+pub mod request_context;
@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use super::client_ip::ClientIp;
+use crate::{
+    db::SharedState,
+    error::ApiError,
+    services::rate_limit::{Decision, Quota},
+};
+
+// Per client address. Generous enough for a household behind one address, tight
+// enough that a script can't bury the moderation queue.
+pub const SUBMISSION_QUOTA: Quota = Quota::per_minutes(5, 10);
+pub const UPLOAD_QUOTA: Quota = Quota::per_minutes(20, 10);
+pub const LOGIN_QUOTA: Quota = Quota::per_minutes(10, 1);
+pub const REPORT_QUOTA: Quota = Quota::per_minutes(10, 60);
+// Each accepted subscription can send a confirmation email to someone else's address
+pub const NEWSLETTER_QUOTA: Quota = Quota::per_minutes(5, 60);
+// Each open page holds two streams and reconnects them whenever the connection drops
+pub const STREAM_QUOTA: Quota = Quota::per_minutes(30, 10);
+// Fediverse servers deliver in bursts, and each request may have us fetch its sender's key
+pub const INBOX_QUOTA: Quota = Quota::per_minutes(60, 1);
+
+/// The limit for one route, applied with
+/// `axum::middleware::from_fn_with_state(RouteLimit::new(...), rate_limit)`.
+#[derive(Clone)]
+pub struct RouteLimit {
+    state: SharedState,
+    name: &'static str, // Routes sharing a name share buckets
+    quota: Quota,
+}
+
+impl RouteLimit {
+    pub fn new(state: &SharedState, name: &'static str, quota: Quota) -> Self {
+        RouteLimit { state: state.clone(), name, quota }
+    }
+}
+
+/// Token-bucket limiting per route and client address; over-limit requests get 429
+/// with `Retry-After`.
+pub async fn rate_limit(State(limit): State<RouteLimit>, request: Request, next: Next) -> Response {
+    let client = ClientIp::resolve(request.extensions(), request.headers(), &limit.state.trusted_proxies);
+    let key = format!("ratelimit:{}:{}", limit.name, client.bucket_key());
+
+    match limit.state.rate_limiter.check(&key, limit.quota).await {
+        Decision::Allowed => next.run(request).await,
+        Decision::Limited { retry_after_seconds } => {
+            tracing::info!("Rate limited {} on {}", client, limit.name);
+            ApiError::RateLimited(retry_after_seconds).into_response()
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use axum::{
+    body::to_bytes,
+    extract::Request,
+    http::{header, HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::Instrument;
+
+use crate::error::{ApiError, FieldErrorCode};
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+// Extractor rejections are short plain-text messages
+const MAX_REJECTION_BYTES: usize = 16 * 1024;
+
+lazy_static::lazy_static! {
+    // Ids passed in by a proxy are kept if they look like ids
+    static ref REQUEST_ID_REGEX: regex::Regex = regex::Regex::new(r"^[A-Za-z0-9._-]{1,64}$").unwrap();
+    static ref MISSING_FIELD_REGEX: regex::Regex = regex::Regex::new(r"missing field `([^`]+)`").unwrap();
+    static ref REJECTED_FIELD_REGEX: regex::Regex =
+        regex::Regex::new(r"into the target type: ([A-Za-z0-9_.\[\]]+): ").unwrap();
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub request_id: String,
+    pub language: String, // From ?lang= or Accept-Language; used for error messages
+}
+
+tokio::task_local! {
+    static CONTEXT: RequestContext;
+}
+
+/// The context of the request being handled, if called from within one.
+pub fn current() -> Option<RequestContext> {
+    CONTEXT.try_with(|c| c.clone()).ok()
+}
+
+/// Gives every request an id (echoed in `X-Request-Id` and attached to its log lines)
+/// and a language, and turns error responses that didn't come from `ApiError`, such
+/// as extractor rejections, into the same structured shape.
+pub async fn request_context(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| REQUEST_ID_REGEX.is_match(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let context = RequestContext {
+        request_id: request_id.clone(),
+        language: request_language(&request),
+    };
+    let span = tracing::info_span!("request", request_id = %request_id);
+
+    let mut response = CONTEXT
+        .scope(context, async move { structure_error(next.run(request).await).await })
+        .instrument(span)
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+fn request_language(request: &Request) -> String {
+    // Language codes need no percent-decoding
+    let from_query = request
+        .uri()
+        .query()
+        .and_then(|query| query.split('&').find_map(|pair| pair.strip_prefix("lang=")))
+        .map(str::to_string);
+
+    let from_header = || {
+        request
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .map(|tag| tag.split(';').next().unwrap_or("").trim().to_string())
+    };
+
+    from_query
+        .or_else(from_header)
+        .and_then(|tag| tag.split(['-', '_']).next().map(str::to_lowercase))
+        .filter(|lang| !lang.is_empty())
+        .unwrap_or_else(|| "en".to_string())
+}
+
+async fn structure_error(response: Response) -> Response {
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !(status.is_client_error() || status.is_server_error()) || is_json {
+        return response;
+    }
+
+    let body = to_bytes(response.into_body(), MAX_REJECTION_BYTES).await.unwrap_or_default();
+    let rejection = String::from_utf8_lossy(&body);
+
+    // Body deserialization failures name the offending field; keep that for the client
+    let error = if let Some(field) = MISSING_FIELD_REGEX.captures(&rejection) {
+        ApiError::field(&field[1], FieldErrorCode::Required)
+    } else if let Some(field) = REJECTED_FIELD_REGEX.captures(&rejection) {
+        ApiError::field(&field[1], FieldErrorCode::Invalid)
+    } else {
+        if !rejection.is_empty() {
+            tracing::debug!("Rejected request ({}): {}", status, rejection);
+        }
+        ApiError::from_status(status)
+    };
+
+    error.into_response()
+}
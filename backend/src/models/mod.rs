@@ -1,6 +1,10 @@
 use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{types::Json, FromRow};
+use std::collections::HashMap;
+use validator::Validate;
+
+use crate::services::validation::{not_blank, trimmed_option, valid_text_direction};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Language {
@@ -15,31 +19,86 @@ pub struct Language {
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Post {
     pub id: i32,
+    pub slug: Option<String>,
     pub author_name: String,
     pub author_email: Option<String>,
     pub title: String,
     pub content: Option<String>,
     pub link_url: Option<String>,
     pub image_url: Option<String>,
-    pub post_type: String,
+    pub image_id: Option<String>,
+    pub post_type: PostType,
     pub original_language: String,
     pub text_direction: String,
     pub published: bool,
+    pub publish_at: Option<DateTime<Utc>>, // Approved, but not shown before this
+    pub expires_at: Option<DateTime<Utc>>,
+    pub archived_at: Option<DateTime<Utc>>, // Set once expired
+    pub pinned_at: Option<DateTime<Utc>>,
+    pub pinned_until: Option<DateTime<Utc>>,
+    pub pin_priority: i32,
+    pub emergency: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Stored as text; the posts_post_type_check constraint holds the same list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum PostType {
+    Text,
+    Link,
+    Announcement,
+    Article,
+}
+
+impl PostType {
+    pub const ALL: [PostType; 4] = [PostType::Text, PostType::Link, PostType::Announcement, PostType::Article];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PostType::Text => "text",
+            PostType::Link => "link",
+            PostType::Announcement => "announcement",
+            PostType::Article => "article",
+        }
+    }
+
+    /// A link post is the link; every other type is read for its text.
+    pub fn requires_link(self) -> bool {
+        self == PostType::Link
+    }
+
+    pub fn requires_content(self) -> bool {
+        self != PostType::Link
+    }
+}
+
+// Lengths follow the column sizes; language is checked against supported_languages
+// by the handler, as that takes a query
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct CreatePostRequest {
+    #[validate(custom(function = "not_blank"), length(max = 255, code = "too_long"))]
     pub author_name: String,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(email(code = "invalid"), length(max = 255, code = "too_long"))]
     pub author_email: Option<String>,
+    #[validate(custom(function = "not_blank"), length(max = 255, code = "too_long"))]
     pub title: String,
     pub content: Option<String>,
     pub link_url: Option<String>,
     pub image_url: Option<String>,
-    pub post_type: String,
+    pub image_id: Option<String>, // From POST /api/uploads/images
+    pub post_type: PostType,
+    #[serde(default, deserialize_with = "trimmed_option")]
     pub language: Option<String>,
-    pub text_direction: Option<String>,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(custom(function = "valid_text_direction"))]
+    pub text_direction: Option<String>, // ltr, rtl or auto
+    #[serde(default)]
+    pub website: Option<String>, // Honeypot: hidden in the form, so only bots fill it in
+    pub challenge_response: Option<String>, // Solved challenge, when the route requires one
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -56,6 +115,7 @@ pub struct PostTranslation {
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct PostWithTranslation {
     pub id: i32,
+    pub slug: Option<String>,
     pub author_name: String,
     pub title: String,
     pub content: Option<String>,
@@ -63,17 +123,33 @@ pub struct PostWithTranslation {
     pub original_content: Option<String>,
     pub link_url: Option<String>,
     pub image_url: Option<String>,
-    pub post_type: String,
+    pub thumbnail_url: Option<String>,
+    pub link_preview: Option<Json<LinkPreview>>,
+    pub post_type: PostType,
     pub original_language: String,
     pub original_text_direction: String,
     pub text_direction: String,
     pub is_translated: bool,
+    pub pinned: bool,
+    pub emergency: bool,
     pub created_at: DateTime<Utc>,
 }
 
+// Unfurled link_url, localized like the post it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkPreview {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub site_name: Option<String>,
+    pub is_translated: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Event {
     pub id: i32,
+    pub slug: Option<String>,
     pub organizer_name: String,
     pub organizer_email: Option<String>,
     pub title: String,
@@ -87,23 +163,45 @@ pub struct Event {
     pub original_language: String,
     pub text_direction: String,
     pub published: bool,
+    pub recurrence_rule: Option<String>,
+    pub recurrence_exdates: Vec<NaiveDate>,
+    pub venue_id: Option<i32>,
+    pub image_id: Option<String>,
+    pub archived_at: Option<DateTime<Utc>>, // Set once the event (or its series) is over
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct CreateEventRequest {
+    #[validate(custom(function = "not_blank"), length(max = 255, code = "too_long"))]
     pub organizer_name: String,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(email(code = "invalid"), length(max = 255, code = "too_long"))]
     pub organizer_email: Option<String>,
+    #[validate(custom(function = "not_blank"), length(max = 255, code = "too_long"))]
     pub title: String,
     pub description: Option<String>,
     pub event_date: String,
-    pub event_time: Option<String>,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    pub event_time: Option<String>, // Format: HH:MM or HH:MM:SS
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(length(max = 255, code = "too_long"))]
     pub location: Option<String>,
     pub category: Option<String>,
     pub is_free: bool,
     pub ticket_url: Option<String>,
+    #[serde(default, deserialize_with = "trimmed_option")]
     pub language: Option<String>,
-    pub text_direction: Option<String>,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(custom(function = "valid_text_direction"))]
+    pub text_direction: Option<String>, // ltr, rtl or auto
+    #[serde(default)]
+    pub website: Option<String>, // Honeypot, as for posts
+    pub challenge_response: Option<String>,
+    pub recurrence_rule: Option<String>, // RRULE subset, e.g. FREQ=WEEKLY;BYDAY=SA
+    pub recurrence_exdates: Option<Vec<String>>, // Format: YYYY-MM-DD
+    pub venue_id: Option<i32>,
+    pub image_id: Option<String>, // From POST /api/uploads/images
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -120,6 +218,7 @@ pub struct EventTranslation {
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct EventWithTranslation {
     pub id: i32,
+    pub slug: Option<String>,
     pub organizer_name: String,
     pub title: String,
     pub description: Option<String>,
@@ -128,14 +227,470 @@ pub struct EventWithTranslation {
     pub event_date: NaiveDate,
     pub event_time: Option<NaiveTime>,
     pub location: Option<String>,
+    pub venue_id: Option<i32>,
+    pub venue_name: Option<String>,
+    pub venue_address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub distance_km: Option<f64>,
     pub category: Option<String>,
+    pub category_name: Option<String>,
+    pub category_color: Option<String>,
     pub is_free: bool,
     pub ticket_url: Option<String>,
+    pub image_url: Option<String>,
+    pub thumbnail_url: Option<String>,
     pub original_language: String,
     pub original_text_direction: String,
     pub text_direction: String,
     pub is_translated: bool,
+    pub recurrence_rule: Option<String>,
+    #[serde(skip)]
+    pub recurrence_exdates: Vec<NaiveDate>,
+    #[sqlx(default)]
+    pub is_cancelled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EventOccurrenceOverride {
+    pub id: i32,
+    pub event_id: i32,
+    pub occurrence_date: NaiveDate,
+    pub cancelled: bool,
+    pub event_time: Option<NaiveTime>,
+    pub location: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OccurrenceOverrideRequest {
+    #[serde(default)]
+    pub cancelled: bool,
+    pub event_time: Option<String>,
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SearchResult {
+    pub result_type: String,
+    pub id: i32,
+    pub slug: Option<String>,
+    pub title: String,
+    pub snippet: String,
+    #[serde(skip)]
+    pub body: Option<String>,
+    pub text_direction: String,
+    pub is_translated: bool,
+    pub event_date: Option<NaiveDate>,
+    pub rank: f32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Image {
+    pub id: String,
+    pub display_key: String,
+    pub thumbnail_key: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RemoteImage {
+    pub id: String, // sha256 of the URL
+    pub url: String,
+    pub image_key: Option<String>,
+    pub fetched_at: Option<DateTime<Utc>>,
+    pub failed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Venue {
+    pub id: i32,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub accessibility_info: Option<String>,
+    pub wheelchair_accessible: Option<bool>,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueRequest {
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub accessibility_info: Option<String>,
+    pub wheelchair_accessible: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeVenueRequest {
+    pub into_venue_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Category {
+    pub slug: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CategoryTranslation {
+    pub id: i32,
+    pub category_slug: String,
+    pub language_code: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CategoryWithTranslation {
+    pub slug: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: i32,
+    pub is_translated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCategoryRequest {
+    pub slug: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: Option<i32>,
+    pub names: HashMap<String, String>, // language code -> display name
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCategoryRequest {
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: Option<i32>,
+    pub names: Option<HashMap<String, String>>,
+}
+
+// One check's contribution to a submission's spam score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpamReason {
+    pub check: String,
+    pub score: i32,
+    pub detail: String,
+}
+
+// What moderators see on top of the submission itself
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SpamAssessmentInfo {
+    pub spam_score: i32,
+    pub spam_reasons: Json<Vec<SpamReason>>,
+    pub quarantined: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AdminPost {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub post: Post,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub spam: SpamAssessmentInfo,
+    pub hidden: bool, // Held back by reports pending review
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AdminEvent {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub event: Event,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub spam: SpamAssessmentInfo,
+    pub hidden: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BlockedDomain {
+    pub domain: String,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedDomainRequest {
+    pub domain: String,
+    pub note: Option<String>,
+}
+
+// Stored as text; the reports_reason_check constraint holds the same list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ReportReason {
+    Misinformation,
+    HateSpeech,
+    Harassment,
+    Spam,
+    Outdated,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ReportRequest {
+    pub reason: ReportReason,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(length(max = 1000, code = "too_long"))]
+    pub comment: Option<String>,
+}
+
+// The pending reports about one post or event, taken together
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReportedItem {
+    pub item_type: String, // post or event
+    pub item_id: i32,
+    pub title: String,
+    pub slug: Option<String>,
+    pub hidden: bool,
+    pub report_count: i64,
+    pub reporter_count: i64, // Distinct reporting addresses
+    pub reasons: Json<HashMap<String, i64>>, // Reports per reason
+    pub comments: Vec<String>,
+    pub first_reported_at: DateTime<Utc>,
+    pub last_reported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportResolution {
+    Dismiss, // The item is fine; it's shown again
+    Uphold,  // The reports are right; it stays hidden
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveReportsRequest {
+    pub action: ReportResolution,
+}
+
+// Publishing without a body publishes right away and never expires
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PublishPostRequest {
+    pub publish_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct PinPostRequest {
+    #[serde(default)]
+    #[validate(range(min = -100, max = 100))]
+    pub priority: i32,
+    pub pinned_until: Option<DateTime<Utc>>, // Pinned until unpinned if absent
+    #[serde(default)]
+    pub emergency: bool,
+}
+
+// Stored as text; the alerts_severity_check constraint holds the same list, mildest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Minor,
+    Moderate,
+    Severe,
+    Extreme,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Alert {
+    pub id: i32,
+    pub title: String,
+    pub body: String,
+    pub severity: AlertSeverity,
+    pub original_language: String,
+    pub text_direction: String,
+    pub expires_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>, // Set when a moderator ends it early
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateAlertRequest {
+    #[validate(custom(function = "not_blank"), length(max = 255, code = "too_long"))]
+    pub title: String,
+    #[validate(custom(function = "not_blank"), length(max = 5000, code = "too_long"))]
+    pub body: String,
+    pub severity: AlertSeverity,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    pub language: Option<String>,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(custom(function = "valid_text_direction"))]
+    pub text_direction: Option<String>, // ltr, rtl or auto
+}
+
+// An active alert in the reader's language; every alert is translated before it goes live
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LocalizedAlert {
+    pub id: i32,
+    pub title: String,
+    pub body: String,
+    pub severity: AlertSeverity,
+    pub original_language: String,
+    pub text_direction: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+// What a webhook can subscribe to; stored by name in webhooks.events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    #[serde(rename = "post.submitted")]
+    PostSubmitted,
+    #[serde(rename = "post.published")]
+    PostPublished,
+    #[serde(rename = "post.translated")]
+    PostTranslated,
+    #[serde(rename = "post.deleted")]
+    PostDeleted,
+    #[serde(rename = "event.submitted")]
+    EventSubmitted,
+    #[serde(rename = "event.published")]
+    EventPublished,
+    #[serde(rename = "event.translated")]
+    EventTranslated,
+    #[serde(rename = "event.deleted")]
+    EventDeleted,
+}
+
+impl WebhookEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEvent::PostSubmitted => "post.submitted",
+            WebhookEvent::PostPublished => "post.published",
+            WebhookEvent::PostTranslated => "post.translated",
+            WebhookEvent::PostDeleted => "post.deleted",
+            WebhookEvent::EventSubmitted => "event.submitted",
+            WebhookEvent::EventPublished => "event.published",
+            WebhookEvent::EventTranslated => "event.translated",
+            WebhookEvent::EventDeleted => "event.deleted",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Webhook {
+    pub id: i32,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String, // Only ever shown when it's set
+    pub events: Vec<String>,
+    pub description: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Creates a webhook, or replaces one's settings; a missing secret is generated on
+// create and left alone on update
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct WebhookRequest {
+    pub url: String,
+    #[validate(length(min = 1, code = "required"))]
+    pub events: Vec<WebhookEvent>,
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(length(max = 255, code = "too_long"))]
+    pub description: Option<String>,
+    pub enabled: Option<bool>, // Defaults to true
+    #[serde(default, deserialize_with = "trimmed_option")]
+    #[validate(length(min = 16, max = 128, code = "out_of_range"))]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    Pending, // Not attempted yet, or waiting to be retried
+    Delivered,
+    Failed, // Out of attempts
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event: String,
+    pub payload: Json<serde_json::Value>,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub response_status: Option<i32>, // Of the latest attempt
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NewsletterTopic {
+    Posts,  // The week's published posts
+    Events, // Events in the coming week
+}
+
+impl NewsletterTopic {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NewsletterTopic::Posts => "posts",
+            NewsletterTopic::Events => "events",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NewsletterSubscriber {
+    pub id: i32,
+    pub email: String,
+    pub language_code: String,
+    pub topics: Vec<String>,
+    pub categories: Vec<String>, // Empty means every category
+    #[serde(skip_serializing)]
+    pub token: String, // Only ever sent to the address itself
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub confirmation_sent_at: DateTime<Utc>,
+    pub last_digest_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Language and categories are checked by the handler, as that takes a query
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct NewsletterSubscribeRequest {
+    #[validate(email(code = "invalid"), length(max = 255, code = "too_long"))]
+    pub email: String,
+    pub language: Option<String>, // Defaults to en
+    #[validate(length(min = 1, code = "required"))]
+    pub topics: Vec<NewsletterTopic>,
+    #[serde(default)]
+    pub categories: Vec<String>, // Event category slugs; empty for all
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct NewsletterPreferencesRequest {
+    pub language: String,
+    #[validate(length(min = 1, code = "required"))]
+    pub topics: Vec<NewsletterTopic>,
+    #[serde(default)]
+    pub categories: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
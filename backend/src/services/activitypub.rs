@@ -0,0 +1,702 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use anyhow::{bail, Context, Result};
+use axum::http::HeaderMap;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{header, Url};
+use rsa::{
+    pkcs1::DecodeRsaPublicKey,
+    pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, LineEnding},
+    Pkcs1v15Sign, RsaPrivateKey, RsaPublicKey,
+};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::{types::Json, FromRow, PgPool};
+use tokio::{
+    sync::OnceCell,
+    time::{interval, MissedTickBehavior},
+};
+
+use crate::{
+    db::SharedState,
+    error::ApiError,
+    handlers::{fetch_published_event, fetch_published_post},
+    models::{EventWithTranslation, PostWithTranslation},
+    services::{delivery, html::escape_html, notices::ContentKind, remote, translation::content_language},
+};
+
+pub const ACTIVITY_JSON: &str = "application/activity+json";
+pub const ACTIVITYSTREAMS_CONTEXT: &str = "https://www.w3.org/ns/activitystreams";
+pub const SECURITY_CONTEXT: &str = "https://w3id.org/security/v1";
+const PUBLIC_COLLECTION: &str = "https://www.w3.org/ns/activitystreams#Public";
+// Servers differ on which of these they send
+const ACCEPT_ACTIVITY: &str =
+    r#"application/activity+json, application/ld+json; profile="https://www.w3.org/ns/activitystreams""#;
+
+const KEY_BITS: usize = 2048;
+const HTTP_DATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+// Signed requests older than this, or dated further ahead than the skew, are refused
+const MAX_SIGNATURE_AGE_HOURS: i64 = 12;
+const MAX_CLOCK_SKEW_HOURS: i64 = 1;
+const MAX_ACTOR_BYTES: usize = 256 * 1024;
+// Senders' keys are trusted this long before being fetched again
+const REMOTE_KEY_TTL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+// Past this many cached keys, expired ones are swept on the next insert
+const MAX_REMOTE_KEYS: usize = 10_000;
+
+// How long an item waits for its translation before going out in its original language
+const TRANSLATION_GRACE_SECS: f64 = 600.0;
+// Ready announcements turned into deliveries per transaction
+const ANNOUNCE_BATCH_SIZE: i64 = 20;
+
+pub fn actor_url(public_url: &str, lang: &str) -> String {
+    format!("{}/ap/actors/{}", public_url, lang)
+}
+
+pub fn key_id(public_url: &str, lang: &str) -> String {
+    format!("{}#main-key", actor_url(public_url, lang))
+}
+
+pub fn object_url(public_url: &str, lang: &str, kind: ContentKind, id: i32) -> String {
+    format!("{}/{}/{}", actor_url(public_url, lang), kind_path(kind), id)
+}
+
+// The share page: per-item link previews for the fediverse, and a way into the app
+pub fn page_url(public_url: &str, lang: &str, kind: ContentKind, slug: Option<&str>, id: i32) -> String {
+    let key = slug.map(str::to_string).unwrap_or_else(|| id.to_string());
+    format!("{}/share/{}/{}/{}", public_url, lang, kind_path(kind), key)
+}
+
+fn kind_path(kind: ContentKind) -> &'static str {
+    match kind {
+        ContentKind::Post => "posts",
+        ContentKind::Event => "events",
+    }
+}
+
+/// The key pair every actor signs with. It is generated and stored the first time any
+/// instance needs it, then kept in memory.
+#[derive(Clone, Default)]
+pub struct ActorKeys(Arc<OnceCell<KeyPair>>);
+
+pub struct KeyPair {
+    private_key: RsaPrivateKey,
+    pub public_key_pem: String,
+}
+
+impl ActorKeys {
+    pub async fn get(&self, pool: &PgPool) -> Result<&KeyPair> {
+        self.0.get_or_try_init(|| load_or_create_keys(pool)).await
+    }
+}
+
+/// Public keys of remote actors that signed inbox requests, so a server delivering a
+/// stream of activities isn't fetched from for each one.
+#[derive(Clone, Default)]
+pub struct RemoteKeys(Arc<Mutex<HashMap<String, CachedKey>>>); // By key id
+
+struct CachedKey {
+    actor: RemoteActor,
+    key: RsaPublicKey,
+    fetched_at: Instant,
+}
+
+impl RemoteKeys {
+    fn get(&self, key_id: &str) -> Option<(RemoteActor, RsaPublicKey)> {
+        let keys = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        keys.get(key_id)
+            .filter(|cached| cached.fetched_at.elapsed() < REMOTE_KEY_TTL)
+            .map(|cached| (cached.actor.clone(), cached.key.clone()))
+    }
+
+    fn insert(&self, key_id: &str, actor: RemoteActor, key: RsaPublicKey) {
+        let mut keys = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if keys.len() >= MAX_REMOTE_KEYS {
+            keys.retain(|_, cached| cached.fetched_at.elapsed() < REMOTE_KEY_TTL);
+        }
+        keys.insert(key_id.to_string(), CachedKey { actor, key, fetched_at: Instant::now() });
+    }
+}
+
+async fn load_or_create_keys(pool: &PgPool) -> Result<KeyPair> {
+    let select = "SELECT private_key_pem, public_key_pem FROM activitypub_keys";
+    let stored: Option<(String, String)> = sqlx::query_as(select).fetch_optional(pool).await?;
+
+    let (private_pem, public_pem) = match stored {
+        Some(pems) => pems,
+        None => {
+            let (private_pem, public_pem) = tokio::task::spawn_blocking(generate_key_pems).await??;
+            // Another instance may have got there first; theirs wins
+            sqlx::query(
+                "INSERT INTO activitypub_keys (private_key_pem, public_key_pem) VALUES ($1, $2) ON CONFLICT (id) DO NOTHING",
+            )
+            .bind(&private_pem)
+            .bind(&public_pem)
+            .execute(pool)
+            .await?;
+            tracing::info!("Generated the ActivityPub signing key");
+            sqlx::query_as(select).fetch_one(pool).await?
+        }
+    };
+
+    Ok(KeyPair {
+        private_key: RsaPrivateKey::from_pkcs8_pem(&private_pem).context("Stored ActivityPub private key")?,
+        public_key_pem: public_pem,
+    })
+}
+
+fn generate_key_pems() -> Result<(String, String)> {
+    let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), KEY_BITS)?;
+    let private_pem = private_key.to_pkcs8_pem(LineEnding::LF)?.to_string();
+    let public_pem = private_key.to_public_key().to_public_key_pem(LineEnding::LF)?;
+    Ok((private_pem, public_pem))
+}
+
+/// A post as a Note, in whichever language it was fetched in.
+pub fn note(public_url: &str, lang: &str, post: &PostWithTranslation) -> serde_json::Value {
+    let actor = actor_url(public_url, lang);
+    let page = page_url(public_url, lang, ContentKind::Post, post.slug.as_deref(), post.id);
+
+    let mut content = format!("<p><strong>{}</strong></p>", escape_html(&post.title));
+    if let Some(text) = &post.content {
+        content.push_str(&paragraphs(text));
+    }
+    if let Some(link) = &post.link_url {
+        content.push_str(&link_paragraph(link));
+    }
+    content.push_str(&link_paragraph(&page));
+
+    let mut object = json!({
+        "id": object_url(public_url, lang, ContentKind::Post, post.id),
+        "type": "Note",
+        "attributedTo": actor,
+        "content": content,
+        "contentMap": { content_language(lang, &post.original_language, post.is_translated): content },
+        "url": page,
+        "published": post.created_at,
+        "to": [PUBLIC_COLLECTION],
+        "cc": [format!("{}/followers", actor)],
+    });
+    if let Some(image) = image_attachment(public_url, post.image_url.as_deref(), &post.title) {
+        object["attachment"] = json!([image]);
+    }
+    object
+}
+
+/// An event as an Event, in whichever language it was fetched in. Times are local
+/// wall-clock times, as they are everywhere else.
+pub fn event(public_url: &str, lang: &str, event: &EventWithTranslation) -> serde_json::Value {
+    let actor = actor_url(public_url, lang);
+    let page = page_url(public_url, lang, ContentKind::Event, event.slug.as_deref(), event.id);
+
+    let mut content = event.description.as_deref().map(paragraphs).unwrap_or_default();
+    content.push_str(&link_paragraph(&page));
+
+    let start = event.event_date.and_time(event.event_time.unwrap_or_default());
+    let mut object = json!({
+        "id": object_url(public_url, lang, ContentKind::Event, event.id),
+        "type": "Event",
+        "attributedTo": actor,
+        "name": event.title,
+        "content": content,
+        "contentMap": { content_language(lang, &event.original_language, event.is_translated): content },
+        "startTime": start.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        "url": page,
+        "published": event.created_at,
+        "to": [PUBLIC_COLLECTION],
+        "cc": [format!("{}/followers", actor)],
+    });
+    if let Some(location) = &event.location {
+        let mut place = json!({ "type": "Place", "name": location });
+        if let Some(address) = &event.venue_address {
+            place["address"] = json!(address);
+        }
+        if let (Some(latitude), Some(longitude)) = (event.latitude, event.longitude) {
+            place["latitude"] = json!(latitude);
+            place["longitude"] = json!(longitude);
+        }
+        object["location"] = place;
+    }
+    if let Some(image) = image_attachment(public_url, event.image_url.as_deref(), &event.title) {
+        object["attachment"] = json!([image]);
+    }
+    object
+}
+
+/// Wraps an object built by [`note`] or [`event`] in the activity that announces it.
+pub fn create_activity(object: serde_json::Value) -> serde_json::Value {
+    let id = object["id"].as_str().unwrap_or_default();
+    json!({
+        "@context": ACTIVITYSTREAMS_CONTEXT,
+        "id": format!("{}/activity", id),
+        "type": "Create",
+        "actor": object["attributedTo"],
+        "published": object["published"],
+        "to": object["to"],
+        "cc": object["cc"],
+        "object": object,
+    })
+}
+
+/// The item as its ActivityPub object in `lang`, if it's publicly visible.
+pub async fn localized_object(
+    state: &SharedState,
+    lang: &str,
+    kind: ContentKind,
+    id: i32,
+) -> Result<Option<serde_json::Value>, ApiError> {
+    let key = id.to_string();
+    Ok(match kind {
+        ContentKind::Post => fetch_published_post(state, lang, &key).await?.map(|post| note(&state.public_url, lang, &post)),
+        ContentKind::Event => {
+            fetch_published_event(state, lang, &key).await?.map(|item| event(&state.public_url, lang, &item))
+        }
+    })
+}
+
+// Plain text as HTML paragraphs, keeping single line breaks
+fn paragraphs(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| format!("<p>{}</p>", escape_html(p).replace('\n', "<br>")))
+        .collect()
+}
+
+fn link_paragraph(url: &str) -> String {
+    format!(r#"<p><a href="{0}">{0}</a></p>"#, escape_html(url))
+}
+
+// Only our own stored images have a known type; proxied remote images are left out
+fn image_attachment(public_url: &str, image_url: Option<&str>, name: &str) -> Option<serde_json::Value> {
+    let path = image_url.filter(|url| url.starts_with("/api/images/"))?;
+    let media_type = if path.ends_with(".png") { "image/png" } else { "image/jpeg" };
+    Some(json!({
+        "type": "Image",
+        "mediaType": media_type,
+        "url": format!("{}{}", public_url, path),
+        "name": name,
+    }))
+}
+
+/// Queues the item to be announced to each language's followers, once it's ready in
+/// that language. Failures are logged; federation never holds up publishing.
+pub async fn enqueue(pool: &PgPool, kind: ContentKind, id: i32) {
+    let queued = sqlx::query(
+        r#"
+        INSERT INTO activitypub_announcements (item_kind, item_id, language_code)
+        SELECT DISTINCT $1, $2, language_code FROM activitypub_followers
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(kind)
+    .bind(id)
+    .execute(pool)
+    .await;
+
+    if let Err(e) = queued {
+        tracing::error!("Failed to queue ActivityPub announcement of {:?} {}: {:?}", kind, id, e);
+    }
+}
+
+/// Queues an activity for one inbox, sent on behalf of the `lang` actor.
+pub async fn enqueue_delivery(pool: &PgPool, lang: &str, inbox_url: &str, activity: &serde_json::Value) -> Result<()> {
+    sqlx::query("INSERT INTO activitypub_deliveries (language_code, inbox_url, activity) VALUES ($1, $2, $3)")
+        .bind(lang)
+        .bind(inbox_url)
+        .bind(Json(activity))
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Announces items as they become ready and sends queued activities, retrying failures
+/// with backoff. Runs for the life of the server; work is claimed, so any number of
+/// instances can run it.
+pub async fn run(state: SharedState) {
+    let mut ticker = interval(delivery::POLL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        if let Err(e) = announce_ready(&state).await {
+            tracing::error!("ActivityPub announcement run failed: {:?}", e);
+        }
+        if let Err(e) = deliver_due(&state).await {
+            tracing::error!("ActivityPub delivery run failed: {:?}", e);
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct ReadyAnnouncement {
+    item_kind: ContentKind,
+    item_id: i32,
+    language_code: String,
+}
+
+async fn announce_ready(state: &SharedState) -> Result<()> {
+    // Without an API key no translation is coming, so nothing waits for one
+    let translations_expected = state.google_api_key.is_some();
+
+    loop {
+        let mut tx = state.pool.begin().await?;
+        let ready = sqlx::query_as::<_, ReadyAnnouncement>(
+            r#"
+            UPDATE activitypub_announcements a SET announced_at = NOW()
+            WHERE a.id IN (
+                SELECT pending.id FROM activitypub_announcements pending
+                LEFT JOIN posts p ON pending.item_kind = 'post' AND p.id = pending.item_id
+                LEFT JOIN events e ON pending.item_kind = 'event' AND e.id = pending.item_id
+                WHERE pending.announced_at IS NULL AND (
+                    NOT $1
+                    OR pending.created_at <= NOW() - make_interval(secs => $2)
+                    OR pending.language_code = COALESCE(p.original_language, e.original_language)
+                    OR EXISTS (
+                        SELECT 1 FROM post_translations pt
+                        WHERE pt.post_id = p.id AND pt.language_code = pending.language_code
+                    )
+                    OR EXISTS (
+                        SELECT 1 FROM event_translations et
+                        WHERE et.event_id = e.id AND et.language_code = pending.language_code
+                    )
+                )
+                ORDER BY pending.created_at
+                LIMIT $3
+                FOR UPDATE OF pending SKIP LOCKED
+            )
+            RETURNING a.item_kind, a.item_id, a.language_code
+            "#,
+        )
+        .bind(translations_expected)
+        .bind(TRANSLATION_GRACE_SECS)
+        .bind(ANNOUNCE_BATCH_SIZE)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let batch = ready.len() as i64;
+        for announcement in ready {
+            let lang = &announcement.language_code;
+            // Hidden or deleted since it was published
+            let Some(object) = localized_object(state, lang, announcement.item_kind, announcement.item_id).await? else {
+                continue;
+            };
+
+            // One copy per server that offers a shared inbox
+            let queued = sqlx::query(
+                r#"
+                INSERT INTO activitypub_deliveries (language_code, inbox_url, activity)
+                SELECT DISTINCT $1, COALESCE(shared_inbox_url, inbox_url), $2::jsonb
+                FROM activitypub_followers WHERE language_code = $1
+                "#,
+            )
+            .bind(lang)
+            .bind(Json(create_activity(object)))
+            .execute(&mut *tx)
+            .await?;
+            tracing::debug!(
+                "Announcing {:?} {} to {} {} inboxes",
+                announcement.item_kind,
+                announcement.item_id,
+                queued.rows_affected(),
+                lang
+            );
+        }
+        tx.commit().await?;
+
+        if batch < ANNOUNCE_BATCH_SIZE {
+            return Ok(());
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct DueDelivery {
+    id: i32,
+    language_code: String,
+    inbox_url: String,
+    activity: Json<serde_json::Value>,
+    attempts: i32, // Including the one being made
+}
+
+async fn deliver_due(state: &SharedState) -> Result<()> {
+    let pool = &state.pool;
+    let claim = || async {
+        let due = sqlx::query_as::<_, DueDelivery>(
+            r#"
+            UPDATE activitypub_deliveries d SET
+                attempts = d.attempts + 1,
+                next_attempt_at = NOW() + make_interval(secs => $2)
+            WHERE d.id IN (
+                SELECT id FROM activitypub_deliveries
+                WHERE next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING d.id, d.language_code, d.inbox_url, d.activity, d.attempts
+            "#,
+        )
+        .bind(delivery::BATCH_SIZE)
+        .bind(delivery::CLAIM_SECS)
+        .fetch_all(pool)
+        .await?;
+        Ok(due)
+    };
+
+    delivery::drain(claim, |delivery: DueDelivery| async move {
+        let key_id = key_id(&state.public_url, &delivery.language_code);
+        let sent = match state.actor_keys.get(pool).await {
+            Ok(keys) => send(keys, &key_id, &delivery.inbox_url, &delivery.activity).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = record(pool, &delivery, sent).await {
+            tracing::error!("Failed to record ActivityPub delivery {}: {:?}", delivery.id, e);
+        }
+    })
+    .await
+}
+
+async fn record(pool: &PgPool, delivery: &DueDelivery, sent: Result<()>) -> Result<()> {
+    let error = match sent {
+        Ok(()) => None,
+        Err(e) => Some(e.to_string()),
+    };
+
+    if error.is_none() || delivery::exhausted(delivery.attempts) {
+        if let Some(error) = &error {
+            tracing::warn!(
+                "Giving up on ActivityPub delivery {} to {} after {} attempts: {}",
+                delivery.id,
+                delivery.inbox_url,
+                delivery.attempts,
+                error
+            );
+        }
+        sqlx::query("DELETE FROM activitypub_deliveries WHERE id = $1")
+            .bind(delivery.id)
+            .execute(pool)
+            .await?;
+        return Ok(());
+    }
+
+    sqlx::query(
+        "UPDATE activitypub_deliveries SET next_attempt_at = NOW() + make_interval(secs => $2), last_error = $3 WHERE id = $1",
+    )
+    .bind(delivery.id)
+    .bind(delivery::retry_delay_secs(delivery.attempts))
+    .bind(&error)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+// POSTs the activity with an HTTP signature over the request line, host, date and body
+// digest, which is what Mastodon and most other servers insist on
+async fn send(keys: &KeyPair, key_id: &str, inbox_url: &str, activity: &serde_json::Value) -> Result<()> {
+    let url = Url::parse(inbox_url)?;
+    let body = serde_json::to_vec(activity)?;
+
+    let date = Utc::now().format(HTTP_DATE).to_string();
+    let digest = format!("SHA-256={}", general_purpose::STANDARD.encode(Sha256::digest(&body)));
+    let signed = format!(
+        "(request-target): post {}\nhost: {}\ndate: {}\ndigest: {}",
+        request_path(&url),
+        host_header(&url)?,
+        date,
+        digest
+    );
+    let signature = keys
+        .private_key
+        .sign(Pkcs1v15Sign::new::<Sha256>(), &Sha256::digest(signed.as_bytes()))?;
+    let signature = format!(
+        r#"keyId="{}",algorithm="rsa-sha256",headers="(request-target) host date digest",signature="{}""#,
+        key_id,
+        general_purpose::STANDARD.encode(signature)
+    );
+
+    let client = remote::pinned_client(&url).await?;
+    let response = client
+        .post(url)
+        .header(header::CONTENT_TYPE, ACTIVITY_JSON)
+        .header(header::ACCEPT, ACTIVITY_JSON)
+        .header(header::DATE, date)
+        .header("Digest", digest)
+        .header("Signature", signature)
+        .body(body)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        bail!(delivery::error_response(response).await);
+    }
+    Ok(())
+}
+
+fn request_path(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+// As reqwest sends it: the port only when it isn't the scheme's default
+fn host_header(url: &Url) -> Result<String> {
+    let host = url.host_str().context("URL without a host")?;
+    Ok(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+/// The sender of a verified inbox request.
+#[derive(Clone)]
+pub struct RemoteActor {
+    pub id: String,
+    pub inbox: String,
+    pub shared_inbox: Option<String>,
+}
+
+/// Checks an inbox request's HTTP signature against the key its sender publishes, and
+/// that the signature covers the body. `path` includes any query string.
+pub async fn verify_request(
+    keys: &RemoteKeys,
+    method: &str,
+    path: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<RemoteActor> {
+    let signature_header = header_value(headers, "signature").context("Missing Signature header")?;
+    let params = signature_params(&signature_header);
+    let key_id = params.get("keyId").context("Signature without a keyId")?;
+    let signature = general_purpose::STANDARD
+        .decode(params.get("signature").context("Signature without a signature")?)
+        .context("Signature is not base64")?;
+    if let Some(algorithm) = params.get("algorithm") {
+        if !matches!(algorithm.as_str(), "rsa-sha256" | "hs2019") {
+            bail!("Unsupported signature algorithm {}", algorithm);
+        }
+    }
+
+    let signed_headers: Vec<String> = params
+        .get("headers")
+        .map(String::as_str)
+        .unwrap_or("date")
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    for required in ["(request-target)", "host", "date", "digest"] {
+        if !signed_headers.iter().any(|h| h == required) {
+            bail!("Signature does not cover {}", required);
+        }
+    }
+
+    let digest = header_value(headers, "digest").context("Missing Digest header")?;
+    let expected = general_purpose::STANDARD.encode(Sha256::digest(body));
+    let matches = digest
+        .split(',')
+        .filter_map(|d| d.trim().split_once('='))
+        .any(|(algorithm, value)| algorithm.eq_ignore_ascii_case("sha-256") && value == expected);
+    if !matches {
+        bail!("Digest does not match the body");
+    }
+
+    let date = header_value(headers, "date").context("Missing Date header")?;
+    let date = DateTime::parse_from_rfc2822(&date).context("Unreadable Date header")?;
+    let age = Utc::now().signed_duration_since(date);
+    if age > Duration::hours(MAX_SIGNATURE_AGE_HOURS) || age < -Duration::hours(MAX_CLOCK_SKEW_HOURS) {
+        bail!("Date {} is out of range", date);
+    }
+
+    let mut lines = Vec::with_capacity(signed_headers.len());
+    for name in &signed_headers {
+        let value = if name == "(request-target)" {
+            format!("{} {}", method.to_lowercase(), path)
+        } else {
+            header_value(headers, name).with_context(|| format!("Signed header {} is missing", name))?
+        };
+        lines.push(format!("{}: {}", name, value));
+    }
+    let signed = Sha256::digest(lines.join("\n").as_bytes());
+    let verify = |key: &RsaPublicKey| key.verify(Pkcs1v15Sign::new::<Sha256>(), &signed, &signature);
+
+    // A cached key that doesn't verify may have been rotated, so it's fetched again
+    if let Some((actor, public_key)) = keys.get(key_id) {
+        if verify(&public_key).is_ok() {
+            return Ok(actor);
+        }
+    }
+    let (actor, public_key) = fetch_signing_actor(key_id).await?;
+    verify(&public_key).context("Signature does not verify")?;
+    keys.insert(key_id, actor.clone(), public_key);
+    Ok(actor)
+}
+
+// All values of a header, joined as the signature spec says
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    let values: Vec<&str> = headers.get_all(name).iter().filter_map(|v| v.to_str().ok()).collect();
+    (!values.is_empty()).then(|| values.join(", "))
+}
+
+// keyId="...",headers="...",signature="..."
+fn signature_params(header: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    let mut rest = header.trim();
+    while let Some((name, value)) = rest.split_once('=') {
+        let name = name.trim().trim_start_matches(',').trim().to_string();
+        let value = value.trim_start();
+        let (value, remainder) = match value.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, remainder)) => (value, remainder),
+                None => (quoted, ""),
+            },
+            None => value.split_once(',').unwrap_or((value, "")),
+        };
+        params.insert(name, value.to_string());
+        rest = remainder.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    }
+    params
+}
+
+// The actor owning the key, and the key. The key id is the actor's id plus a fragment.
+async fn fetch_signing_actor(key_id: &str) -> Result<(RemoteActor, RsaPublicKey)> {
+    let mut actor_url = Url::parse(key_id).context("keyId is not a URL")?;
+    actor_url.set_fragment(None);
+
+    let response = remote::fetch_public(actor_url.as_str(), ACCEPT_ACTIVITY, MAX_ACTOR_BYTES).await?;
+    let actor: serde_json::Value = serde_json::from_slice(&response.bytes).context("Actor is not JSON")?;
+
+    // Usually one key, occasionally a list of them
+    let keys = match &actor["publicKey"] {
+        serde_json::Value::Array(keys) => keys.clone(),
+        key => vec![key.clone()],
+    };
+    let key = keys
+        .iter()
+        .find(|k| k["id"].as_str() == Some(key_id))
+        .with_context(|| format!("{} does not publish key {}", actor_url, key_id))?;
+    let owner = key["owner"].as_str().or(actor["id"].as_str());
+    let id = actor["id"].as_str().context("Actor without an id")?;
+    if owner != Some(id) {
+        bail!("Key {} is not owned by {}", key_id, id);
+    }
+    // Servers only get to speak for their own actors
+    if Url::parse(id).ok().and_then(|u| u.host_str().map(str::to_string)).as_deref() != response.url.host_str() {
+        bail!("{} served actor {}", response.url, id);
+    }
+
+    let pem = key["publicKeyPem"].as_str().context("Key without a PEM")?;
+    let public_key = RsaPublicKey::from_public_key_pem(pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+        .context("Unreadable public key")?;
+
+    let inbox = actor["inbox"].as_str().context("Actor without an inbox")?;
+    let shared_inbox = actor["endpoints"]["sharedInbox"].as_str().map(str::to_string);
+    Ok((RemoteActor { id: id.to_string(), inbox: inbox.to_string(), shared_inbox }, public_key))
+}
@@ -3,18 +3,26 @@ use std::sync::Arc;
 use tokio::time::{sleep, Duration};
 
 use crate::{
-    models::{Post, Event, Language},
-    services::translation::TranslationService,
+    models::{Post, Event, Language, WebhookEvent},
+    services::{
+        images,
+        link_preview::{decode_html, extract_preview, PagePreview},
+        notices::{ContentChange, ContentKind, Notifier},
+        remote,
+        translation::TranslationService,
+        webhooks,
+    },
 };
 
 pub async fn translate_post(
     pool: Arc<PgPool>,
+    notifier: Notifier,
     post_id: i32,
     api_key: String,
 ) {
     tracing::info!("Starting translation for post {}", post_id);
     
-    let translation_service = TranslationService::new(api_key);
+    let translation_service = TranslationService::new(api_key.clone());
     
     // Get the post
     let post = match sqlx::query_as::<_, Post>(
@@ -106,11 +114,17 @@ pub async fn translate_post(
         tracing::info!("Translated post {} to {}", post_id, lang.code);
     }
     
+    translate_link_preview(pool.clone(), post_id, api_key).await;
+    
     tracing::info!("Completed translation for post {}", post_id);
+    // Open streams re-send the post in each reader's language
+    notifier.content(ContentChange::Updated, ContentKind::Post, post_id).await;
+    webhooks::enqueue_post(&pool, WebhookEvent::PostTranslated, &post).await;
 }
 
 pub async fn translate_event(
     pool: Arc<PgPool>,
+    notifier: Notifier,
     event_id: i32,
     api_key: String,
 ) {
@@ -209,4 +223,230 @@ pub async fn translate_event(
     }
     
     tracing::info!("Completed translation for event {}", event_id);
-}
\ No newline at end of file
+    notifier.content(ContentChange::Updated, ContentKind::Event, event_id).await;
+    webhooks::enqueue_event(&pool, WebhookEvent::EventTranslated, &event).await;
+}
+// Pages are read only as far as their <head>, which is almost always within this
+const MAX_LINK_PREVIEW_PAGE_BYTES: usize = 512 * 1024;
+
+/// Fetches a post's link_url and stores a preview of the page. Failures are recorded
+/// on the preview and leave the post showing just the bare link.
+pub async fn unfurl_link(
+    pool: Arc<PgPool>,
+    post_id: i32,
+    api_key: Option<String>,
+) {
+    let post = match sqlx::query_as::<_, Post>(
+        "SELECT * FROM posts WHERE id = $1"
+    )
+    .bind(post_id)
+    .fetch_one(pool.as_ref())
+    .await {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::error!("Failed to fetch post {}: {:?}", post_id, e);
+            return;
+        }
+    };
+
+    let Some(link_url) = post.link_url else {
+        return;
+    };
+
+    if let Err(e) = sqlx::query(
+        r#"
+        INSERT INTO link_previews (post_id, url) VALUES ($1, $2)
+        ON CONFLICT (post_id) DO UPDATE SET url = EXCLUDED.url, status = 'pending'
+        "#
+    )
+    .bind(post_id)
+    .bind(&link_url)
+    .execute(pool.as_ref())
+    .await {
+        tracing::error!("Failed to create link preview for post {}: {:?}", post_id, e);
+        return;
+    }
+
+    let preview = match fetch_page_preview(&link_url).await {
+        Ok(preview) => preview,
+        Err(e) => {
+            tracing::warn!("Failed to unfurl {} for post {}: {:?}", link_url, post_id, e);
+            if let Err(e) = sqlx::query(
+                "UPDATE link_previews SET status = 'failed', fetched_at = NOW() WHERE post_id = $1"
+            )
+            .bind(post_id)
+            .execute(pool.as_ref())
+            .await
+            {
+                tracing::error!("Failed to record failed link preview for post {}: {:?}", post_id, e);
+            }
+            return;
+        }
+    };
+
+    if let Some(image_url) = &preview.image_url {
+        if let Err(e) = images::register_remote_image(pool.as_ref(), image_url).await {
+            tracing::error!("Failed to register preview image for post {}: {:?}", post_id, e);
+            return;
+        }
+    }
+
+    let saved = sqlx::query(
+        r#"
+        UPDATE link_previews SET
+            status = 'ready',
+            title = $2,
+            description = $3,
+            image_url = $4,
+            site_name = $5,
+            language = $6,
+            fetched_at = NOW()
+        WHERE post_id = $1
+        "#
+    )
+    .bind(post_id)
+    .bind(&preview.title)
+    .bind(&preview.description)
+    .bind(&preview.image_url)
+    .bind(&preview.site_name)
+    .bind(&preview.language)
+    .execute(pool.as_ref())
+    .await;
+
+    if let Err(e) = saved {
+        tracing::error!("Failed to save link preview for post {}: {:?}", post_id, e);
+        return;
+    }
+
+    tracing::info!("Unfurled link for post {}", post_id);
+
+    // Posts published (or scheduled) before the preview was ready missed its translation
+    if post.published || post.publish_at.is_some() {
+        if let Some(api_key) = api_key {
+            translate_link_preview(pool, post_id, api_key).await;
+        }
+    }
+}
+
+async fn fetch_page_preview(url: &str) -> anyhow::Result<PagePreview> {
+    let response = remote::fetch_public_prefix(
+        url,
+        "text/html,application/xhtml+xml;q=0.9,*/*;q=0.1",
+        MAX_LINK_PREVIEW_PAGE_BYTES,
+    )
+    .await?;
+
+    match response.content_type.as_deref() {
+        Some("text/html" | "application/xhtml+xml") => {}
+        other => anyhow::bail!("Not an HTML page: {:?}", other),
+    }
+
+    let html = decode_html(&response.bytes, response.charset.as_deref());
+    Ok(extract_preview(&html, &response.url))
+}
+
+/// Translates a post's link preview title and description into each enabled language.
+pub async fn translate_link_preview(
+    pool: Arc<PgPool>,
+    post_id: i32,
+    api_key: String,
+) {
+    let preview = match sqlx::query_as::<_, (Option<String>, Option<String>, Option<String>)>(
+        "SELECT title, description, language FROM link_previews WHERE post_id = $1 AND status = 'ready'"
+    )
+    .bind(post_id)
+    .fetch_optional(pool.as_ref())
+    .await {
+        Ok(Some(preview)) => preview,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to fetch link preview for post {}: {:?}", post_id, e);
+            return;
+        }
+    };
+    let (title, description, source_language) = preview;
+
+    let languages = match sqlx::query_as::<_, Language>(
+        "SELECT * FROM supported_languages WHERE enabled = true"
+    )
+    .fetch_all(pool.as_ref())
+    .await {
+        Ok(langs) => langs,
+        Err(e) => {
+            tracing::error!("Failed to fetch languages: {:?}", e);
+            return;
+        }
+    };
+
+    let translation_service = TranslationService::new(api_key);
+
+    for lang in languages {
+        if source_language.as_deref() == Some(lang.code.as_str()) {
+            continue;
+        }
+
+        // Small delay to avoid rate limiting
+        sleep(Duration::from_millis(100)).await;
+
+        let translated_title = match &title {
+            Some(title) => match translation_service
+                .translate_text(title, &lang.code, source_language.as_deref())
+                .await {
+                Ok(r) => Some(r),
+                Err(e) => {
+                    tracing::error!("Failed to translate link preview title to {}: {:?}", lang.code, e);
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let translated_description = match &description {
+            Some(description) => match translation_service
+                .translate_text(description, &lang.code, source_language.as_deref())
+                .await {
+                Ok(r) => Some(r),
+                Err(e) => {
+                    tracing::error!("Failed to translate link preview description to {}: {:?}", lang.code, e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // Pages that don't declare a language are auto-detected; there's nothing to
+        // store when one turns out to be in the target language already
+        let detected_language = translated_title
+            .as_ref()
+            .or(translated_description.as_ref())
+            .map(|r| r.source_language.split('-').next().unwrap_or("").to_string());
+        if detected_language.is_none() || detected_language.as_deref() == Some(lang.code.as_str()) {
+            continue;
+        }
+
+        let stored = sqlx::query(
+            r#"
+            INSERT INTO link_preview_translations (
+                post_id, language_code, title, description
+            )
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (post_id, language_code)
+            DO UPDATE SET
+                title = EXCLUDED.title,
+                description = EXCLUDED.description,
+                translated_at = NOW()
+            "#
+        )
+        .bind(post_id)
+        .bind(&lang.code)
+        .bind(translated_title.map(|r| r.translated_text))
+        .bind(translated_description.map(|r| r.translated_text))
+        .execute(pool.as_ref())
+        .await;
+
+        match stored {
+            Ok(_) => tracing::info!("Translated link preview for post {} to {}", post_id, lang.code),
+            Err(e) => tracing::error!("Failed to store link preview translation for post {} to {}: {:?}", post_id, lang.code, e),
+        }
+    }
+}
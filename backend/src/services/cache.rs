@@ -0,0 +1,40 @@
+use redis::AsyncCommands;
+
+// Redis is only a cache here: every failure is logged and treated as a miss
+
+pub async fn get_i64(client: &redis::Client, key: &str) -> Option<i64> {
+    let result: redis::RedisResult<Option<i64>> = async {
+        let mut conn = client.get_multiplexed_async_connection().await?;
+        conn.get(key).await
+    }
+    .await;
+
+    result.unwrap_or_else(|e| {
+        tracing::warn!("Redis GET {} failed: {:?}", key, e);
+        None
+    })
+}
+
+pub async fn set_i64(client: &redis::Client, key: &str, value: i64, ttl_seconds: u64) {
+    let result: redis::RedisResult<()> = async {
+        let mut conn = client.get_multiplexed_async_connection().await?;
+        conn.set_ex(key, value, ttl_seconds).await
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!("Redis SET {} failed: {:?}", key, e);
+    }
+}
+
+pub async fn invalidate(client: &redis::Client, key: &str) {
+    let result: redis::RedisResult<()> = async {
+        let mut conn = client.get_multiplexed_async_connection().await?;
+        conn.del(key).await
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!("Redis DEL {} failed: {:?}", key, e);
+    }
+}
@@ -0,0 +1,343 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::HashSet, net::IpAddr, sync::Arc, time::Duration};
+
+use super::rate_limit::RateLimiter;
+
+const DEFAULT_DIFFICULTY: u32 = 16;
+const MAX_DIFFICULTY: u32 = 32;
+const PROOF_OF_WORK_TTL_SECONDS: i64 = 10 * 60;
+
+const TURNSTILE_VERIFY_URL: &str = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
+const HCAPTCHA_VERIFY_URL: &str = "https://api.hcaptcha.com/siteverify";
+
+// The one response the stub provider accepts
+const STUB_RESPONSE: &str = "pass";
+
+/// What a client has to do before submitting, as returned by `GET /api/challenge`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Challenge {
+    /// Find a `solution` such that sha256("{token}:{solution}") starts with
+    /// `difficulty` zero bits, then submit "{token}:{solution}".
+    ProofOfWork {
+        token: String,
+        difficulty: u32,
+        expires_at: DateTime<Utc>,
+    },
+    /// Render the provider's widget with `site_key` and submit the token it produces.
+    Captcha { provider: &'static str, site_key: String },
+}
+
+#[async_trait]
+pub trait ChallengeProvider: Send + Sync {
+    async fn issue(&self) -> Result<Challenge>;
+
+    /// Whether `response` solves a challenge from this provider. A solution is only
+    /// accepted once.
+    async fn verify(&self, response: &str, client: IpAddr) -> Result<bool>;
+}
+
+/// Hashcash-style proof of work. Tokens are signed rather than stored, so issuing one
+/// costs nothing; only spent tokens are remembered, until they'd have expired anyway.
+pub struct ProofOfWork {
+    secret: Vec<u8>,
+    difficulty: u32,
+    spent: RateLimiter,
+}
+
+impl ProofOfWork {
+    pub fn new(secret: Vec<u8>, difficulty: u32, spent: RateLimiter) -> Self {
+        Self { secret, difficulty, spent }
+    }
+
+    fn sign(&self, payload: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+#[async_trait]
+impl ChallengeProvider for ProofOfWork {
+    async fn issue(&self) -> Result<Challenge> {
+        let expires_at = Utc::now() + chrono::Duration::seconds(PROOF_OF_WORK_TTL_SECONDS);
+        let payload = format!(
+            "{}.{}.{}",
+            expires_at.timestamp(),
+            hex::encode(rand::random::<[u8; 16]>()),
+            self.difficulty
+        );
+        let signature = hex::encode(self.sign(&payload).finalize().into_bytes());
+
+        Ok(Challenge::ProofOfWork {
+            token: format!("{}.{}", payload, signature),
+            difficulty: self.difficulty,
+            expires_at,
+        })
+    }
+
+    async fn verify(&self, response: &str, _client: IpAddr) -> Result<bool> {
+        let Some((token, _solution)) = response.rsplit_once(':') else {
+            return Ok(false);
+        };
+        let Some((payload, signature)) = token.rsplit_once('.') else {
+            return Ok(false);
+        };
+        let Ok(signature) = hex::decode(signature) else {
+            return Ok(false);
+        };
+        if self.sign(payload).verify_slice(&signature).is_err() {
+            return Ok(false);
+        }
+
+        // The signature vouches for the fields
+        let fields: Vec<&str> = payload.split('.').collect();
+        let [expires, nonce, difficulty] = fields[..] else {
+            return Ok(false);
+        };
+        let remaining = expires.parse::<i64>()? - Utc::now().timestamp();
+        if remaining <= 0 || leading_zero_bits(&Sha256::digest(response.as_bytes())) < difficulty.parse()? {
+            return Ok(false);
+        }
+
+        let (uses, _) = self
+            .spent
+            .increment(&format!("challenge_spent:{}", nonce), Duration::from_secs(remaining as u64))
+            .await;
+        Ok(uses == 1)
+    }
+}
+
+/// hCaptcha and Turnstile, which share the `siteverify` API.
+pub struct SiteVerifyCaptcha {
+    provider: &'static str,
+    verify_url: String,
+    site_key: String,
+    secret: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+    #[serde(rename = "error-codes", default)]
+    error_codes: Vec<String>,
+}
+
+impl SiteVerifyCaptcha {
+    pub fn new(provider: &'static str, verify_url: String, site_key: String, secret: String) -> Self {
+        Self {
+            provider,
+            verify_url,
+            site_key,
+            secret,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to build captcha client"),
+        }
+    }
+}
+
+#[async_trait]
+impl ChallengeProvider for SiteVerifyCaptcha {
+    async fn issue(&self) -> Result<Challenge> {
+        Ok(Challenge::Captcha {
+            provider: self.provider,
+            site_key: self.site_key.clone(),
+        })
+    }
+
+    async fn verify(&self, response: &str, client: IpAddr) -> Result<bool> {
+        let result = self
+            .client
+            .post(&self.verify_url)
+            .form(&[
+                ("secret", self.secret.as_str()),
+                ("response", response),
+                ("remoteip", &client.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<SiteVerifyResponse>()
+            .await?;
+
+        if !result.success {
+            tracing::debug!("{} rejected a response: {:?}", self.provider, result.error_codes);
+        }
+        Ok(result.success)
+    }
+}
+
+/// Stands in for a captcha in development and tests: the response "pass" solves it.
+pub struct StubCaptcha;
+
+#[async_trait]
+impl ChallengeProvider for StubCaptcha {
+    async fn issue(&self) -> Result<Challenge> {
+        Ok(Challenge::Captcha {
+            provider: "stub",
+            site_key: "stub".to_string(),
+        })
+    }
+
+    async fn verify(&self, response: &str, _client: IpAddr) -> Result<bool> {
+        Ok(response == STUB_RESPONSE)
+    }
+}
+
+/// The challenge provider and the routes that require solving one.
+pub struct ChallengeConfig {
+    pub provider: Arc<dyn ChallengeProvider>,
+    routes: HashSet<String>,
+}
+
+impl ChallengeConfig {
+    pub fn new(provider: Arc<dyn ChallengeProvider>, routes: HashSet<String>) -> Self {
+        Self { provider, routes }
+    }
+
+    pub fn required_for(&self, route: &str) -> bool {
+        self.routes.contains(route)
+    }
+}
+
+/// Builds the configuration from `CHALLENGE_ROUTES` (e.g. "posts,events"; empty turns
+/// challenges off) and `CHALLENGE_PROVIDER`: `pow` (the default), `turnstile`,
+/// `hcaptcha` or `stub`.
+pub fn challenge_config_from_env(rate_limiter: &RateLimiter) -> ChallengeConfig {
+    let routes: HashSet<String> = std::env::var("CHALLENGE_ROUTES")
+        .unwrap_or_default()
+        .split(',')
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .collect();
+
+    let provider_name = std::env::var("CHALLENGE_PROVIDER").unwrap_or_else(|_| "pow".to_string());
+    let captcha = |provider, verify_url: &str| -> Arc<dyn ChallengeProvider> {
+        let site_key = std::env::var("CAPTCHA_SITE_KEY").expect("CAPTCHA_SITE_KEY must be set for a captcha provider");
+        let secret = std::env::var("CAPTCHA_SECRET").expect("CAPTCHA_SECRET must be set for a captcha provider");
+        let verify_url = std::env::var("CAPTCHA_VERIFY_URL").unwrap_or_else(|_| verify_url.to_string());
+        Arc::new(SiteVerifyCaptcha::new(provider, verify_url, site_key, secret))
+    };
+
+    let provider: Arc<dyn ChallengeProvider> = match provider_name.as_str() {
+        "pow" => {
+            let difficulty = std::env::var("CHALLENGE_DIFFICULTY")
+                .ok()
+                .map(|d| d.parse().expect("Invalid CHALLENGE_DIFFICULTY"))
+                .unwrap_or(DEFAULT_DIFFICULTY)
+                .min(MAX_DIFFICULTY);
+            // Without a configured secret, tokens don't survive a restart or work across instances
+            let secret = std::env::var("CHALLENGE_SECRET")
+                .ok()
+                .filter(|s| !s.is_empty())
+                .map(String::into_bytes)
+                .unwrap_or_else(|| rand::random::<[u8; 32]>().to_vec());
+            Arc::new(ProofOfWork::new(secret, difficulty, rate_limiter.clone()))
+        }
+        "turnstile" => captcha("turnstile", TURNSTILE_VERIFY_URL),
+        "hcaptcha" => captcha("hcaptcha", HCAPTCHA_VERIFY_URL),
+        "stub" => Arc::new(StubCaptcha),
+        other => panic!("Unknown CHALLENGE_PROVIDER: {}", other),
+    };
+
+    if !routes.is_empty() {
+        let mut names: Vec<_> = routes.iter().map(String::as_str).collect();
+        names.sort();
+        tracing::info!("Requiring a {} challenge for {}", provider_name, names.join(", "));
+    }
+    ChallengeConfig::new(provider, routes)
+}
+
+fn leading_zero_bits(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: IpAddr = IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+
+    fn proof_of_work(difficulty: u32) -> ProofOfWork {
+        // Nothing listens there, so spent tokens are tracked in memory
+        let redis = redis::Client::open("redis://127.0.0.1:1").unwrap();
+        ProofOfWork::new(b"test secret".to_vec(), difficulty, RateLimiter::new(redis))
+    }
+
+    async fn issue_token(pow: &ProofOfWork) -> String {
+        match pow.issue().await.unwrap() {
+            Challenge::ProofOfWork { token, .. } => token,
+            other => panic!("unexpected challenge {:?}", other),
+        }
+    }
+
+    fn solve(token: &str, difficulty: u32) -> String {
+        (0u64..)
+            .map(|solution| format!("{}:{}", token, solution))
+            .find(|response| leading_zero_bits(&Sha256::digest(response.as_bytes())) >= difficulty)
+            .unwrap()
+    }
+
+    #[test]
+    fn leading_zero_bits_counts_across_bytes() {
+        assert_eq!(leading_zero_bits(&[0xff]), 0);
+        assert_eq!(leading_zero_bits(&[0x01, 0xff]), 7);
+        assert_eq!(leading_zero_bits(&[0x00, 0x00, 0x10]), 19);
+        assert_eq!(leading_zero_bits(&[0x00, 0x80, 0x00]), 8);
+        assert_eq!(leading_zero_bits(&[0x00, 0x00]), 16);
+    }
+
+    #[tokio::test]
+    async fn proof_of_work_accepts_a_solution_once() {
+        let pow = proof_of_work(8);
+        let response = solve(&issue_token(&pow).await, 8);
+
+        assert!(pow.verify(&response, CLIENT).await.unwrap());
+        assert!(!pow.verify(&response, CLIENT).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn proof_of_work_rejects_too_little_work() {
+        let pow = proof_of_work(8);
+        let token = issue_token(&pow).await;
+        let response = (0u64..)
+            .map(|solution| format!("{}:{}", token, solution))
+            .find(|response| leading_zero_bits(&Sha256::digest(response.as_bytes())) < 8)
+            .unwrap();
+
+        assert!(!pow.verify(&response, CLIENT).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn proof_of_work_rejects_forged_tokens() {
+        let pow = proof_of_work(8);
+        let token = issue_token(&pow).await;
+
+        // Lowering the difficulty breaks the signature
+        let (payload, signature) = token.rsplit_once('.').unwrap();
+        let easier = format!("{}.0.{}", payload.rsplit_once('.').unwrap().0, signature);
+        assert!(!pow.verify(&solve(&easier, 0), CLIENT).await.unwrap());
+
+        // So does a token signed with another secret
+        let other = ProofOfWork::new(b"other secret".to_vec(), 8, proof_of_work(8).spent);
+        let foreign = solve(&issue_token(&other).await, 8);
+        assert!(!pow.verify(&foreign, CLIENT).await.unwrap());
+
+        assert!(!pow.verify("no separator", CLIENT).await.unwrap());
+    }
+}
@@ -0,0 +1,111 @@
+use std::future::Future;
+
+use anyhow::Result;
+use futures_util::future::join_all;
+
+// Outgoing deliveries (webhooks and ActivityPub) are queued in the database, claimed a
+// batch at a time so each goes out from one instance only, and retried with backoff.
+
+pub const POLL: std::time::Duration = std::time::Duration::from_secs(5);
+pub const BATCH_SIZE: i64 = 20;
+// A claimed delivery is left alone by other instances for this long, comfortably more
+// than a request can take
+pub const CLAIM_SECS: f64 = 120.0;
+// Six attempts, the last about two and a half hours after the first
+pub const MAX_ATTEMPTS: i32 = 6;
+const FIRST_RETRY_SECS: i64 = 30;
+// Enough of a failed response's body to tell what went wrong. Receivers aren't trusted,
+// so no more than this is read, however much they send.
+const MAX_ERROR_BODY_CHARS: usize = 500;
+const MAX_ERROR_BODY_BYTES: usize = 4 * MAX_ERROR_BODY_CHARS;
+
+/// Claims due deliveries with `claim`, which takes up to `BATCH_SIZE` of them for
+/// `CLAIM_SECS`, and sends each batch concurrently with `send`, for as long as there's
+/// a backlog.
+pub async fn drain<T, Claim, Claimed, Send, Sent>(mut claim: Claim, send: Send) -> Result<()>
+where
+    Claim: FnMut() -> Claimed,
+    Claimed: Future<Output = Result<Vec<T>>>,
+    Send: Fn(T) -> Sent,
+    Sent: Future<Output = ()>,
+{
+    loop {
+        let due = claim().await?;
+        let batch = due.len() as i64;
+        join_all(due.into_iter().map(&send)).await;
+
+        if batch < BATCH_SIZE {
+            return Ok(());
+        }
+    }
+}
+
+/// Whether a delivery is given up on after failing its `attempts`th attempt.
+pub fn exhausted(attempts: i32) -> bool {
+    attempts >= MAX_ATTEMPTS
+}
+
+// 30s, 2m, 8m, 32m, then a little over 2h
+pub fn retry_delay_secs(attempts: i32) -> f64 {
+    (FIRST_RETRY_SECS * 4_i64.pow(attempts.clamp(1, MAX_ATTEMPTS) as u32 - 1)) as f64
+}
+
+/// What went wrong, for a response that wasn't a 2xx.
+pub async fn error_response(mut response: reqwest::Response) -> String {
+    let status = response.status();
+
+    let mut bytes = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        let take = chunk.len().min(MAX_ERROR_BODY_BYTES - bytes.len());
+        bytes.extend_from_slice(&chunk[..take]);
+        if bytes.len() == MAX_ERROR_BODY_BYTES {
+            break;
+        }
+    }
+
+    let body: String = String::from_utf8_lossy(&bytes).chars().take(MAX_ERROR_BODY_CHARS).collect();
+    format!("HTTP {}: {}", status, body.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    #[test]
+    fn retries_back_off_until_exhausted() {
+        assert_eq!(retry_delay_secs(1), 30.0);
+        assert_eq!(retry_delay_secs(2), 120.0);
+        assert_eq!(retry_delay_secs(MAX_ATTEMPTS), retry_delay_secs(MAX_ATTEMPTS + 1));
+        assert!(!exhausted(MAX_ATTEMPTS - 1));
+        assert!(exhausted(MAX_ATTEMPTS));
+    }
+
+    #[tokio::test]
+    async fn error_response_reads_only_the_start_of_an_endless_body() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = socket.read(&mut request).await;
+            socket
+                .write_all(b"HTTP/1.1 500 Internal Server Error\r\ntransfer-encoding: chunked\r\n\r\n")
+                .await
+                .unwrap();
+            // Keeps sending until the client hangs up
+            let chunk = format!("{:x}\r\n{}\r\n", 1024, "é".repeat(512));
+            while socket.write_all(chunk.as_bytes()).await.is_ok() {}
+        });
+
+        let response = reqwest::get(format!("http://{}/", addr)).await.unwrap();
+        let error = error_response(response).await;
+
+        assert!(error.starts_with("HTTP 500 Internal Server Error: ééé"));
+        assert_eq!(error.chars().filter(|c| *c == 'é').count(), MAX_ERROR_BODY_CHARS);
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::{sync::Arc, time::Duration};
+
+#[derive(Debug, Clone)]
+pub struct GeocodeResult {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub display_name: Option<String>,
+}
+
+#[async_trait]
+pub trait Geocoder: Send + Sync {
+    /// Resolves a free-text address; `Ok(None)` means the provider found no match.
+    async fn geocode(&self, address: &str) -> Result<Option<GeocodeResult>>;
+}
+
+/// Geocoder for deployments without a provider configured; never resolves anything.
+pub struct DisabledGeocoder;
+
+#[async_trait]
+impl Geocoder for DisabledGeocoder {
+    async fn geocode(&self, _address: &str) -> Result<Option<GeocodeResult>> {
+        Ok(None)
+    }
+}
+
+/// Talks to any service exposing the Nominatim `/search?format=json` API, including
+/// a self-hosted Nominatim or a local stub serving canned responses.
+pub struct NominatimGeocoder {
+    base_url: String,
+    user_agent: String,
+    client: reqwest::Client,
+}
+
+#[derive(Debug, Deserialize)]
+struct NominatimPlace {
+    lat: String,
+    lon: String,
+    display_name: Option<String>,
+}
+
+impl NominatimGeocoder {
+    pub fn new(base_url: String, user_agent: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            user_agent,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to build geocoding client"),
+        }
+    }
+}
+
+#[async_trait]
+impl Geocoder for NominatimGeocoder {
+    async fn geocode(&self, address: &str) -> Result<Option<GeocodeResult>> {
+        let places = self
+            .client
+            .get(format!("{}/search", self.base_url))
+            .header(reqwest::header::USER_AGENT, &self.user_agent)
+            .query(&[("q", address), ("format", "json"), ("limit", "1")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<NominatimPlace>>()
+            .await?;
+
+        let place = match places.into_iter().next() {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+
+        Ok(Some(GeocodeResult {
+            latitude: place.lat.parse()?,
+            longitude: place.lon.parse()?,
+            display_name: place.display_name,
+        }))
+    }
+}
+
+/// Builds the geocoder from `GEOCODER_URL` / `GEOCODER_USER_AGENT`; disabled when no URL is set.
+pub fn geocoder_from_env() -> Arc<dyn Geocoder> {
+    match std::env::var("GEOCODER_URL").ok().filter(|u| !u.is_empty()) {
+        Some(url) => {
+            let user_agent = std::env::var("GEOCODER_USER_AGENT")
+                .unwrap_or_else(|_| "lancaster-local/0.1".to_string());
+            tracing::info!("Geocoding venues via {}", url);
+            Arc::new(NominatimGeocoder::new(url, user_agent))
+        }
+        None => Arc::new(DisabledGeocoder),
+    }
+}
+
+/// Normalized identity of a place: case, punctuation and whitespace differences are ignored.
+pub fn venue_dedupe_key(name: &str, address: Option<&str>) -> String {
+    fn normalize(value: &str) -> String {
+        value
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    format!("{}|{}", normalize(name), normalize(address.unwrap_or("")))
+}
@@ -0,0 +1,180 @@
+use anyhow::{bail, Result};
+use image::{imageops::FilterType, io::Limits, DynamicImage, ImageFormat, ImageOutputFormat};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::io::Cursor;
+
+pub const MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+
+// Larger sources are rejected before decoding (decompression bombs)
+const MAX_SOURCE_DIMENSION: u32 = 12_000;
+const MAX_DECODE_ALLOC_BYTES: u64 = 512 * 1024 * 1024;
+
+const DISPLAY_MAX_DIMENSION: u32 = 1600;
+const THUMBNAIL_MAX_DIMENSION: u32 = 400;
+const JPEG_QUALITY: u8 = 85;
+
+/// MIME types accepted for upload; anything else is refused before decoding.
+pub const ACCEPTED_CONTENT_TYPES: &[&str] = &["image/jpeg", "image/png", "image/webp", "image/gif"];
+
+pub struct Variant {
+    pub key: String, // Content address: sha256 of the encoded bytes plus extension
+    pub bytes: Vec<u8>,
+    pub content_type: &'static str,
+}
+
+pub struct ProcessedImage {
+    pub display: Variant,
+    pub thumbnail: Variant,
+    pub width: u32, // Of the display variant
+    pub height: u32,
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Makes a remote image URL fetchable through the image proxy. Its proxy id is the
+/// sha256 of the URL, which queries derive with `encode(sha256(...), 'hex')`.
+pub async fn register_remote_image(pool: &PgPool, url: &str) -> Result<()> {
+    sqlx::query("INSERT INTO remote_images (id, url) VALUES ($1, $2) ON CONFLICT (id) DO NOTHING")
+        .bind(sha256_hex(url.as_bytes()))
+        .bind(url)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Decodes an upload, applies its EXIF orientation and re-encodes it into display and
+/// thumbnail variants. Re-encoding drops all metadata, including GPS coordinates.
+pub fn process_upload(bytes: &[u8]) -> Result<ProcessedImage> {
+    let (image, encoding) = decode(bytes)?;
+
+    let display = fit(&image, DISPLAY_MAX_DIMENSION);
+    let thumbnail = image.thumbnail(THUMBNAIL_MAX_DIMENSION, THUMBNAIL_MAX_DIMENSION);
+
+    Ok(ProcessedImage {
+        width: display.width(),
+        height: display.height(),
+        display: encode(&display, &encoding)?,
+        thumbnail: encode(&thumbnail, &encoding)?,
+    })
+}
+
+/// Same treatment for an image fetched from elsewhere, which only needs the display variant.
+pub fn process_remote(bytes: &[u8]) -> Result<Variant> {
+    let (image, encoding) = decode(bytes)?;
+    encode(&fit(&image, DISPLAY_MAX_DIMENSION), &encoding)
+}
+
+struct Encoding {
+    format: ImageOutputFormat,
+    extension: &'static str,
+    content_type: &'static str,
+}
+
+fn decode(bytes: &[u8]) -> Result<(DynamicImage, Encoding)> {
+    let format = match image::guess_format(bytes)? {
+        f @ (ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP | ImageFormat::Gif) => f,
+        other => bail!("Unsupported image format: {:?}", other),
+    };
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC_BYTES);
+
+    let mut reader = image::io::Reader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    let mut image = reader.decode()?;
+
+    if format == ImageFormat::Jpeg {
+        image = apply_orientation(image, jpeg_orientation(bytes).unwrap_or(1));
+    }
+
+    // Keep transparency where there is some; everything else becomes a JPEG
+    let encoding = if image.color().has_alpha() {
+        Encoding { format: ImageOutputFormat::Png, extension: "png", content_type: "image/png" }
+    } else {
+        image = DynamicImage::ImageRgb8(image.to_rgb8());
+        Encoding { format: ImageOutputFormat::Jpeg(JPEG_QUALITY), extension: "jpg", content_type: "image/jpeg" }
+    };
+
+    Ok((image, encoding))
+}
+
+fn fit(image: &DynamicImage, max_dimension: u32) -> DynamicImage {
+    if image.width() > max_dimension || image.height() > max_dimension {
+        image.resize(max_dimension, max_dimension, FilterType::Lanczos3)
+    } else {
+        image.clone()
+    }
+}
+
+fn encode(image: &DynamicImage, encoding: &Encoding) -> Result<Variant> {
+    let mut encoded = Cursor::new(Vec::new());
+    image.write_to(&mut encoded, encoding.format.clone())?;
+    let bytes = encoded.into_inner();
+    Ok(Variant {
+        key: format!("{}.{}", sha256_hex(&bytes), encoding.extension),
+        bytes,
+        content_type: encoding.content_type,
+    })
+}
+
+fn apply_orientation(image: DynamicImage, orientation: u16) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+// Reads the Orientation tag (0x0112) from a JPEG's EXIF (APP1) segment
+fn jpeg_orientation(bytes: &[u8]) -> Option<u16> {
+    let mut pos = 2; // Skip SOI
+    while pos + 4 <= bytes.len() && bytes[pos] == 0xFF {
+        let marker = bytes[pos + 1];
+        let length = u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]) as usize;
+        let segment = bytes.get(pos + 4..pos + 2 + length)?;
+
+        if marker == 0xE1 && segment.starts_with(b"Exif\0\0") {
+            return tiff_orientation(&segment[6..]);
+        }
+        // Start of scan: no more metadata segments
+        if marker == 0xDA {
+            return None;
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+fn tiff_orientation(tiff: &[u8]) -> Option<u16> {
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let u16_at = |offset: usize| -> Option<u16> {
+        let b = tiff.get(offset..offset + 2)?;
+        Some(if little_endian { u16::from_le_bytes([b[0], b[1]]) } else { u16::from_be_bytes([b[0], b[1]]) })
+    };
+    let u32_at = |offset: usize| -> Option<u32> {
+        let b = tiff.get(offset..offset + 4)?;
+        let b = [b[0], b[1], b[2], b[3]];
+        Some(if little_endian { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+    };
+
+    let ifd = u32_at(4)? as usize;
+    let entries = u16_at(ifd)? as usize;
+    (0..entries)
+        .map(|i| ifd + 2 + i * 12)
+        .find(|&entry| u16_at(entry) == Some(0x0112))
+        .and_then(|entry| u16_at(entry + 8))
+}
@@ -0,0 +1,114 @@
+use reqwest::Url;
+use std::collections::HashMap;
+
+use crate::services::{html::unescape_html, urls::normalize_url};
+
+// Match the link_previews columns
+const MAX_TITLE_CHARS: usize = 300;
+const MAX_SITE_NAME_CHARS: usize = 200;
+const MAX_DESCRIPTION_CHARS: usize = 1000;
+
+lazy_static::lazy_static! {
+    static ref HEAD_END: regex::Regex = regex::Regex::new(r"(?i)</head\s*>|<body\b").unwrap();
+    static ref META_TAG: regex::Regex = regex::Regex::new(r"(?is)<meta\b[^>]*>").unwrap();
+    static ref ATTRIBUTE: regex::Regex =
+        regex::Regex::new(r#"(?is)([a-z][a-z0-9:_-]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap();
+    static ref TITLE_TAG: regex::Regex = regex::Regex::new(r"(?is)<title\b[^>]*>(.*?)</title\s*>").unwrap();
+    static ref META_CHARSET: regex::bytes::Regex =
+        regex::bytes::Regex::new(r#"(?i)<meta\b[^>]*?charset\s*=\s*["']?([a-z0-9_:.-]+)"#).unwrap();
+    static ref HTML_LANG: regex::Regex =
+        regex::Regex::new(r#"(?is)<html\b[^>]*?\blang\s*=\s*["']?([a-z]{2,3})\b"#).unwrap();
+}
+
+#[derive(Debug, Default)]
+pub struct PagePreview {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>, // Absolute and validated like submitted URLs
+    pub site_name: Option<String>,
+    pub language: Option<String>, // Primary language subtag, e.g. "en"
+}
+
+/// Decodes a page using the charset from its Content-Type header or, failing that, its
+/// own <meta> declaration. Undeclared pages are taken to be UTF-8.
+pub fn decode_html(bytes: &[u8], header_charset: Option<&str>) -> String {
+    let declared = header_charset.map(|c| c.as_bytes().to_vec()).or_else(|| {
+        let prefix = &bytes[..bytes.len().min(4096)];
+        META_CHARSET.captures(prefix).map(|c| c[1].to_vec())
+    });
+
+    let encoding = declared
+        .and_then(|label| encoding_rs::Encoding::for_label(&label))
+        .unwrap_or(encoding_rs::UTF_8);
+    // A byte order mark overrides the declaration, as in browsers
+    let (text, _, _) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+/// Pulls a preview out of a page's Open Graph, Twitter card and plain HTML metadata,
+/// in that order of preference.
+pub fn extract_preview(html: &str, page_url: &Url) -> PagePreview {
+    // Metadata belongs in <head>; stopping there keeps <meta> tags in the content out
+    let head = match HEAD_END.find(html) {
+        Some(end) => &html[..end.start()],
+        None => html,
+    };
+
+    let mut meta: HashMap<String, String> = HashMap::new();
+    for tag in META_TAG.find_iter(head) {
+        let attributes: HashMap<String, String> = ATTRIBUTE
+            .captures_iter(tag.as_str())
+            .map(|c| {
+                let value = c.get(2).or_else(|| c.get(3)).or_else(|| c.get(4)).map_or("", |m| m.as_str());
+                (c[1].to_lowercase(), value.to_string())
+            })
+            .collect();
+
+        let key = attributes.get("property").or_else(|| attributes.get("name"));
+        if let (Some(key), Some(content)) = (key, attributes.get("content")) {
+            // The first occurrence wins, as it does for crawlers
+            meta.entry(key.to_lowercase()).or_insert_with(|| content.clone());
+        }
+    }
+    let first = |keys: &[&str]| keys.iter().find_map(|k| meta.get(*k).map(String::as_str));
+
+    let title = first(&["og:title", "twitter:title"])
+        .or_else(|| TITLE_TAG.captures(head).and_then(|c| c.get(1)).map(|m| m.as_str()));
+    let description = first(&["og:description", "twitter:description", "description"]);
+
+    let image_url = first(&["og:image:secure_url", "og:image", "og:image:url", "twitter:image", "twitter:image:src"])
+        .and_then(|image| page_url.join(unescape_html(image).trim()).ok())
+        .and_then(|image| normalize_url(Some(image.as_str())).ok().flatten());
+
+    let site_name = first(&["og:site_name"])
+        .and_then(|name| clean(name, MAX_SITE_NAME_CHARS))
+        .or_else(|| page_url.host_str().map(|host| host.trim_start_matches("www.").to_string()));
+
+    let language = HTML_LANG
+        .captures(head)
+        .map(|c| c[1].to_lowercase())
+        .or_else(|| first(&["og:locale"]).and_then(|l| l.split(['_', '-']).next()).map(str::to_lowercase))
+        .filter(|l| (2..=3).contains(&l.len()));
+
+    PagePreview {
+        title: title.and_then(|t| clean(t, MAX_TITLE_CHARS)),
+        description: description.and_then(|d| clean(d, MAX_DESCRIPTION_CHARS)),
+        image_url,
+        site_name,
+        language,
+    }
+}
+
+// Decodes entities, collapses whitespace and cuts to the column size
+fn clean(value: &str, max_chars: usize) -> Option<String> {
+    let value = unescape_html(value).split_whitespace().collect::<Vec<_>>().join(" ");
+    if value.is_empty() {
+        return None;
+    }
+    if value.chars().count() <= max_chars {
+        return Some(value);
+    }
+
+    let truncated: String = value.chars().take(max_chars - 1).collect();
+    Some(format!("{}…", truncated.trim_end()))
+}
@@ -0,0 +1,95 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use lettre::{
+    message::{
+        header::{HeaderName, HeaderValue},
+        Mailbox, MultiPart,
+    },
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+    pub list_unsubscribe: Option<String>, // One-click unsubscribe URL (RFC 8058)
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: Email) -> Result<()>;
+}
+
+/// Mailer for deployments without SMTP configured; logs what would have been sent.
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, email: Email) -> Result<()> {
+        tracing::info!("SMTP_URL is not set; not sending {:?} to {}", email.subject, email.to);
+        tracing::debug!("{}", email.text);
+        Ok(())
+    }
+}
+
+/// Sends through any SMTP server, including a local mail catcher such as Mailpit or MailHog.
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(url: &str, from: Mailbox) -> Result<Self> {
+        Ok(Self {
+            transport: AsyncSmtpTransport::<Tokio1Executor>::from_url(url)?.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, email: Email) -> Result<()> {
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .to(email.to.parse()?)
+            .subject(email.subject);
+
+        if let Some(url) = email.list_unsubscribe {
+            builder = builder
+                .raw_header(HeaderValue::new(
+                    HeaderName::new_from_ascii_str("List-Unsubscribe"),
+                    format!("<{}>", url),
+                ))
+                .raw_header(HeaderValue::new(
+                    HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+                    "List-Unsubscribe=One-Click".to_string(),
+                ));
+        }
+
+        let message = builder.multipart(MultiPart::alternative_plain_html(email.text, email.html))?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
+
+/// Builds the mailer from `SMTP_URL` / `NEWSLETTER_FROM`; only logs when no URL is set.
+pub fn mailer_from_env() -> Arc<dyn Mailer> {
+    match std::env::var("SMTP_URL").ok().filter(|u| !u.is_empty()) {
+        Some(url) => {
+            let from = std::env::var("NEWSLETTER_FROM")
+                .unwrap_or_else(|_| "Lancaster Local <newsletter@localhost>".to_string())
+                .parse()
+                .expect("Invalid NEWSLETTER_FROM");
+            let mailer = SmtpMailer::new(&url, from).expect("Invalid SMTP_URL");
+            // The URL may carry credentials, so only the server is logged
+            let server = url.rsplit_once('@').map_or(url.as_str(), |(_, server)| server);
+            tracing::info!("Sending email via {}", server);
+            Arc::new(mailer)
+        }
+        None => Arc::new(LogMailer),
+    }
+}
@@ -1,2 +1,23 @@
+pub mod activitypub;
 pub mod background;
-pub mod translation;
\ No newline at end of file
+pub mod cache;
+pub mod challenge;
+pub mod delivery;
+pub mod geocoding;
+pub mod html;
+pub mod images;
+pub mod link_preview;
+pub mod mail;
+pub mod newsletter;
+pub mod notices;
+pub mod rate_limit;
+pub mod recurrence;
+pub mod remote;
+pub mod scheduler;
+pub mod slug;
+pub mod spam;
+pub mod storage;
+pub mod translation;
+pub mod urls;
+pub mod validation;
+pub mod webhooks;
\ No newline at end of file
@@ -0,0 +1,803 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use sqlx::FromRow;
+use tokio::time::{interval, MissedTickBehavior};
+
+use crate::{
+    db::SharedState,
+    error::ApiError,
+    handlers::{fetch_events_between, fetch_posts_published_between},
+    models::{EventWithTranslation, NewsletterSubscriber, NewsletterTopic, PostWithTranslation},
+    services::{
+        activitypub::page_url,
+        html::escape_html,
+        mail::Email,
+        notices::ContentKind,
+        translation::content_language,
+    },
+};
+
+// Editions go out on Friday mornings (UTC) with the past week's posts and the coming
+// week's events
+const EDITION_WEEKDAY: Weekday = Weekday::Fri;
+const EDITION_HOUR: u32 = 7;
+const TICK: std::time::Duration = std::time::Duration::from_secs(60);
+const BATCH_SIZE: i64 = 20;
+// Longer digests are cut short; the site has the rest
+const MAX_ITEMS: usize = 20;
+const EXCERPT_MAX_CHARS: usize = 200;
+/// Another confirmation email is only sent to an address once this has passed.
+pub const CONFIRMATION_RESEND_MINUTES: i64 = 10;
+// Subscriptions nobody confirms are dropped after this long
+const UNCONFIRMED_TTL_DAYS: i64 = 7;
+const SITE_NAME: &str = "Lancaster Local";
+
+/// Email copy in one of the languages the interface ships in.
+struct Strings {
+    dir: &'static str,
+    subject: &'static str,
+    intro: &'static str,
+    posts_heading: &'static str,
+    events_heading: &'static str,
+    free: &'static str,
+    manage: &'static str,
+    unsubscribe: &'static str,
+    footer: &'static str,
+    confirm_subject: &'static str,
+    confirm_intro: &'static str,
+    confirm_button: &'static str,
+    confirm_ignore: &'static str,
+    subscribed_subject: &'static str,
+    subscribed_intro: &'static str,
+    weekdays: [&'static str; 7], // Monday first
+    date_format: &'static str,   // Day and month
+}
+
+const EN: Strings = Strings {
+    dir: "ltr",
+    subject: "This week in Lancaster",
+    intro: "The latest community news and what's on in the week ahead.",
+    posts_heading: "News this week",
+    events_heading: "Coming up",
+    free: "Free",
+    manage: "Manage your subscription",
+    unsubscribe: "Unsubscribe",
+    footer: "You are receiving this because you subscribed to the Lancaster Local newsletter.",
+    confirm_subject: "Confirm your Lancaster Local newsletter subscription",
+    confirm_intro: "Please confirm that you want to receive the Lancaster Local weekly newsletter at this address.",
+    confirm_button: "Confirm subscription",
+    confirm_ignore: "If you did not ask for this, ignore this email and you will not hear from us again.",
+    subscribed_subject: "Your Lancaster Local newsletter subscription",
+    subscribed_intro: "This address is already subscribed to the weekly newsletter. Use the link below to change your preferences or unsubscribe.",
+    weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+    date_format: "%m/%d",
+};
+
+const ES: Strings = Strings {
+    dir: "ltr",
+    subject: "Esta semana en Lancaster",
+    intro: "Las últimas noticias de la comunidad y lo que pasa en la próxima semana.",
+    posts_heading: "Noticias de la semana",
+    events_heading: "Próximamente",
+    free: "Gratis",
+    manage: "Gestionar tu suscripción",
+    unsubscribe: "Darse de baja",
+    footer: "Recibes este correo porque te suscribiste al boletín de Lancaster Local.",
+    confirm_subject: "Confirma tu suscripción al boletín de Lancaster Local",
+    confirm_intro: "Confirma que quieres recibir el boletín semanal de Lancaster Local en esta dirección.",
+    confirm_button: "Confirmar suscripción",
+    confirm_ignore: "Si no lo has solicitado, ignora este correo y no volverás a saber de nosotros.",
+    subscribed_subject: "Tu suscripción al boletín de Lancaster Local",
+    subscribed_intro: "Esta dirección ya está suscrita al boletín semanal. Usa el enlace de abajo para cambiar tus preferencias o darte de baja.",
+    weekdays: ["lunes", "martes", "miércoles", "jueves", "viernes", "sábado", "domingo"],
+    date_format: "%d/%m",
+};
+
+const FR: Strings = Strings {
+    dir: "ltr",
+    subject: "Cette semaine à Lancaster",
+    intro: "Les dernières nouvelles de la communauté et le programme de la semaine à venir.",
+    posts_heading: "Les nouvelles de la semaine",
+    events_heading: "À venir",
+    free: "Gratuit",
+    manage: "Gérer votre abonnement",
+    unsubscribe: "Se désabonner",
+    footer: "Vous recevez ce message car vous êtes abonné(e) à la lettre d'information de Lancaster Local.",
+    confirm_subject: "Confirmez votre abonnement à la lettre d'information de Lancaster Local",
+    confirm_intro: "Merci de confirmer que vous souhaitez recevoir la lettre hebdomadaire de Lancaster Local à cette adresse.",
+    confirm_button: "Confirmer l'abonnement",
+    confirm_ignore: "Si vous n'avez rien demandé, ignorez ce message : vous n'aurez plus de nouvelles de nous.",
+    subscribed_subject: "Votre abonnement à la lettre d'information de Lancaster Local",
+    subscribed_intro: "Cette adresse est déjà abonnée à la lettre hebdomadaire. Utilisez le lien ci-dessous pour modifier vos préférences ou vous désabonner.",
+    weekdays: ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+    date_format: "%d/%m",
+};
+
+const DE: Strings = Strings {
+    dir: "ltr",
+    subject: "Diese Woche in Lancaster",
+    intro: "Die neuesten Nachrichten aus der Gemeinschaft und was in der kommenden Woche los ist.",
+    posts_heading: "Nachrichten der Woche",
+    events_heading: "Demnächst",
+    free: "Kostenlos",
+    manage: "Abonnement verwalten",
+    unsubscribe: "Abbestellen",
+    footer: "Sie erhalten diese E-Mail, weil Sie den Newsletter von Lancaster Local abonniert haben.",
+    confirm_subject: "Bestätigen Sie Ihr Abonnement des Lancaster-Local-Newsletters",
+    confirm_intro: "Bitte bestätigen Sie, dass Sie den wöchentlichen Newsletter von Lancaster Local an diese Adresse erhalten möchten.",
+    confirm_button: "Abonnement bestätigen",
+    confirm_ignore: "Wenn Sie das nicht angefordert haben, ignorieren Sie diese E-Mail. Sie hören dann nicht mehr von uns.",
+    subscribed_subject: "Ihr Abonnement des Lancaster-Local-Newsletters",
+    subscribed_intro: "Diese Adresse hat den wöchentlichen Newsletter bereits abonniert. Über den Link unten können Sie Ihre Einstellungen ändern oder den Newsletter abbestellen.",
+    weekdays: ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"],
+    date_format: "%d.%m.",
+};
+
+const ZH: Strings = Strings {
+    dir: "ltr",
+    subject: "本周兰开斯特",
+    intro: "社区最新消息和下周活动。",
+    posts_heading: "本周新闻",
+    events_heading: "即将举行",
+    free: "免费",
+    manage: "管理订阅",
+    unsubscribe: "退订",
+    footer: "您收到此邮件是因为您订阅了 Lancaster Local 简报。",
+    confirm_subject: "请确认订阅 Lancaster Local 简报",
+    confirm_intro: "请确认您希望通过此地址接收 Lancaster Local 每周简报。",
+    confirm_button: "确认订阅",
+    confirm_ignore: "如果这不是您本人的操作，请忽略此邮件，我们不会再联系您。",
+    subscribed_subject: "您的 Lancaster Local 简报订阅",
+    subscribed_intro: "此地址已订阅每周简报。请使用下方链接更改偏好设置或退订。",
+    weekdays: ["星期一", "星期二", "星期三", "星期四", "星期五", "星期六", "星期日"],
+    date_format: "%m月%d日",
+};
+
+const AR: Strings = Strings {
+    dir: "rtl",
+    subject: "هذا الأسبوع في لانكستر",
+    intro: "آخر أخبار المجتمع وما يجري في الأسبوع القادم.",
+    posts_heading: "أخبار هذا الأسبوع",
+    events_heading: "قريبًا",
+    free: "مجاني",
+    manage: "إدارة اشتراكك",
+    unsubscribe: "إلغاء الاشتراك",
+    footer: "تصلك هذه الرسالة لأنك اشتركت في النشرة الإخبارية لـ Lancaster Local.",
+    confirm_subject: "أكّد اشتراكك في النشرة الإخبارية لـ Lancaster Local",
+    confirm_intro: "يرجى تأكيد رغبتك في تلقي النشرة الأسبوعية لـ Lancaster Local على هذا العنوان.",
+    confirm_button: "تأكيد الاشتراك",
+    confirm_ignore: "إذا لم تطلب ذلك، فتجاهل هذه الرسالة ولن نراسلك مرة أخرى.",
+    subscribed_subject: "اشتراكك في النشرة الإخبارية لـ Lancaster Local",
+    subscribed_intro: "هذا العنوان مشترك بالفعل في النشرة الأسبوعية. استخدم الرابط أدناه لتغيير تفضيلاتك أو إلغاء الاشتراك.",
+    weekdays: ["الاثنين", "الثلاثاء", "الأربعاء", "الخميس", "الجمعة", "السبت", "الأحد"],
+    date_format: "%d/%m",
+};
+
+// The copy is in the interface languages; other languages get English around their content
+fn strings(lang: &str) -> (&'static str, &'static Strings) {
+    match lang {
+        "es" => ("es", &ES),
+        "fr" => ("fr", &FR),
+        "de" => ("de", &DE),
+        "zh" => ("zh", &ZH),
+        "ar" => ("ar", &AR),
+        _ => ("en", &EN),
+    }
+}
+
+pub fn manage_url(public_url: &str, token: &str) -> String {
+    format!("{}/newsletter?token={}", public_url, token)
+}
+
+fn confirm_url(public_url: &str, token: &str) -> String {
+    format!("{}/newsletter/confirm?token={}", public_url, token)
+}
+
+// Opens the subscription page ready to unsubscribe; a plain GET never unsubscribes, as
+// mail scanners follow links
+fn unsubscribe_page_url(public_url: &str, token: &str) -> String {
+    format!("{}/newsletter?token={}&unsubscribe=1", public_url, token)
+}
+
+// For mail clients' own unsubscribe button, which POSTs (RFC 8058)
+fn one_click_unsubscribe_url(public_url: &str, token: &str) -> String {
+    format!("{}/api/newsletter/unsubscribe?token={}", public_url, token)
+}
+
+/// The start of the newest edition at or before `now`.
+pub fn latest_edition(now: DateTime<Utc>) -> DateTime<Utc> {
+    let today = now.date_naive();
+    let days_back = (7 + today.weekday().num_days_from_monday() - EDITION_WEEKDAY.num_days_from_monday()) % 7;
+    let edition = (today - Duration::days(days_back as i64))
+        .and_hms_opt(EDITION_HOUR, 0, 0)
+        .expect("EDITION_HOUR is a valid hour")
+        .and_utc();
+
+    if edition > now {
+        edition - Duration::days(7)
+    } else {
+        edition
+    }
+}
+
+/// What one edition holds for a language and set of categories.
+#[derive(Debug, Clone, Default)]
+pub struct Digest {
+    pub posts: Vec<PostWithTranslation>,
+    pub events: Vec<EventWithTranslation>,
+}
+
+impl Digest {
+    fn is_empty_for(&self, topics: &[String]) -> bool {
+        let wants = |topic: NewsletterTopic| topics.iter().any(|t| t == topic.as_str());
+        let has_posts = wants(NewsletterTopic::Posts) && !self.posts.is_empty();
+        let has_events = wants(NewsletterTopic::Events) && !self.events.is_empty();
+        !has_posts && !has_events
+    }
+}
+
+/// Collects an edition: posts published in the week before the edition, and the week's
+/// events from the edition day on, as the site lists them.
+pub async fn build_digest(
+    state: &SharedState,
+    lang: &str,
+    categories: &[String],
+    edition: DateTime<Utc>,
+) -> Result<Digest, ApiError> {
+    let week_start = edition - Duration::days(7);
+    let posts = fetch_posts_published_between(state, lang, week_start, edition, MAX_ITEMS as i64).await?;
+
+    // An edition sent late leaves out the days already gone
+    let from = edition.date_naive().max(Utc::now().date_naive());
+    let to = edition.date_naive() + Duration::days(6);
+    let mut events: Vec<_> = fetch_events_between(state, lang, from, to, categories)
+        .await?
+        .into_iter()
+        .filter(|e| !e.is_cancelled)
+        .collect();
+    events.truncate(MAX_ITEMS);
+
+    Ok(Digest { posts, events })
+}
+
+/// A rendered edition for one subscriber, with the links that manage their subscription.
+pub fn digest_email(
+    public_url: &str,
+    subscriber: &NewsletterSubscriber,
+    digest: &Digest,
+    edition: DateTime<Utc>,
+) -> Email {
+    let lang = subscriber.language_code.as_str();
+    let (copy_lang, s) = strings(lang);
+    let align = text_align(s.dir);
+    let wants = |topic: NewsletterTopic| subscriber.topics.iter().any(|t| t == topic.as_str());
+    let subject = format!("{} · {}", s.subject, format_date(s, edition.date_naive()));
+
+    let mut html = String::new();
+    let mut text = format!("{}\n{}\n", SITE_NAME, s.intro);
+
+    if wants(NewsletterTopic::Posts) && !digest.posts.is_empty() {
+        html.push_str(&section_heading(s, s.posts_heading));
+        text.push_str(&format!("\n== {} ==\n", s.posts_heading));
+
+        for post in &digest.posts {
+            let url = page_url(public_url, lang, ContentKind::Post, post.slug.as_deref(), post.id);
+            let excerpt = post.content.as_deref().map(excerpt).filter(|e| !e.is_empty());
+            let item_lang = content_language(lang, &post.original_language, post.is_translated);
+
+            html.push_str(&item_html(item_lang, &post.text_direction, &post.title, &url, None, excerpt.as_deref()));
+            text.push_str(&format!("\n{}\n", post.title));
+            if let Some(excerpt) = &excerpt {
+                text.push_str(&format!("{}\n", excerpt));
+            }
+            text.push_str(&format!("{}\n", url));
+        }
+    }
+
+    if wants(NewsletterTopic::Events) && !digest.events.is_empty() {
+        html.push_str(&section_heading(s, s.events_heading));
+        text.push_str(&format!("\n== {} ==\n", s.events_heading));
+
+        for event in &digest.events {
+            let url = page_url(public_url, lang, ContentKind::Event, event.slug.as_deref(), event.id);
+            let item_lang = content_language(lang, &event.original_language, event.is_translated);
+
+            let mut when = vec![format!(
+                "{} {}",
+                s.weekdays[event.event_date.weekday().num_days_from_monday() as usize],
+                format_date(s, event.event_date)
+            )];
+            if let Some(time) = event.event_time {
+                when.push(time.format("%H:%M").to_string());
+            }
+            if let Some(place) = event.venue_name.as_deref().or(event.location.as_deref()) {
+                when.push(place.to_string());
+            }
+            if event.is_free {
+                when.push(s.free.to_string());
+            }
+            let when = when.join(" · ");
+            let excerpt = event.description.as_deref().map(excerpt).filter(|e| !e.is_empty());
+
+            html.push_str(&item_html(
+                item_lang,
+                &event.text_direction,
+                &event.title,
+                &url,
+                Some((copy_lang, s.dir, &when)),
+                excerpt.as_deref(),
+            ));
+            text.push_str(&format!("\n{}\n{}\n", event.title, when));
+            if let Some(excerpt) = &excerpt {
+                text.push_str(&format!("{}\n", excerpt));
+            }
+            text.push_str(&format!("{}\n", url));
+        }
+    }
+
+    let manage = manage_url(public_url, &subscriber.token);
+    let unsubscribe = unsubscribe_page_url(public_url, &subscriber.token);
+    let footer = format!(
+        r#"<tr><td dir="{dir}" style="padding:16px 24px 24px;border-top:1px solid #e5e7eb;font-size:12px;line-height:18px;color:#6b7280;text-align:{align};">{}<br><a href="{}" style="color:#4b5563;">{}</a> · <a href="{}" style="color:#4b5563;">{}</a></td></tr>"#,
+        escape_html(s.footer),
+        escape_html(&manage),
+        escape_html(s.manage),
+        escape_html(&unsubscribe),
+        escape_html(s.unsubscribe),
+        dir = s.dir,
+    );
+    text.push_str(&format!(
+        "\n--\n{}\n{}: {}\n{}: {}\n",
+        s.footer, s.manage, manage, s.unsubscribe, unsubscribe
+    ));
+
+    Email {
+        to: subscriber.email.clone(),
+        html: layout(copy_lang, s, &subject, &format!("{}{}{}", header_html(s, s.intro), html, footer)),
+        text,
+        subject,
+        list_unsubscribe: Some(one_click_unsubscribe_url(public_url, &subscriber.token)),
+    }
+}
+
+/// Asks the owner of a new subscription to confirm it (double opt-in).
+pub fn confirmation_email(public_url: &str, to: &str, lang: &str, token: &str) -> Email {
+    let (copy_lang, s) = strings(lang);
+    let url = confirm_url(public_url, token);
+    let body = format!(
+        "{}{}{}",
+        header_html(s, s.confirm_intro),
+        button_html(s, &url, s.confirm_button),
+        note_html(s, s.confirm_ignore)
+    );
+
+    Email {
+        to: to.to_string(),
+        subject: s.confirm_subject.to_string(),
+        text: format!("{}\n\n{}\n\n{}\n{}\n\n{}\n", SITE_NAME, s.confirm_intro, s.confirm_button, url, s.confirm_ignore),
+        html: layout(copy_lang, s, s.confirm_subject, &body),
+        list_unsubscribe: None,
+    }
+}
+
+/// Sent instead of a confirmation when the address is already subscribed, so a lost
+/// link can always be recovered by subscribing again.
+pub fn subscribed_email(public_url: &str, to: &str, lang: &str, token: &str) -> Email {
+    let (copy_lang, s) = strings(lang);
+    let url = manage_url(public_url, token);
+    let body = format!("{}{}", header_html(s, s.subscribed_intro), button_html(s, &url, s.manage));
+
+    Email {
+        to: to.to_string(),
+        subject: s.subscribed_subject.to_string(),
+        text: format!("{}\n\n{}\n\n{}\n{}\n", SITE_NAME, s.subscribed_intro, s.manage, url),
+        html: layout(copy_lang, s, s.subscribed_subject, &body),
+        list_unsubscribe: Some(one_click_unsubscribe_url(public_url, token)),
+    }
+}
+
+// Email clients ignore stylesheets and much of CSS, so this is tables and inline styles.
+// Direction is set on the document and again on each block, since some clients drop
+// the html element.
+fn layout(copy_lang: &str, s: &Strings, title: &str, rows: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{copy_lang}" dir="{dir}">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+</head>
+<body dir="{dir}" style="margin:0;padding:0;background:#f3f4f6;">
+<table role="presentation" width="100%" cellpadding="0" cellspacing="0" style="background:#f3f4f6;">
+<tr><td align="center" style="padding:24px 12px;">
+<table role="presentation" width="100%" cellpadding="0" cellspacing="0" dir="{dir}" style="max-width:600px;background:#ffffff;border-radius:8px;font-family:Arial,Helvetica,sans-serif;color:#111827;">
+{rows}
+</table>
+</td></tr>
+</table>
+</body>
+</html>
+"#,
+        dir = s.dir,
+        title = escape_html(title),
+    )
+}
+
+fn header_html(s: &Strings, intro: &str) -> String {
+    format!(
+        r#"<tr><td dir="{dir}" style="padding:24px 24px 8px;text-align:{align};"><h1 style="margin:0 0 8px;font-size:22px;line-height:28px;">{SITE_NAME}</h1><p style="margin:0;font-size:15px;line-height:22px;color:#4b5563;">{}</p></td></tr>"#,
+        escape_html(intro),
+        dir = s.dir,
+        align = text_align(s.dir),
+    )
+}
+
+fn section_heading(s: &Strings, heading: &str) -> String {
+    format!(
+        r#"<tr><td dir="{dir}" style="padding:24px 24px 4px;text-align:{align};"><h2 style="margin:0;font-size:18px;line-height:24px;border-bottom:2px solid #2563eb;padding-bottom:4px;">{}</h2></td></tr>"#,
+        escape_html(heading),
+        dir = s.dir,
+        align = text_align(s.dir),
+    )
+}
+
+fn button_html(s: &Strings, url: &str, label: &str) -> String {
+    format!(
+        r#"<tr><td dir="{dir}" style="padding:16px 24px;text-align:{align};"><a href="{}" style="display:inline-block;padding:10px 18px;background:#2563eb;color:#ffffff;border-radius:6px;font-weight:bold;text-decoration:none;">{}</a></td></tr>"#,
+        escape_html(url),
+        escape_html(label),
+        dir = s.dir,
+        align = text_align(s.dir),
+    )
+}
+
+fn note_html(s: &Strings, note: &str) -> String {
+    format!(
+        r#"<tr><td dir="{dir}" style="padding:8px 24px 24px;font-size:13px;line-height:19px;color:#6b7280;text-align:{align};">{}</td></tr>"#,
+        escape_html(note),
+        dir = s.dir,
+        align = text_align(s.dir),
+    )
+}
+
+// Each item runs in its own language and direction, which may not be the email's: an
+// untranslated post is shown as written
+fn item_html(
+    item_lang: &str,
+    item_dir: &str,
+    title: &str,
+    url: &str,
+    meta: Option<(&str, &str, &str)>, // Language, direction and text of a line in the email's language
+    excerpt: Option<&str>,
+) -> String {
+    let mut html = format!(
+        r#"<tr><td lang="{}" dir="{item_dir}" style="padding:12px 24px;text-align:{};"><a href="{}" style="font-size:16px;line-height:22px;font-weight:bold;color:#1d4ed8;text-decoration:none;">{}</a>"#,
+        escape_html(item_lang),
+        text_align(item_dir),
+        escape_html(url),
+        escape_html(title),
+    );
+    if let Some((meta_lang, meta_dir, meta)) = meta {
+        html.push_str(&format!(
+            r#"<div lang="{meta_lang}" dir="{meta_dir}" style="margin-top:4px;font-size:13px;line-height:19px;color:#4b5563;text-align:{};">{}</div>"#,
+            text_align(meta_dir),
+            escape_html(meta),
+        ));
+    }
+    if let Some(excerpt) = excerpt {
+        html.push_str(&format!(
+            r#"<p style="margin:4px 0 0;font-size:14px;line-height:20px;color:#374151;">{}</p>"#,
+            escape_html(excerpt)
+        ));
+    }
+    html.push_str("</td></tr>");
+    html
+}
+
+fn text_align(dir: &str) -> &'static str {
+    if dir == "rtl" {
+        "right"
+    } else {
+        "left"
+    }
+}
+
+fn format_date(s: &Strings, date: NaiveDate) -> String {
+    date.format(s.date_format).to_string()
+}
+
+fn excerpt(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= EXCERPT_MAX_CHARS {
+        return text;
+    }
+
+    let truncated: String = text.chars().take(EXCERPT_MAX_CHARS - 1).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+/// Sends each confirmed subscriber the latest edition once, and drops subscriptions
+/// that were never confirmed. Runs for the life of the server.
+pub async fn run(state: SharedState) {
+    let mut ticker = interval(TICK);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        if let Err(e) = send_due(&state).await {
+            tracing::error!("Newsletter run failed: {:?}", e);
+        }
+        if let Err(e) = purge_unconfirmed(&state).await {
+            tracing::error!("Failed to purge unconfirmed subscriptions: {:?}", e);
+        }
+    }
+}
+
+// A subscriber claimed for an edition, with what it replaced in case the claim is handed back
+#[derive(FromRow)]
+struct Claimed {
+    #[sqlx(flatten)]
+    subscriber: NewsletterSubscriber,
+    previous_digest_at: Option<DateTime<Utc>>,
+}
+
+async fn send_due(state: &SharedState) -> Result<()> {
+    let edition = latest_edition(Utc::now());
+    // Subscribers with the same language and categories get the same digest
+    let mut digests: HashMap<(String, Vec<String>), Digest> = HashMap::new();
+
+    loop {
+        // Claiming marks the edition as sent: a failed send isn't retried, so nobody
+        // gets an edition twice
+        let claimed = sqlx::query_as::<_, Claimed>(
+            r#"
+            UPDATE newsletter_subscribers s SET last_digest_at = $1
+            FROM (
+                SELECT id, last_digest_at FROM newsletter_subscribers
+                WHERE confirmed_at < $1 AND (last_digest_at IS NULL OR last_digest_at < $1)
+                ORDER BY id
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            ) old
+            WHERE s.id = old.id
+            RETURNING s.*, old.last_digest_at AS previous_digest_at
+            "#,
+        )
+        .bind(edition)
+        .bind(BATCH_SIZE)
+        .fetch_all(&state.pool)
+        .await?;
+
+        // Every digest the batch needs is built before anything is sent, and if one
+        // can't be the batch is handed back for the next run
+        for Claimed { subscriber, .. } in &claimed {
+            let key = (subscriber.language_code.clone(), subscriber.categories.clone());
+            if digests.contains_key(&key) {
+                continue;
+            }
+            match build_digest(state, &subscriber.language_code, &subscriber.categories, edition).await {
+                Ok(digest) => {
+                    digests.insert(key, digest);
+                }
+                Err(e) => {
+                    release(state, edition, &claimed).await?;
+                    return Err(e.into());
+                }
+            }
+        }
+
+        for Claimed { subscriber, .. } in &claimed {
+            let digest = &digests[&(subscriber.language_code.clone(), subscriber.categories.clone())];
+            if digest.is_empty_for(&subscriber.topics) {
+                continue;
+            }
+
+            let email = digest_email(&state.public_url, subscriber, digest, edition);
+            match state.mailer.send(email).await {
+                Ok(()) => tracing::info!("Sent newsletter {} to subscriber {}", edition.date_naive(), subscriber.id),
+                Err(e) => tracing::warn!("Failed to send newsletter to subscriber {}: {:?}", subscriber.id, e),
+            }
+        }
+
+        if (claimed.len() as i64) < BATCH_SIZE {
+            return Ok(());
+        }
+    }
+}
+
+// Puts back what claiming an edition replaced, so those subscribers are sent it later
+async fn release(state: &SharedState, edition: DateTime<Utc>, claimed: &[Claimed]) -> Result<()> {
+    let ids: Vec<i32> = claimed.iter().map(|c| c.subscriber.id).collect();
+    let previous: Vec<Option<DateTime<Utc>>> = claimed.iter().map(|c| c.previous_digest_at).collect();
+
+    sqlx::query(
+        r#"
+        UPDATE newsletter_subscribers s SET last_digest_at = r.previous
+        FROM UNNEST($1::int[], $2::timestamptz[]) AS r(id, previous)
+        WHERE s.id = r.id AND s.last_digest_at = $3
+        "#,
+    )
+    .bind(ids)
+    .bind(previous)
+    .bind(edition)
+    .execute(&state.pool)
+    .await?;
+    Ok(())
+}
+
+async fn purge_unconfirmed(state: &SharedState) -> Result<()> {
+    let purged = sqlx::query(
+        "DELETE FROM newsletter_subscribers WHERE confirmed_at IS NULL AND confirmation_sent_at < NOW() - make_interval(days => $1)",
+    )
+    .bind(UNCONFIRMED_TTL_DAYS as i32)
+    .execute(&state.pool)
+    .await?
+    .rows_affected();
+
+    if purged > 0 {
+        tracing::info!("Removed {} unconfirmed newsletter subscriptions", purged);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PostType;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn post(id: i32, title: &str, language: &str, direction: &str, is_translated: bool) -> PostWithTranslation {
+        PostWithTranslation {
+            id,
+            slug: None,
+            author_name: "Reader".to_string(),
+            title: title.to_string(),
+            content: Some(format!("{}  \n\n body", title)),
+            original_title: title.to_string(),
+            original_content: None,
+            link_url: None,
+            image_url: None,
+            thumbnail_url: None,
+            link_preview: None,
+            post_type: PostType::Text,
+            original_language: language.to_string(),
+            original_text_direction: direction.to_string(),
+            text_direction: direction.to_string(),
+            is_translated,
+            pinned: false,
+            emergency: false,
+            created_at: at("2026-10-14T12:00:00Z"),
+        }
+    }
+
+    fn event(title: &str) -> EventWithTranslation {
+        EventWithTranslation {
+            id: 1,
+            slug: Some("market".to_string()),
+            organizer_name: "Market".to_string(),
+            title: title.to_string(),
+            description: None,
+            original_title: title.to_string(),
+            original_description: None,
+            event_date: NaiveDate::from_ymd_opt(2026, 10, 20).unwrap(),
+            event_time: None,
+            location: Some("Penn Square".to_string()),
+            venue_id: None,
+            venue_name: None,
+            venue_address: None,
+            latitude: None,
+            longitude: None,
+            distance_km: None,
+            category: None,
+            category_name: None,
+            category_color: None,
+            is_free: true,
+            ticket_url: None,
+            image_url: None,
+            thumbnail_url: None,
+            original_language: "ar".to_string(),
+            original_text_direction: "rtl".to_string(),
+            text_direction: "rtl".to_string(),
+            is_translated: false,
+            recurrence_rule: None,
+            recurrence_exdates: Vec::new(),
+            is_cancelled: false,
+            created_at: at("2026-10-14T12:00:00Z"),
+        }
+    }
+
+    fn subscriber(lang: &str) -> NewsletterSubscriber {
+        let now = at("2026-10-01T00:00:00Z");
+        NewsletterSubscriber {
+            id: 1,
+            email: "reader@example.org".to_string(),
+            language_code: lang.to_string(),
+            topics: vec!["posts".to_string(), "events".to_string()],
+            categories: Vec::new(),
+            token: "token".to_string(),
+            confirmed_at: Some(now),
+            confirmation_sent_at: now,
+            last_digest_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn latest_edition_is_the_last_friday_morning() {
+        let friday = at("2026-10-16T07:00:00Z");
+        assert_eq!(latest_edition(friday), friday);
+        assert_eq!(latest_edition(at("2026-10-16T06:59:59Z")), at("2026-10-09T07:00:00Z"));
+        assert_eq!(latest_edition(at("2026-10-18T23:00:00Z")), friday);
+        assert_eq!(latest_edition(at("2026-10-22T23:59:59Z")), friday);
+        assert_eq!(latest_edition(at("2026-10-23T07:00:00Z")), at("2026-10-23T07:00:00Z"));
+    }
+
+    #[test]
+    fn excerpt_collapses_whitespace() {
+        assert_eq!(excerpt("  Community\n\ngarden   opens "), "Community garden opens");
+        assert_eq!(excerpt(""), "");
+    }
+
+    #[test]
+    fn excerpt_truncates_by_characters() {
+        let exact = "ب".repeat(EXCERPT_MAX_CHARS);
+        assert_eq!(excerpt(&exact), exact);
+
+        let long = "ب".repeat(EXCERPT_MAX_CHARS + 1);
+        assert_eq!(excerpt(&long), format!("{}…", "ب".repeat(EXCERPT_MAX_CHARS - 1)));
+
+        // No space is left dangling before the ellipsis
+        let at_space = format!("{} {}", "a".repeat(EXCERPT_MAX_CHARS - 2), "b".repeat(10));
+        assert_eq!(excerpt(&at_space), format!("{}…", "a".repeat(EXCERPT_MAX_CHARS - 2)));
+    }
+
+    #[test]
+    fn digest_email_lays_out_arabic_right_to_left() {
+        let digest = Digest {
+            posts: vec![
+                post(1, "افتتاح حديقة المجتمع", "ar", "rtl", true),
+                post(2, "Library hours", "en", "ltr", false),
+            ],
+            events: vec![event("سوق المزارعين")],
+        };
+        let email = digest_email("https://example.org", &subscriber("ar"), &digest, at("2026-10-16T07:00:00Z"));
+
+        assert!(email.html.contains(r#"<html lang="ar" dir="rtl">"#));
+        assert!(email.html.contains(r#"<body dir="rtl""#));
+        assert!(email.subject.ends_with("16/10"));
+
+        // Each item is marked up in its own language and direction
+        assert!(email.html.contains(r#"<td lang="ar" dir="rtl" style="padding:12px 24px;text-align:right;">"#));
+        assert!(email.html.contains(r#"<td lang="en" dir="ltr" style="padding:12px 24px;text-align:left;">"#));
+        assert!(email.html.contains("Library hours"));
+
+        // The event's date line is in the subscriber's language
+        assert!(email.html.contains(r#"<div lang="ar" dir="rtl""#));
+        assert!(email.text.contains("الثلاثاء 20/10 · Penn Square · مجاني"));
+        assert!(email.text.contains(AR.posts_heading));
+        assert_eq!(email.list_unsubscribe.as_deref(), Some("https://example.org/api/newsletter/unsubscribe?token=token"));
+    }
+
+    #[test]
+    fn digest_email_leaves_out_topics_not_subscribed_to() {
+        let digest = Digest {
+            posts: vec![post(1, "Library hours", "en", "ltr", false)],
+            events: vec![event("سوق المزارعين")],
+        };
+        let mut subscriber = subscriber("en");
+        subscriber.topics = vec!["posts".to_string()];
+        let email = digest_email("https://example.org", &subscriber, &digest, at("2026-10-16T07:00:00Z"));
+
+        assert!(email.html.contains(r#"<html lang="en" dir="ltr">"#));
+        assert!(email.text.contains(EN.posts_heading));
+        assert!(!email.text.contains(EN.events_heading));
+        assert!(email.subject.ends_with("10/16"));
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_util::StreamExt;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+// Every backend instance publishes to and relays from this one channel
+const REDIS_CHANNEL: &str = "lancaster:notices";
+// Streams this far behind are told they lagged and carry on from the latest notice
+const CHANNEL_CAPACITY: usize = 256;
+const MAX_RELAY_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ContentKind {
+    Post,
+    Event,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentChange {
+    /// The item became publicly visible
+    Published,
+    /// A visible item changed, e.g. a translation landed or it was pinned
+    Updated,
+    /// The item is no longer publicly visible: deleted, hidden, archived or expired
+    Deleted,
+}
+
+/// What open streams are told about. Notices only carry ids; each stream looks the item
+/// up in its own language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "notice", rename_all = "snake_case")]
+pub enum Notice {
+    Content { change: ContentChange, kind: ContentKind, id: i32 },
+    AlertPublished { id: i32 },
+    AlertEnded { id: i32 },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    origin: Uuid,
+    #[serde(flatten)]
+    notice: Notice,
+}
+
+/// Fans notices out to the streams open on every instance. Local streams are always told
+/// directly; other instances hear about it over Redis pub/sub, so a Redis outage only
+/// costs cross-instance delivery.
+#[derive(Clone)]
+pub struct Notifier {
+    redis_client: redis::Client,
+    local: broadcast::Sender<Notice>,
+    origin: Uuid,
+}
+
+impl Notifier {
+    pub fn new(redis_client: redis::Client) -> Self {
+        Self {
+            redis_client,
+            local: broadcast::channel(CHANNEL_CAPACITY).0,
+            origin: Uuid::new_v4(),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Notice> {
+        self.local.subscribe()
+    }
+
+    pub async fn publish(&self, notice: Notice) {
+        // Nobody listening is fine
+        let _ = self.local.send(notice);
+
+        let payload = match serde_json::to_string(&Envelope { origin: self.origin, notice }) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to encode notice {:?}: {:?}", notice, e);
+                return;
+            }
+        };
+        let result: redis::RedisResult<()> = async {
+            let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+            conn.publish(REDIS_CHANNEL, payload).await
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Redis PUBLISH {:?} failed: {:?}", notice, e);
+        }
+    }
+
+    pub async fn content(&self, change: ContentChange, kind: ContentKind, id: i32) {
+        self.publish(Notice::Content { change, kind, id }).await;
+    }
+
+    /// Relays notices published by other instances to this one's streams, reconnecting
+    /// with backoff whenever Redis goes away. Runs for the life of the server.
+    pub async fn relay(self) {
+        let mut backoff = Duration::from_secs(1);
+
+        loop {
+            match self.relay_until_disconnected(&mut backoff).await {
+                Ok(()) => tracing::warn!("Redis notice subscription closed"),
+                Err(e) => tracing::warn!("Redis notice subscription failed: {:?}", e),
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_RELAY_BACKOFF);
+        }
+    }
+
+    async fn relay_until_disconnected(&self, backoff: &mut Duration) -> redis::RedisResult<()> {
+        let mut pubsub = self.redis_client.get_async_connection().await?.into_pubsub();
+        pubsub.subscribe(REDIS_CHANNEL).await?;
+        *backoff = Duration::from_secs(1);
+        tracing::info!("Relaying notices from Redis channel {}", REDIS_CHANNEL);
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let payload: String = match message.get_payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    tracing::warn!("Unreadable notice payload: {:?}", e);
+                    continue;
+                }
+            };
+            match serde_json::from_str::<Envelope>(&payload) {
+                // Our own notices went straight to local streams
+                Ok(envelope) if envelope.origin == self.origin => {}
+                Ok(envelope) => {
+                    let _ = self.local.send(envelope.notice);
+                }
+                Err(e) => tracing::warn!("Ignoring malformed notice {:?}: {:?}", payload, e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A content notice with the item as streams in one language show it.
+#[derive(Debug, Clone)]
+pub struct LocalizedNotice {
+    pub change: ContentChange,
+    pub kind: ContentKind,
+    pub id: i32,
+    pub item: Option<serde_json::Value>, // None for deletions
+}
+
+/// One channel of localized content notices for each language with streams open, so a
+/// notice is looked up once per language rather than once per stream.
+#[derive(Clone, Default)]
+pub struct LocalizedChannels(Arc<Mutex<HashMap<String, broadcast::Sender<LocalizedNotice>>>>);
+
+impl LocalizedChannels {
+    /// Subscribes to `lang`'s channel. A new channel's sender is returned too, for the
+    /// caller to feed until `close_if_unused` closes it.
+    pub fn subscribe(
+        &self,
+        lang: &str,
+    ) -> (broadcast::Receiver<LocalizedNotice>, Option<broadcast::Sender<LocalizedNotice>>) {
+        let mut channels = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(sender) = channels.get(lang) {
+            return (sender.subscribe(), None);
+        }
+
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        channels.insert(lang.to_string(), sender.clone());
+        (receiver, Some(sender))
+    }
+
+    /// Closes `lang`'s channel once no stream is subscribed to it. Returns whether it's closed.
+    pub fn close_if_unused(&self, lang: &str) -> bool {
+        let mut channels = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let unused = channels.get(lang).is_none_or(|sender| sender.receiver_count() == 0);
+        if unused {
+            channels.remove(lang);
+        }
+        unused
+    }
+}
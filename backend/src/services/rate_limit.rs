@@ -0,0 +1,196 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// Buckets are kept in Redis so limits hold across restarts and instances. When Redis
+// can't be reached the limiter falls back to per-process memory instead of letting
+// everything through.
+
+// Refills the bucket for the time since it was last touched, then takes one token.
+// Returns {allowed, seconds until a token is available}.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) + tonumber(time[2]) / 1000000
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'updated')
+local tokens = tonumber(bucket[1]) or capacity
+local updated = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - updated) * rate)
+
+local allowed, retry_after = 0, 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    retry_after = math.ceil((1 - tokens) / rate)
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated', tostring(now))
+redis.call('EXPIRE', KEYS[1], math.ceil(capacity / rate) + 1)
+return {allowed, retry_after}
+"#;
+
+// Past this many in-memory entries, stale ones are swept on the next write
+const MAX_MEMORY_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Quota {
+    pub burst: u32, // Requests allowed back to back
+    pub refill_per_second: f64, // Sustained rate once the burst is spent
+}
+
+impl Quota {
+    /// `count` requests per `minutes`, all of which may be used at once.
+    pub const fn per_minutes(count: u32, minutes: u32) -> Self {
+        Quota {
+            burst: count,
+            refill_per_second: count as f64 / (minutes as f64 * 60.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Allowed,
+    Limited { retry_after_seconds: u64 },
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    redis_client: redis::Client,
+    buckets: Arc<Mutex<HashMap<String, (f64, Instant)>>>, // Tokens left and when
+    counters: Arc<Mutex<HashMap<String, (u32, Instant)>>>, // Count and expiry
+}
+
+impl RateLimiter {
+    pub fn new(redis_client: redis::Client) -> Self {
+        RateLimiter {
+            redis_client,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+            counters: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Takes a token from the bucket named `key`.
+    pub async fn check(&self, key: &str, quota: Quota) -> Decision {
+        let result: redis::RedisResult<(u8, u64)> = async {
+            let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+            redis::Script::new(TOKEN_BUCKET_SCRIPT)
+                .key(key)
+                .arg(quota.burst)
+                .arg(quota.refill_per_second)
+                .invoke_async(&mut conn)
+                .await
+        }
+        .await;
+
+        match result {
+            Ok((1, _)) => Decision::Allowed,
+            Ok((_, retry_after_seconds)) => Decision::Limited { retry_after_seconds: retry_after_seconds.max(1) },
+            Err(e) => {
+                tracing::warn!("Redis rate limit check for {} failed, using memory: {:?}", key, e);
+                self.check_in_memory(key, quota)
+            }
+        }
+    }
+
+    fn check_in_memory(&self, key: &str, quota: Quota) -> Decision {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() > MAX_MEMORY_ENTRIES {
+            // A bucket idle long enough to have refilled is the same as no bucket
+            let full_after = Duration::from_secs_f64(quota.burst as f64 / quota.refill_per_second);
+            buckets.retain(|_, (_, updated)| now.duration_since(*updated) < full_after);
+        }
+
+        let (tokens, updated) = buckets.entry(key.to_string()).or_insert((quota.burst as f64, now));
+        *tokens = (*tokens + now.duration_since(*updated).as_secs_f64() * quota.refill_per_second)
+            .min(quota.burst as f64);
+        *updated = now;
+
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            Decision::Allowed
+        } else {
+            let retry_after_seconds = ((1.0 - *tokens) / quota.refill_per_second).ceil() as u64;
+            Decision::Limited { retry_after_seconds: retry_after_seconds.max(1) }
+        }
+    }
+
+    /// Counts an event (such as a failed login) in a window that starts with the
+    /// first one. Returns the count so far and the seconds until the window ends.
+    pub async fn increment(&self, key: &str, window: Duration) -> (u32, u64) {
+        let result: redis::RedisResult<(u32, i64)> = async {
+            let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+            let (count, ttl): (u32, i64) = redis::pipe()
+                .atomic()
+                .cmd("SET").arg(key).arg(0).arg("EX").arg(window.as_secs()).arg("NX").ignore()
+                .cmd("INCR").arg(key)
+                .cmd("TTL").arg(key)
+                .query_async(&mut conn)
+                .await?;
+            Ok((count, ttl))
+        }
+        .await;
+
+        match result {
+            Ok((count, ttl)) => (count, ttl.max(1) as u64),
+            Err(e) => {
+                tracing::warn!("Redis INCR {} failed, using memory: {:?}", key, e);
+                let now = Instant::now();
+                let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+                if counters.len() > MAX_MEMORY_ENTRIES {
+                    counters.retain(|_, (_, expires)| *expires > now);
+                }
+
+                let entry = counters.entry(key.to_string()).or_insert((0, now + window));
+                if entry.1 <= now {
+                    *entry = (0, now + window);
+                }
+                entry.0 += 1;
+                (entry.0, entry.1.duration_since(now).as_secs().max(1))
+            }
+        }
+    }
+
+    /// The current count for `key` and the seconds left in its window, if one is open.
+    pub async fn count(&self, key: &str) -> Option<(u32, u64)> {
+        let result: redis::RedisResult<(Option<u32>, i64)> = async {
+            let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+            redis::pipe().get(key).ttl(key).query_async(&mut conn).await
+        }
+        .await;
+
+        match result {
+            Ok((Some(count), ttl)) if ttl > 0 => Some((count, ttl as u64)),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!("Redis GET {} failed, using memory: {:?}", key, e);
+                let now = Instant::now();
+                let counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+                counters
+                    .get(key)
+                    .filter(|(_, expires)| *expires > now)
+                    .map(|(count, expires)| (*count, expires.duration_since(now).as_secs().max(1)))
+            }
+        }
+    }
+
+    pub async fn reset(&self, key: &str) {
+        self.counters.lock().unwrap_or_else(|e| e.into_inner()).remove(key);
+
+        let result: redis::RedisResult<()> = async {
+            let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
+            redis::cmd("DEL").arg(key).query_async(&mut conn).await
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Redis DEL {} failed: {:?}", key, e);
+        }
+    }
+}
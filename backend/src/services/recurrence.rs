@@ -0,0 +1,464 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use std::collections::HashMap;
+
+use crate::models::{EventOccurrenceOverride, EventWithTranslation};
+
+// Guards against pathological rules (e.g. INTERVAL=1 with no UNTIL/COUNT and a far-away window)
+const MAX_PERIODS: u32 = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// A BYDAY entry such as `MO`, `1MO` (first Monday) or `-1FR` (last Friday).
+/// Ordinals are only meaningful for monthly rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+/// Subset of RFC 5545 RRULE: FREQ (DAILY/WEEKLY/MONTHLY), INTERVAL, BYDAY, COUNT and UNTIL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDate>,
+}
+
+impl RecurrenceRule {
+    pub fn parse(rule: &str) -> Result<Self> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut count = None;
+        let mut until = None;
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Malformed RRULE part: {}", part))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        other => bail!("Unsupported FREQ: {}", other),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|i| *i > 0)
+                        .ok_or_else(|| anyhow!("Invalid INTERVAL: {}", value))?;
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_by_day(day)?);
+                    }
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|c| *c > 0)
+                            .ok_or_else(|| anyhow!("Invalid COUNT: {}", value))?,
+                    );
+                }
+                "UNTIL" => until = Some(parse_rrule_date(value)?),
+                "WKST" => {}
+                other => bail!("Unsupported RRULE part: {}", other),
+            }
+        }
+
+        let freq = freq.ok_or_else(|| anyhow!("RRULE is missing FREQ"))?;
+
+        if count.is_some() && until.is_some() {
+            bail!("RRULE cannot have both COUNT and UNTIL");
+        }
+
+        if freq != Frequency::Monthly && by_day.iter().any(|d| d.ordinal.is_some()) {
+            bail!("BYDAY ordinals are only supported for MONTHLY rules");
+        }
+
+        Ok(Self {
+            freq,
+            interval,
+            by_day,
+            count,
+            until,
+        })
+    }
+
+    /// Expands the series that starts on `start` into the occurrence dates falling
+    /// inside `[window_start, window_end]`, with `exdates` removed.
+    pub fn occurrences(
+        &self,
+        start: NaiveDate,
+        exdates: &[NaiveDate],
+        window_start: NaiveDate,
+        window_end: NaiveDate,
+    ) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        let mut generated = 0;
+
+        for period in 0..MAX_PERIODS {
+            let candidates = match self.period_candidates(start, period) {
+                Some(c) => c,
+                None => break,
+            };
+
+            for date in candidates {
+                if date < start {
+                    continue;
+                }
+                if self.until.is_some_and(|until| date > until) || date > window_end {
+                    return dates;
+                }
+
+                // COUNT counts generated instances, EXDATEs are removed afterwards (RFC 5545 3.8.5.1)
+                generated += 1;
+                if date >= window_start && !exdates.contains(&date) {
+                    dates.push(date);
+                }
+                if self.count.is_some_and(|count| generated >= count) {
+                    return dates;
+                }
+            }
+        }
+
+        dates
+    }
+
+    /// Whether `date` is a real (non-excluded) occurrence of the series.
+    pub fn occurs_on(&self, start: NaiveDate, exdates: &[NaiveDate], date: NaiveDate) -> bool {
+        !self.occurrences(start, exdates, date, date).is_empty()
+    }
+
+    /// Whether the series ends, i.e. has an UNTIL or a COUNT.
+    pub fn is_finite(&self) -> bool {
+        self.until.is_some() || self.count.is_some()
+    }
+
+    /// The final non-excluded occurrence of a finite series; `None` for endless series or
+    /// when every occurrence is excluded.
+    pub fn last_occurrence(&self, start: NaiveDate, exdates: &[NaiveDate]) -> Option<NaiveDate> {
+        if !self.is_finite() {
+            return None;
+        }
+        self.occurrences(start, exdates, start, self.until.unwrap_or(NaiveDate::MAX))
+            .last()
+            .copied()
+    }
+
+    // Candidate dates for the n-th period of the series, in ascending order
+    fn period_candidates(&self, start: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)?;
+
+        match self.freq {
+            Frequency::Daily => {
+                let date = start.checked_add_signed(Duration::days(step as i64))?;
+                if self.by_day.is_empty() || self.by_day.iter().any(|d| d.weekday == date.weekday()) {
+                    Some(vec![date])
+                } else {
+                    Some(vec![])
+                }
+            }
+            Frequency::Weekly => {
+                let week_start = start
+                    .checked_sub_signed(Duration::days(start.weekday().num_days_from_monday() as i64))?
+                    .checked_add_signed(Duration::weeks(step as i64))?;
+
+                let mut weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![start.weekday()]
+                } else {
+                    self.by_day.iter().map(|d| d.weekday).collect()
+                };
+                weekdays.sort_by_key(|w| w.num_days_from_monday());
+                weekdays.dedup();
+
+                Some(
+                    weekdays
+                        .into_iter()
+                        .filter_map(|w| {
+                            week_start.checked_add_signed(Duration::days(w.num_days_from_monday() as i64))
+                        })
+                        .collect(),
+                )
+            }
+            Frequency::Monthly => {
+                let month_start = start
+                    .with_day(1)?
+                    .checked_add_months(Months::new(step))?;
+
+                let mut dates: Vec<NaiveDate> = if self.by_day.is_empty() {
+                    // Months without the start's day of month are skipped, as in RFC 5545
+                    month_start.with_day(start.day()).into_iter().collect()
+                } else {
+                    self.by_day
+                        .iter()
+                        .flat_map(|d| weekdays_in_month(month_start, *d))
+                        .collect()
+                };
+                dates.sort();
+                dates.dedup();
+
+                Some(dates)
+            }
+        }
+    }
+}
+
+/// Expands recurring rows into one row per occurrence inside the window, applying
+/// per-occurrence overrides. Non-recurring rows are passed through untouched.
+pub fn expand_events(
+    events: Vec<EventWithTranslation>,
+    overrides: &[EventOccurrenceOverride],
+    window_start: NaiveDate,
+    window_end: NaiveDate,
+) -> Vec<EventWithTranslation> {
+    let overrides: HashMap<(i32, NaiveDate), &EventOccurrenceOverride> = overrides
+        .iter()
+        .map(|o| ((o.event_id, o.occurrence_date), o))
+        .collect();
+
+    let mut expanded = Vec::with_capacity(events.len());
+
+    for event in events {
+        let rule = match event.recurrence_rule.as_deref().map(RecurrenceRule::parse) {
+            None => {
+                expanded.push(event);
+                continue;
+            }
+            Some(Ok(rule)) => rule,
+            Some(Err(e)) => {
+                tracing::warn!("Skipping event {} with invalid recurrence rule: {:?}", event.id, e);
+                continue;
+            }
+        };
+
+        for date in rule.occurrences(
+            event.event_date,
+            &event.recurrence_exdates,
+            window_start,
+            window_end,
+        ) {
+            let mut occurrence = event.clone();
+            occurrence.event_date = date;
+
+            if let Some(o) = overrides.get(&(event.id, date)) {
+                occurrence.is_cancelled = o.cancelled;
+                if o.event_time.is_some() {
+                    occurrence.event_time = o.event_time;
+                }
+                if o.location.is_some() {
+                    occurrence.location = o.location.clone();
+                }
+            }
+
+            expanded.push(occurrence);
+        }
+    }
+
+    expanded.sort_by(|a, b| {
+        a.event_date
+            .cmp(&b.event_date)
+            .then(a.event_time.cmp(&b.event_time))
+    });
+
+    expanded
+}
+
+fn parse_by_day(value: &str) -> Result<ByDay> {
+    let value = value.trim().to_ascii_uppercase();
+    // The weekday is split off by bytes
+    if value.len() < 2 || !value.is_ascii() {
+        bail!("Invalid BYDAY: {}", value);
+    }
+
+    let (ordinal, day) = value.split_at(value.len() - 2);
+    let weekday = match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => bail!("Invalid BYDAY weekday: {}", value),
+    };
+
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        let n = ordinal
+            .trim_start_matches('+')
+            .parse::<i32>()
+            .map_err(|_| anyhow!("Invalid BYDAY ordinal: {}", value))?;
+        if n == 0 || !(-5..=5).contains(&n) {
+            bail!("Invalid BYDAY ordinal: {}", value);
+        }
+        Some(n)
+    };
+
+    Ok(ByDay { ordinal, weekday })
+}
+
+// Accepts the DATE (YYYYMMDD) and DATE-TIME (YYYYMMDDTHHMMSS[Z]) forms; only the date is kept
+fn parse_rrule_date(value: &str) -> Result<NaiveDate> {
+    let date = value.get(..8).ok_or_else(|| anyhow!("Invalid UNTIL: {}", value))?;
+    NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| anyhow!("Invalid UNTIL: {}", value))
+}
+
+fn weekdays_in_month(month_start: NaiveDate, by_day: ByDay) -> Vec<NaiveDate> {
+    let all: Vec<NaiveDate> = month_start
+        .iter_days()
+        .take_while(|d| d.month() == month_start.month())
+        .filter(|d| d.weekday() == by_day.weekday)
+        .collect();
+
+    match by_day.ordinal {
+        None => all,
+        Some(n) if n > 0 => all.get(n as usize - 1).copied().into_iter().collect(),
+        Some(n) => all
+            .len()
+            .checked_sub(n.unsigned_abs() as usize)
+            .and_then(|i| all.get(i).copied())
+            .into_iter()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn dates(list: &[&str]) -> Vec<NaiveDate> {
+        list.iter().map(|d| date(d)).collect()
+    }
+
+    fn expand(rule: &str, start: &str, exdates: &[&str], window_start: &str, window_end: &str) -> Vec<NaiveDate> {
+        RecurrenceRule::parse(rule)
+            .unwrap()
+            .occurrences(date(start), &dates(exdates), date(window_start), date(window_end))
+    }
+
+    #[test]
+    fn parse_rejects_unsupported_and_malformed_rules() {
+        for rule in [
+            "",
+            "INTERVAL=2",
+            "FREQ=YEARLY",
+            "FREQ=WEEKLY;INTERVAL=0",
+            "FREQ=WEEKLY;COUNT=0",
+            "FREQ=WEEKLY;COUNT=3;UNTIL=20260101",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=WEEKLY;BYDAY=X",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=WEEKLY;BYDAY=éX",
+            "FREQ=WEEKLY;BYDAY=MOé",
+            "FREQ=WEEKLY;UNTIL=2026",
+            "FREQ=WEEKLY;UNTIL=2026é0101",
+            "FREQ=WEEKLY;BYMONTH=1",
+            "FREQ=WEEKLY;INTERVAL",
+        ] {
+            assert!(RecurrenceRule::parse(rule).is_err(), "{} should be rejected", rule);
+        }
+    }
+
+    #[test]
+    fn parse_reads_the_supported_parts() {
+        let rule = RecurrenceRule::parse("RRULE:freq=monthly;interval=2;byday=-1fr,+2MO;until=20261231T235959Z").unwrap();
+        assert_eq!(rule.freq, Frequency::Monthly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(
+            rule.by_day,
+            vec![
+                ByDay { ordinal: Some(-1), weekday: Weekday::Fri },
+                ByDay { ordinal: Some(2), weekday: Weekday::Mon },
+            ]
+        );
+        assert_eq!(rule.until, Some(date("2026-12-31")));
+        assert_eq!(rule.count, None);
+    }
+
+    #[test]
+    fn count_includes_excluded_dates() {
+        assert_eq!(
+            expand("FREQ=DAILY;COUNT=5", "2026-01-01", &["2026-01-03"], "2026-01-01", "2026-12-31"),
+            dates(&["2026-01-01", "2026-01-02", "2026-01-04", "2026-01-05"])
+        );
+    }
+
+    #[test]
+    fn until_is_inclusive() {
+        assert_eq!(
+            expand("FREQ=WEEKLY;UNTIL=20260115", "2026-01-01", &[], "2026-01-01", "2026-12-31"),
+            dates(&["2026-01-01", "2026-01-08", "2026-01-15"])
+        );
+        assert_eq!(
+            expand("FREQ=WEEKLY;UNTIL=20260114T235959Z", "2026-01-01", &[], "2026-01-01", "2026-12-31"),
+            dates(&["2026-01-01", "2026-01-08"])
+        );
+    }
+
+    #[test]
+    fn window_cuts_the_series_on_both_ends() {
+        assert_eq!(
+            expand("FREQ=WEEKLY", "2026-01-01", &[], "2026-01-10", "2026-01-22"),
+            dates(&["2026-01-15", "2026-01-22"])
+        );
+    }
+
+    #[test]
+    fn monthly_last_friday() {
+        assert_eq!(
+            expand("FREQ=MONTHLY;BYDAY=-1FR", "2026-01-01", &[], "2026-01-01", "2026-03-31"),
+            dates(&["2026-01-30", "2026-02-27", "2026-03-27"])
+        );
+    }
+
+    #[test]
+    fn monthly_fifth_weekday_skips_months_without_one() {
+        assert_eq!(
+            expand("FREQ=MONTHLY;BYDAY=5FR", "2026-01-01", &[], "2026-01-01", "2026-06-30"),
+            dates(&["2026-01-30", "2026-05-29"])
+        );
+    }
+
+    #[test]
+    fn weekly_days_before_the_start_are_skipped_and_not_counted() {
+        // 2026-01-01 is a Thursday, so that week's Monday and Wednesday come before it
+        assert_eq!(
+            expand("FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=4", "2026-01-01", &[], "2025-12-01", "2026-12-31"),
+            dates(&["2026-01-02", "2026-01-05", "2026-01-07", "2026-01-09"])
+        );
+    }
+
+    #[test]
+    fn last_occurrence_of_a_finite_series() {
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;COUNT=3").unwrap();
+        assert_eq!(rule.last_occurrence(date("2026-01-01"), &[]), Some(date("2026-01-15")));
+        assert_eq!(rule.last_occurrence(date("2026-01-01"), &dates(&["2026-01-15"])), Some(date("2026-01-08")));
+        assert!(RecurrenceRule::parse("FREQ=WEEKLY").unwrap().last_occurrence(date("2026-01-01"), &[]).is_none());
+    }
+}
@@ -0,0 +1,229 @@
+use anyhow::{bail, Context, Result};
+use reqwest::{header, redirect, Url};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_REDIRECTS: usize = 3;
+const USER_AGENT: &str = "lancaster-local/0.1";
+
+pub struct RemoteResponse {
+    pub url: Url, // After redirects
+    pub content_type: Option<String>, // Without parameters, lowercased
+    pub charset: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+/// Fetches a user-supplied URL without letting it reach our own network: every hop
+/// (including redirects) must resolve to public addresses only, and the connection
+/// is pinned to the address that was checked so DNS can't be switched underneath us.
+/// Bodies larger than `max_bytes` are refused.
+pub async fn fetch_public(url: &str, accept: &str, max_bytes: usize) -> Result<RemoteResponse> {
+    fetch(url, accept, max_bytes, false).await
+}
+
+/// Like [`fetch_public`], but keeps the first `max_bytes` of a larger body instead of
+/// refusing it; enough for reading a page's <head>.
+pub async fn fetch_public_prefix(url: &str, accept: &str, max_bytes: usize) -> Result<RemoteResponse> {
+    fetch(url, accept, max_bytes, true).await
+}
+
+async fn fetch(url: &str, accept: &str, max_bytes: usize, truncate: bool) -> Result<RemoteResponse> {
+    let mut url = Url::parse(url)?;
+
+    for _ in 0..=MAX_REDIRECTS {
+        let client = pinned_client(&url).await?;
+        let mut response = client
+            .get(url.clone())
+            .header(header::ACCEPT, accept)
+            .send()
+            .await?;
+
+        if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|l| l.to_str().ok())
+                .context("Redirect without a Location header")?;
+            url = url.join(location)?;
+            continue;
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            bail!("{} returned {}", url, status);
+        }
+        if !truncate && response.content_length().is_some_and(|len| len > max_bytes as u64) {
+            bail!("{} is larger than {} bytes", url, max_bytes);
+        }
+
+        let content_type_header = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|c| c.to_str().ok())
+            .unwrap_or("")
+            .to_lowercase();
+        let mut parts = content_type_header.split(';').map(str::trim);
+        let content_type = parts.next().filter(|c| !c.is_empty()).map(str::to_string);
+        let charset = parts
+            .find_map(|p| p.strip_prefix("charset="))
+            .map(|c| c.trim_matches('"').to_string());
+
+        // Content-Length can be missing or wrong, so count as we read
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if bytes.len() + chunk.len() > max_bytes {
+                if !truncate {
+                    bail!("{} is larger than {} bytes", url, max_bytes);
+                }
+                bytes.extend_from_slice(&chunk[..max_bytes - bytes.len()]);
+                break;
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        return Ok(RemoteResponse { url, content_type, charset, bytes });
+    }
+
+    bail!("Too many redirects")
+}
+
+/// A client whose only route to the URL's host is an address we've checked. It follows
+/// no redirects; callers that allow them must check each hop with a new client.
+pub async fn pinned_client(url: &Url) -> Result<reqwest::Client> {
+    if !matches!(url.scheme(), "http" | "https") {
+        bail!("URL scheme not allowed: {}", url.scheme());
+    }
+    let host = url.host_str().context("URL has no host")?;
+    let port = url.port_or_known_default().context("URL has no port")?;
+
+    let builder = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .redirect(redirect::Policy::none())
+        .no_proxy()
+        .user_agent(USER_AGENT);
+
+    // IP literals need no lookup; brackets are part of IPv6 hosts in URLs
+    if let Ok(ip) = host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+        if !is_public(ip) {
+            bail!("{} is not a public address", ip);
+        }
+        return Ok(builder.build()?);
+    }
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port)).await?.collect();
+    let addr = addrs.first().copied().context("Host did not resolve")?;
+    // One private answer is enough to refuse; it may be what a retry would get
+    if let Some(private) = addrs.iter().find(|a| !is_public(a.ip())) {
+        bail!("{} resolves to non-public address {}", host, private.ip());
+    }
+
+    Ok(builder.resolve(host, addr).build()?)
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        // Both IPv4-mapped (::ffff:a.b.c.d) and the deprecated IPv4-compatible (::a.b.c.d)
+        // forms reach the IPv4 address inside
+        IpAddr::V6(ip) => match ip.to_ipv4() {
+            Some(v4) => is_public_v4(v4),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || a == 0
+        || (a == 100 && (64..128).contains(&b)) // Carrier-grade NAT
+        || (a == 192 && b == 0 && c == 0) // IETF protocol assignments
+        || (a == 198 && (18..20).contains(&b)) // Benchmarking
+        || a >= 240) // Reserved
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || (first & 0xfe00) == 0xfc00 // Unique local
+        || (first & 0xffc0) == 0xfe80 // Link local
+        || (first & 0xffc0) == 0xfec0 // Site local, deprecated but still routed by some networks
+        || first == 0x2001 && ip.segments()[1] == 0x0db8 // Documentation
+        || first == 0x2001 && ip.segments()[1] == 0x0000 // Teredo, which tunnels to IPv4
+        || first == 0x2002 // 6to4, which embeds an IPv4 address
+        || first == 0x0064 && ip.segments()[1] == 0xff9b) // NAT64, which maps onto IPv4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public(ip: &str) -> bool {
+        is_public(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn public_addresses_are_allowed() {
+        for ip in ["93.184.216.34", "8.8.8.8", "2606:2800:220:1:248:1893:25c8:1946", "::ffff:93.184.216.34", "::93.184.216.34"] {
+            assert!(public(ip), "{} should be public", ip);
+        }
+    }
+
+    #[test]
+    fn private_and_reserved_ipv4_is_refused() {
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1", // Carrier-grade NAT
+            "100.127.255.255",
+            "0.0.0.0",
+            "192.0.0.1",
+            "198.18.0.1",
+            "224.0.0.1",
+            "240.0.0.1",
+            "255.255.255.255",
+        ] {
+            assert!(!public(ip), "{} should be refused", ip);
+        }
+        assert!(public("100.128.0.1"));
+    }
+
+    #[test]
+    fn private_and_tunnelled_ipv6_is_refused() {
+        for ip in [
+            "::1",
+            "::",
+            "fc00::1",
+            "fd12:3456::1",
+            "fe80::1",
+            "fec0::1", // Site local
+            "feff::1",
+            "ff02::1",
+            "2001:db8::1",
+            "2001:0:4136:e378:8000:63bf:3fff:fdd2", // Teredo
+            "2002:7f00:1::1",                       // 6to4
+            "64:ff9b::7f00:1",                      // NAT64
+            "::ffff:127.0.0.1",                     // IPv4-mapped
+            "::ffff:10.0.0.1",
+            "::127.0.0.1", // IPv4-compatible
+            "::169.254.169.254",
+            "::100.64.0.1",
+        ] {
+            assert!(!public(ip), "{} should be refused", ip);
+        }
+    }
+}
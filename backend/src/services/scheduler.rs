@@ -0,0 +1,139 @@
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, Utc};
+use tokio::time::{interval, MissedTickBehavior};
+
+use crate::{
+    db::SharedState,
+    handlers::invalidate_published_post_counts,
+    models::WebhookEvent,
+    services::{
+        activitypub,
+        notices::{ContentChange, ContentKind},
+        recurrence::RecurrenceRule,
+        webhooks,
+    },
+};
+
+const TICK: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Publishes scheduled posts, archives expired posts, clears lapsed pins and archives
+/// events once they are over. Runs for the life of the server.
+pub async fn run(state: SharedState) {
+    let mut ticker = interval(TICK);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        if let Err(e) = tick(&state).await {
+            tracing::error!("Scheduler run failed: {:?}", e);
+        }
+    }
+}
+
+async fn tick(state: &SharedState) -> Result<()> {
+    // Scheduled posts were translated when they were approved
+    let published: Vec<i32> = sqlx::query_scalar(
+        "UPDATE posts SET published = true, published_at = NOW(), updated_at = NOW() WHERE NOT published AND publish_at <= NOW() RETURNING id",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let expired: Vec<i32> = sqlx::query_scalar(
+        "UPDATE posts SET archived_at = NOW() WHERE archived_at IS NULL AND expires_at <= NOW() RETURNING id",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    if !published.is_empty() || !expired.is_empty() {
+        tracing::info!("Published {} scheduled posts, archived {} expired posts", published.len(), expired.len());
+        invalidate_published_post_counts(state).await;
+    }
+    notify(state, ContentChange::Published, ContentKind::Post, &published).await;
+    for &id in &published {
+        webhooks::enqueue_post_id(&state.pool, WebhookEvent::PostPublished, id).await;
+        activitypub::enqueue(&state.pool, ContentKind::Post, id).await;
+    }
+    notify(state, ContentChange::Deleted, ContentKind::Post, &expired).await;
+
+    // Listings already ignore lapsed pins; this tidies them up for the dashboard
+    let unpinned: Vec<i32> = sqlx::query_scalar(
+        r#"
+        UPDATE posts SET pinned_at = NULL, pinned_until = NULL, pin_priority = 0, emergency = false
+        WHERE pinned_until <= NOW()
+        RETURNING id
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+    if !unpinned.is_empty() {
+        tracing::info!("Unpinned {} posts whose pin lapsed", unpinned.len());
+    }
+    notify(state, ContentChange::Updated, ContentKind::Post, &unpinned).await;
+
+    // A day's grace covers events running late into the night and the server's timezone
+    let cutoff = Utc::now().date_naive() - Duration::days(1);
+    let archived = archive_past_events(state, cutoff).await?;
+    if !archived.is_empty() {
+        tracing::info!("Archived {} past events", archived.len());
+    }
+    notify(state, ContentChange::Deleted, ContentKind::Event, &archived).await;
+
+    Ok(())
+}
+
+async fn notify(state: &SharedState, change: ContentChange, kind: ContentKind, ids: &[i32]) {
+    for &id in ids {
+        state.notifier.content(change, kind, id).await;
+    }
+}
+
+// The ids of the events archived
+async fn archive_past_events(state: &SharedState, cutoff: NaiveDate) -> Result<Vec<i32>> {
+    let mut archived: Vec<i32> = sqlx::query_scalar(
+        r#"
+        UPDATE events SET archived_at = NOW()
+        WHERE archived_at IS NULL AND recurrence_rule IS NULL AND event_date < $1
+        RETURNING id
+        "#,
+    )
+    .bind(cutoff)
+    .fetch_all(&state.pool)
+    .await?;
+
+    // Series only end if their rule has an UNTIL or a COUNT
+    let series: Vec<(i32, NaiveDate, String, Vec<NaiveDate>)> = sqlx::query_as(
+        r#"
+        SELECT id, event_date, recurrence_rule, recurrence_exdates FROM events
+        WHERE archived_at IS NULL AND recurrence_rule IS NOT NULL AND event_date < $1
+        "#,
+    )
+    .bind(cutoff)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let ended: Vec<i32> = series
+        .into_iter()
+        .filter(|(id, start, rule, exdates)| match RecurrenceRule::parse(rule) {
+            Ok(rule) => rule.is_finite() && rule.last_occurrence(*start, exdates).is_none_or(|last| last < cutoff),
+            Err(e) => {
+                tracing::warn!("Not archiving event {} with invalid recurrence rule: {:?}", id, e);
+                false
+            }
+        })
+        .map(|(id, ..)| id)
+        .collect();
+
+    if ended.is_empty() {
+        return Ok(archived);
+    }
+
+    let series_ended: Vec<i32> = sqlx::query_scalar(
+        "UPDATE events SET archived_at = NOW() WHERE id = ANY($1) AND archived_at IS NULL RETURNING id",
+    )
+    .bind(&ended)
+    .fetch_all(&state.pool)
+    .await?;
+
+    archived.extend(series_ended);
+    Ok(archived)
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use sqlx::PgPool;
+
+const MAX_SLUG_CHARS: usize = 80;
+
+/// Lowercases `title` and joins its alphanumeric runs with dashes. Non-Latin letters
+/// are kept as-is, so Arabic or Chinese titles still get a readable slug.
+pub fn slugify(title: &str) -> Option<String> {
+    let slug: String = title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .take(MAX_SLUG_CHARS)
+        .collect();
+
+    let slug = slug.trim_end_matches('-');
+    (!slug.is_empty()).then(|| slug.to_string())
+}
+
+/// Gives the row in `table` (`posts` or `events`) a unique slug derived from its title.
+/// Falls back to `<slug>-<id>` when the bare slug is taken or would look like an id;
+/// returns `None` if the title has nothing to slug or both candidates are taken.
+pub async fn assign_slug(pool: &PgPool, table: &str, id: i32, title: &str) -> Result<Option<String>> {
+    let base = match slugify(title) {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+
+    let mut candidates = vec![format!("{}-{}", base, id)];
+    if !base.chars().all(|c| c.is_ascii_digit()) {
+        candidates.insert(0, base);
+    }
+
+    for candidate in candidates {
+        let result = sqlx::query(&format!(
+            "UPDATE {table} SET slug = $2 WHERE id = $1 AND NOT EXISTS (SELECT 1 FROM {table} WHERE slug = $2)"
+        ))
+        .bind(id)
+        .bind(&candidate)
+        .execute(pool)
+        .await;
+
+        match result {
+            Ok(r) if r.rows_affected() > 0 => return Ok(Some(candidate)),
+            Ok(_) => continue,
+            // A concurrent insert took the slug between the check and the update
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,310 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Url;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::models::SpamReason;
+
+const DEFAULT_QUARANTINE_THRESHOLD: i32 = 60;
+
+// Scores of the individual checks; a submission's score is their sum
+const HONEYPOT_SCORE: i32 = 100;
+const BLOCKED_DOMAIN_SCORE: i32 = 80;
+const SCORE_PER_EXTRA_LINK: i32 = 15;
+const MAX_LINK_SCORE: i32 = 60;
+const REPEATED_CONTENT_SCORE: i32 = 40;
+const SCORE_PER_EXTRA_SUBMISSION: i32 = 15;
+const MAX_VELOCITY_SCORE: i32 = 45;
+const MAX_CLASSIFIER_SCORE: f64 = 70.0;
+
+// Links in the text beyond this many start to count
+const FREE_LINKS: usize = 2;
+// Submissions by the same address or email within an hour beyond this many start to count
+const FREE_SUBMISSIONS_PER_HOUR: i64 = 2;
+const REPEATED_CONTENT_DAYS: i32 = 30;
+
+lazy_static::lazy_static! {
+    static ref TEXT_URL: regex::Regex = regex::Regex::new(r"(?i)\bhttps?://[^\s<>()]+|\bwww\.[^\s<>()]+").unwrap();
+}
+
+/// What the checks see of a post or event submission.
+pub struct Submission<'a> {
+    pub title: &'a str,
+    pub body: Option<&'a str>,
+    pub urls: Vec<&'a str>, // Link, image and ticket URLs given as fields
+    pub email: Option<&'a str>,
+    pub submitter_hash: &'a str,
+    pub content_hash: &'a str,
+    pub honeypot: Option<&'a str>,
+}
+
+impl Submission<'_> {
+    fn text_urls(&self) -> Vec<&str> {
+        [Some(self.title), self.body]
+            .into_iter()
+            .flatten()
+            .flat_map(|text| TEXT_URL.find_iter(text).map(|m| m.as_str()))
+            .collect()
+    }
+}
+
+#[async_trait]
+pub trait SpamCheck: Send + Sync {
+    /// A reason counting against the submission, or `None` if nothing was found.
+    async fn check(&self, pool: &PgPool, submission: &Submission<'_>) -> Result<Option<SpamReason>>;
+}
+
+/// The hidden form field only bots fill in.
+pub struct HoneypotCheck;
+
+#[async_trait]
+impl SpamCheck for HoneypotCheck {
+    async fn check(&self, _pool: &PgPool, submission: &Submission<'_>) -> Result<Option<SpamReason>> {
+        Ok(submission
+            .honeypot
+            .filter(|v| !v.trim().is_empty())
+            .map(|_| reason("honeypot", HONEYPOT_SCORE, "hidden field was filled in".to_string())))
+    }
+}
+
+pub struct LinkCountCheck;
+
+#[async_trait]
+impl SpamCheck for LinkCountCheck {
+    async fn check(&self, _pool: &PgPool, submission: &Submission<'_>) -> Result<Option<SpamReason>> {
+        let links = submission.text_urls().len();
+        if links <= FREE_LINKS {
+            return Ok(None);
+        }
+
+        let score = (SCORE_PER_EXTRA_LINK * (links - FREE_LINKS) as i32).min(MAX_LINK_SCORE);
+        Ok(Some(reason("links", score, format!("{} links in the text", links))))
+    }
+}
+
+/// Links to a domain on the `blocked_domains` list, or a subdomain of one.
+pub struct BlockedDomainCheck;
+
+#[async_trait]
+impl SpamCheck for BlockedDomainCheck {
+    async fn check(&self, pool: &PgPool, submission: &Submission<'_>) -> Result<Option<SpamReason>> {
+        let candidates: Vec<String> = submission
+            .urls
+            .iter()
+            .copied()
+            .chain(submission.text_urls())
+            .filter_map(host)
+            .flat_map(|host| parent_domains(&host))
+            .collect();
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        let blocked: Vec<String> = sqlx::query_scalar("SELECT domain FROM blocked_domains WHERE domain = ANY($1)")
+            .bind(&candidates)
+            .fetch_all(pool)
+            .await?;
+
+        Ok((!blocked.is_empty())
+            .then(|| reason("blocked_domain", BLOCKED_DOMAIN_SCORE, format!("links to {}", blocked.join(", ")))))
+    }
+}
+
+/// The same title and text submitted before, as copy-paste spam is.
+pub struct RepeatedContentCheck;
+
+#[async_trait]
+impl SpamCheck for RepeatedContentCheck {
+    async fn check(&self, pool: &PgPool, submission: &Submission<'_>) -> Result<Option<SpamReason>> {
+        let repeats: i64 = sqlx::query_scalar(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM posts WHERE content_hash = $1 AND created_at > NOW() - make_interval(days => $2))
+              + (SELECT COUNT(*) FROM events WHERE content_hash = $1 AND created_at > NOW() - make_interval(days => $2))
+            "#,
+        )
+        .bind(submission.content_hash)
+        .bind(REPEATED_CONTENT_DAYS)
+        .fetch_one(pool)
+        .await?;
+
+        Ok((repeats > 0).then(|| {
+            let detail = format!("same text submitted {} time(s) in the last {} days", repeats, REPEATED_CONTENT_DAYS);
+            reason("repeated_content", REPEATED_CONTENT_SCORE, detail)
+        }))
+    }
+}
+
+/// Many submissions in a short time from one address or email.
+pub struct VelocityCheck;
+
+#[async_trait]
+impl SpamCheck for VelocityCheck {
+    async fn check(&self, pool: &PgPool, submission: &Submission<'_>) -> Result<Option<SpamReason>> {
+        let recent: i64 = sqlx::query_scalar(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM posts
+                 WHERE created_at > NOW() - INTERVAL '1 hour'
+                   AND (submitter_hash = $1 OR lower(author_email) = lower($2)))
+              + (SELECT COUNT(*) FROM events
+                 WHERE created_at > NOW() - INTERVAL '1 hour'
+                   AND (submitter_hash = $1 OR lower(organizer_email) = lower($2)))
+            "#,
+        )
+        .bind(submission.submitter_hash)
+        .bind(submission.email)
+        .fetch_one(pool)
+        .await?;
+
+        if recent <= FREE_SUBMISSIONS_PER_HOUR {
+            return Ok(None);
+        }
+        let score = (SCORE_PER_EXTRA_SUBMISSION * (recent - FREE_SUBMISSIONS_PER_HOUR) as i32).min(MAX_VELOCITY_SCORE);
+        Ok(Some(reason("velocity", score, format!("{} other submissions in the last hour", recent))))
+    }
+}
+
+/// Asks a self-hosted classifier for the probability that the text is spam. It's
+/// sent `{"text": ...}` and answers `{"score": 0.0..1.0}`.
+pub struct ClassifierCheck {
+    url: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct ClassifierResponse {
+    score: f64,
+}
+
+impl ClassifierCheck {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(3))
+                .build()
+                .expect("Failed to build classifier client"),
+        }
+    }
+}
+
+#[async_trait]
+impl SpamCheck for ClassifierCheck {
+    async fn check(&self, _pool: &PgPool, submission: &Submission<'_>) -> Result<Option<SpamReason>> {
+        let text = format!("{}\n\n{}", submission.title, submission.body.unwrap_or(""));
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({ "text": text }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ClassifierResponse>()
+            .await?;
+
+        let score = (response.score.clamp(0.0, 1.0) * MAX_CLASSIFIER_SCORE).round() as i32;
+        Ok((score > 0).then(|| reason("classifier", score, format!("classifier score {:.2}", response.score))))
+    }
+}
+
+pub struct SpamAssessment {
+    pub score: i32,
+    pub reasons: Vec<SpamReason>,
+    pub quarantined: bool,
+}
+
+/// Runs every check over a submission and adds up the scores.
+pub struct SpamScorer {
+    checks: Vec<Box<dyn SpamCheck>>,
+    quarantine_threshold: i32,
+}
+
+impl SpamScorer {
+    pub fn new(checks: Vec<Box<dyn SpamCheck>>, quarantine_threshold: i32) -> Self {
+        Self { checks, quarantine_threshold }
+    }
+
+    /// A check that fails is skipped rather than holding up the submission.
+    pub async fn assess(&self, pool: &PgPool, submission: &Submission<'_>) -> SpamAssessment {
+        let mut reasons = Vec::new();
+        for check in &self.checks {
+            match check.check(pool, submission).await {
+                Ok(Some(reason)) => reasons.push(reason),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Spam check failed: {:?}", e),
+            }
+        }
+
+        let score = reasons.iter().map(|r| r.score).sum();
+        SpamAssessment {
+            score,
+            reasons,
+            quarantined: score >= self.quarantine_threshold,
+        }
+    }
+}
+
+pub fn spam_scorer_from_env() -> SpamScorer {
+    let mut checks: Vec<Box<dyn SpamCheck>> = vec![
+        Box::new(HoneypotCheck),
+        Box::new(LinkCountCheck),
+        Box::new(BlockedDomainCheck),
+        Box::new(RepeatedContentCheck),
+        Box::new(VelocityCheck),
+    ];
+    if let Some(url) = std::env::var("SPAM_CLASSIFIER_URL").ok().filter(|u| !u.is_empty()) {
+        tracing::info!("Scoring submissions with the classifier at {}", url);
+        checks.push(Box::new(ClassifierCheck::new(url)));
+    }
+
+    let threshold = std::env::var("SPAM_QUARANTINE_THRESHOLD")
+        .ok()
+        .map(|t| t.parse().expect("Invalid SPAM_QUARANTINE_THRESHOLD"))
+        .unwrap_or(DEFAULT_QUARANTINE_THRESHOLD);
+
+    SpamScorer::new(checks, threshold)
+}
+
+/// Identifies resubmissions of the same text regardless of case and spacing.
+pub fn content_hash(title: &str, body: Option<&str>) -> String {
+    let normalized = format!("{}\n{}", title, body.unwrap_or(""))
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// Lets submissions from one address be matched without storing the address.
+pub fn submitter_hash(address_key: &str) -> String {
+    hex::encode(Sha256::digest(format!("submitter:{}", address_key).as_bytes()))
+}
+
+/// Normalizes a domain for the block list: lowercase, without "www." or a trailing dot.
+pub fn normalize_domain(domain: &str) -> String {
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    domain.strip_prefix("www.").map(str::to_string).unwrap_or(domain)
+}
+
+fn host(url: &str) -> Option<String> {
+    let url = if url.to_lowercase().starts_with("www.") { format!("http://{}", url) } else { url.to_string() };
+    Url::parse(&url).ok()?.host_str().map(normalize_domain)
+}
+
+// spam.example.com matches entries for spam.example.com and example.com
+fn parent_domains(host: &str) -> Vec<String> {
+    let labels: Vec<&str> = host.split('.').collect();
+    (0..labels.len().saturating_sub(1)).map(|i| labels[i..].join(".")).collect()
+}
+
+fn reason(check: &str, score: i32, detail: String) -> SpamReason {
+    SpamReason {
+        check: check.to_string(),
+        score,
+        detail,
+    }
+}
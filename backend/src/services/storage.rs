@@ -0,0 +1,194 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Stores `bytes` under `key`. Keys are content hashes, so rewriting one is harmless.
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<()>;
+
+    /// Fetches the blob stored under `key`; `Ok(None)` means there is no such blob.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+}
+
+/// Stores blobs as files under a root directory, sharded by the first two characters of the key.
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.root.join(key.get(..2).unwrap_or("__")).join(key)
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<()> {
+        let path = self.path_for(key);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+
+        // Write then rename, so readers never see a half-written file
+        let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path_for(key)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Talks to any S3-compatible service (AWS S3, MinIO, R2, ...) using path-style
+/// requests signed with AWS Signature Version 4.
+pub struct S3BlobStore {
+    endpoint: String,
+    host: String,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    client: reqwest::Client,
+}
+
+impl S3BlobStore {
+    pub fn new(
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key_id: String,
+        secret_access_key: String,
+    ) -> Result<Self> {
+        let endpoint = endpoint.trim_end_matches('/').to_string();
+        let url = reqwest::Url::parse(&endpoint)?;
+        let host = match (url.host_str(), url.port()) {
+            // Non-default ports are part of the signed Host header
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => bail!("S3 endpoint has no host: {}", endpoint),
+        };
+
+        Ok(Self {
+            endpoint,
+            host,
+            bucket,
+            region,
+            access_key_id,
+            secret_access_key,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()?,
+        })
+    }
+
+    fn signed_request(&self, method: reqwest::Method, key: &str, payload: &[u8]) -> reqwest::RequestBuilder {
+        let path = format!("/{}/{}", self.bucket, key);
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let authorization = self.authorization(method.as_str(), &path, payload, &amz_date);
+
+        self.client
+            .request(method, format!("{}{}", self.endpoint, path))
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", hex::encode(Sha256::digest(payload)))
+            .header(reqwest::header::AUTHORIZATION, authorization)
+    }
+
+    // Authorization header for a request without query parameters (AWS SigV4)
+    fn authorization(&self, method: &str, path: &str, payload: &[u8], amz_date: &str) -> String {
+        let date = &amz_date[..8];
+        let payload_hash = hex::encode(Sha256::digest(payload));
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, self.host, payload_hash, amz_date, signed_headers, payload_hash
+        );
+
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut signing_key = format!("AWS4{}", self.secret_access_key).into_bytes();
+        for part in [date, self.region.as_str(), "s3", "aws4_request"] {
+            signing_key = hmac_sha256(&signing_key, part.as_bytes());
+        }
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        )
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<()> {
+        self.signed_request(reqwest::Method::PUT, key, &bytes)
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(bytes)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let response = self.signed_request(reqwest::Method::GET, key, b"").send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.bytes().await?.to_vec()))
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Builds the blob store from `STORAGE_BACKEND` (`local`, the default, or `s3`).
+/// Local storage lives under `STORAGE_PATH`; S3 needs `S3_ENDPOINT`, `S3_BUCKET`,
+/// `S3_REGION`, `S3_ACCESS_KEY_ID` and `S3_SECRET_ACCESS_KEY`.
+pub fn blob_store_from_env() -> Arc<dyn BlobStore> {
+    match std::env::var("STORAGE_BACKEND").as_deref() {
+        Ok("s3") => {
+            let var = |name: &str| std::env::var(name).unwrap_or_else(|_| panic!("{} must be set", name));
+            let store = S3BlobStore::new(
+                var("S3_ENDPOINT"),
+                var("S3_BUCKET"),
+                std::env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+                var("S3_ACCESS_KEY_ID"),
+                var("S3_SECRET_ACCESS_KEY"),
+            )
+            .expect("Invalid S3 storage configuration");
+            tracing::info!("Storing uploads in S3 bucket {} at {}", store.bucket, store.endpoint);
+            Arc::new(store)
+        }
+        _ => {
+            let root = std::env::var("STORAGE_PATH").unwrap_or_else(|_| "./uploads".to_string());
+            tracing::info!("Storing uploads under {}", root);
+            Arc::new(LocalBlobStore::new(PathBuf::from(root)))
+        }
+    }
+}
@@ -38,6 +38,16 @@ pub fn is_rtl_language(language_code: &str) -> bool {
     RTL_LANGUAGES.contains(language_code)
 }
 
+/// The language an item localized into `lang` is actually in: without a translation it
+/// falls back to its original text.
+pub fn content_language<'a>(lang: &'a str, original_language: &'a str, is_translated: bool) -> &'a str {
+    if is_translated {
+        lang
+    } else {
+        original_language
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct GoogleTranslateRequest {
     q: Vec<String>,
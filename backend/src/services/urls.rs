@@ -0,0 +1,34 @@
+use anyhow::{bail, Result};
+use reqwest::Url;
+
+// Matches the VARCHAR(500) url columns
+pub const MAX_URL_LENGTH: usize = 500;
+
+const ALLOWED_SCHEMES: &[&str] = &["http", "https"];
+
+/// Checks a submitted URL and returns it in normalized form. Blank input means
+/// "no URL" and comes back as `None`.
+pub fn normalize_url(input: Option<&str>) -> Result<Option<String>> {
+    let Some(input) = input.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+
+    let url = Url::parse(input)?;
+    if !ALLOWED_SCHEMES.contains(&url.scheme()) {
+        bail!("URL scheme not allowed: {}", url.scheme());
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        bail!("URL has no host");
+    }
+    // Credentials in links end up on screen and in logs
+    if !url.username().is_empty() || url.password().is_some() {
+        bail!("URL contains credentials");
+    }
+
+    let normalized = url.to_string();
+    if normalized.len() > MAX_URL_LENGTH {
+        bail!("URL is longer than {} characters", MAX_URL_LENGTH);
+    }
+
+    Ok(Some(normalized))
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Deserializer};
+use validator::ValidationError;
+
+// "auto" asks the server to detect the direction from the text
+pub const TEXT_DIRECTIONS: &[&str] = &["ltr", "rtl", "auto"];
+
+/// Rejects values that are empty once surrounding whitespace is removed.
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("required"));
+    }
+    Ok(())
+}
+
+pub fn valid_text_direction(value: &str) -> Result<(), ValidationError> {
+    if !TEXT_DIRECTIONS.contains(&value) {
+        return Err(ValidationError::new("invalid"));
+    }
+    Ok(())
+}
+
+/// Deserializes an optional string, trimmed, with blank values treated as absent. Forms
+/// send empty inputs as "" rather than leaving them out.
+pub fn trimmed_option<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty()))
+}
@@ -0,0 +1,338 @@
+use anyhow::Result;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{header, redirect, Url};
+use serde_json::json;
+use sha2::Sha256;
+use sqlx::{types::Json, FromRow, PgPool};
+use tokio::time::{interval, MissedTickBehavior};
+
+use crate::{
+    db::SharedState,
+    models::{Event, EventTranslation, Post, PostTranslation, Webhook, WebhookDelivery, WebhookEvent},
+    services::{delivery, remote},
+};
+
+const PING_EVENT: &str = "ping";
+const EVENT_HEADER: &str = "X-Lancaster-Event";
+const DELIVERY_HEADER: &str = "X-Lancaster-Delivery";
+const TIMESTAMP_HEADER: &str = "X-Lancaster-Timestamp";
+const SIGNATURE_HEADER: &str = "X-Lancaster-Signature";
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Queues a delivery of `event` to every enabled webhook subscribed to it. Failures are
+/// logged; webhooks never hold up the change that triggered them.
+pub async fn enqueue(pool: &PgPool, event: WebhookEvent, data: serde_json::Value) {
+    let payload = json!({
+        "event": event,
+        "occurred_at": Utc::now(),
+        "data": data
+    });
+
+    let queued = sqlx::query(
+        r#"
+        INSERT INTO webhook_deliveries (webhook_id, event, payload)
+        SELECT id, $1, $2 FROM webhooks WHERE enabled AND $1 = ANY(events)
+        "#,
+    )
+    .bind(event.as_str())
+    .bind(Json(payload))
+    .execute(pool)
+    .await;
+
+    match queued {
+        Ok(result) if result.rows_affected() > 0 => {
+            tracing::debug!("Queued {} {} webhook deliveries", result.rows_affected(), event.as_str());
+        }
+        Ok(_) => {}
+        Err(e) => tracing::error!("Failed to queue {} webhooks: {:?}", event.as_str(), e),
+    }
+}
+
+/// Queues `event` for a post; translated events also carry every translation.
+pub async fn enqueue_post(pool: &PgPool, event: WebhookEvent, post: &Post) {
+    let mut data = json!({ "post": without_email(post, "author_email") });
+    if event == WebhookEvent::PostTranslated {
+        let translations = sqlx::query_as::<_, PostTranslation>(
+            "SELECT * FROM post_translations WHERE post_id = $1 ORDER BY language_code",
+        )
+        .bind(post.id)
+        .fetch_all(pool)
+        .await;
+        match translations {
+            Ok(translations) => data["translations"] = json!(translations),
+            Err(e) => {
+                tracing::error!("Failed to fetch translations of post {}: {:?}", post.id, e);
+                return;
+            }
+        }
+    }
+    enqueue(pool, event, data).await;
+}
+
+/// Queues `event` for an event; translated events also carry every translation.
+pub async fn enqueue_event(pool: &PgPool, event: WebhookEvent, item: &Event) {
+    let mut data = json!({ "event": without_email(item, "organizer_email") });
+    if event == WebhookEvent::EventTranslated {
+        let translations = sqlx::query_as::<_, EventTranslation>(
+            "SELECT * FROM event_translations WHERE event_id = $1 ORDER BY language_code",
+        )
+        .bind(item.id)
+        .fetch_all(pool)
+        .await;
+        match translations {
+            Ok(translations) => data["translations"] = json!(translations),
+            Err(e) => {
+                tracing::error!("Failed to fetch translations of event {}: {:?}", item.id, e);
+                return;
+            }
+        }
+    }
+    enqueue(pool, event, data).await;
+}
+
+/// Looks the post up and queues `event` for it.
+pub async fn enqueue_post_id(pool: &PgPool, event: WebhookEvent, post_id: i32) {
+    match sqlx::query_as::<_, Post>("SELECT * FROM posts WHERE id = $1").bind(post_id).fetch_optional(pool).await {
+        Ok(Some(post)) => enqueue_post(pool, event, &post).await,
+        Ok(None) => {}
+        Err(e) => tracing::error!("Failed to fetch post {} for webhooks: {:?}", post_id, e),
+    }
+}
+
+/// Looks the event up and queues `event` for it.
+pub async fn enqueue_event_id(pool: &PgPool, event: WebhookEvent, event_id: i32) {
+    match sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id = $1").bind(event_id).fetch_optional(pool).await {
+        Ok(Some(item)) => enqueue_event(pool, event, &item).await,
+        Ok(None) => {}
+        Err(e) => tracing::error!("Failed to fetch event {} for webhooks: {:?}", event_id, e),
+    }
+}
+
+// Submitters' addresses stay with us
+fn without_email<T: serde::Serialize>(item: &T, field: &str) -> serde_json::Value {
+    let mut value = json!(item);
+    if let Some(object) = value.as_object_mut() {
+        object.remove(field);
+    }
+    value
+}
+
+/// Sends due deliveries, retrying failures with backoff. Runs for the life of the server;
+/// deliveries are claimed, so any number of instances can run it.
+pub async fn run(state: SharedState) {
+    let mut ticker = interval(delivery::POLL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        if let Err(e) = deliver_due(&state).await {
+            tracing::error!("Webhook delivery run failed: {:?}", e);
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct DueDelivery {
+    id: i32,
+    event: String,
+    payload: Json<serde_json::Value>,
+    attempts: i32, // Including the one being made
+    url: String,
+    secret: String,
+}
+
+async fn deliver_due(state: &SharedState) -> Result<()> {
+    let pool = &state.pool;
+    let claim = || async {
+        let due = sqlx::query_as::<_, DueDelivery>(
+            r#"
+            UPDATE webhook_deliveries d SET
+                attempts = d.attempts + 1,
+                next_attempt_at = NOW() + make_interval(secs => $2)
+            FROM webhooks w
+            WHERE w.id = d.webhook_id AND d.id IN (
+                SELECT due.id FROM webhook_deliveries due
+                JOIN webhooks hook ON hook.id = due.webhook_id
+                WHERE due.status = 'pending' AND due.next_attempt_at <= NOW() AND hook.enabled
+                ORDER BY due.next_attempt_at
+                LIMIT $1
+                FOR UPDATE OF due SKIP LOCKED
+            )
+            RETURNING d.id, d.event, d.payload, d.attempts, w.url, w.secret
+            "#,
+        )
+        .bind(delivery::BATCH_SIZE)
+        .bind(delivery::CLAIM_SECS)
+        .fetch_all(pool)
+        .await?;
+        Ok(due)
+    };
+
+    delivery::drain(claim, |delivery: DueDelivery| async move {
+        let attempt = send(state, &delivery.url, &delivery.secret, delivery.id, &delivery.event, &delivery.payload).await;
+        if let Err(e) = record(pool, delivery.id, delivery.attempts, &attempt).await {
+            tracing::error!("Failed to record webhook delivery {}: {:?}", delivery.id, e);
+        }
+    })
+    .await
+}
+
+// The outcome of one request to a webhook
+struct Attempt {
+    response_status: Option<i32>,
+    error: Option<String>,
+}
+
+impl Attempt {
+    fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+async fn record(pool: &PgPool, id: i32, attempts: i32, attempt: &Attempt) -> Result<()> {
+    if attempt.succeeded() {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries SET
+                status = 'delivered', delivered_at = NOW(), response_status = $2, last_error = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(attempt.response_status)
+        .execute(pool)
+        .await?;
+        return Ok(());
+    }
+
+    let exhausted = delivery::exhausted(attempts);
+    sqlx::query(
+        r#"
+        UPDATE webhook_deliveries SET
+            status = CASE WHEN $2 THEN 'failed' ELSE 'pending' END,
+            next_attempt_at = NOW() + make_interval(secs => $3),
+            response_status = $4,
+            last_error = $5
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(exhausted)
+    .bind(delivery::retry_delay_secs(attempts))
+    .bind(attempt.response_status)
+    .bind(&attempt.error)
+    .execute(pool)
+    .await?;
+
+    if exhausted {
+        tracing::warn!("Giving up on webhook delivery {} after {} attempts", id, attempts);
+    }
+    Ok(())
+}
+
+// POSTs the payload, signed with the webhook's secret. Anything but a 2xx is a failure,
+// redirects included.
+async fn send(
+    state: &SharedState,
+    url: &str, secret: &str, delivery_id: i32, event: &str, payload: &serde_json::Value) -> Attempt {
+    let body = payload.to_string();
+    let timestamp = Utc::now().timestamp().to_string();
+    let signature = sign(secret, &timestamp, &body);
+
+    let result: Result<reqwest::Response> = async {
+        let url = Url::parse(url)?;
+        let client = if state.webhooks_allow_private {
+            reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .redirect(redirect::Policy::none())
+                .build()?
+        } else {
+            remote::pinned_client(&url).await?
+        };
+        let response = client
+            .post(url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event)
+            .header(DELIVERY_HEADER, delivery_id.to_string())
+            .header(TIMESTAMP_HEADER, &timestamp)
+            .header(SIGNATURE_HEADER, format!("sha256={}", signature))
+            .body(body)
+            .send()
+            .await?;
+        Ok(response)
+    }
+    .await;
+
+    let response = match result {
+        Ok(response) => response,
+        Err(e) => {
+            return Attempt { response_status: None, error: Some(e.to_string()) };
+        }
+    };
+
+    let status = response.status();
+    if status.is_success() {
+        return Attempt { response_status: Some(status.as_u16() as i32), error: None };
+    }
+
+    Attempt {
+        response_status: Some(status.as_u16() as i32),
+        error: Some(delivery::error_response(response).await),
+    }
+}
+
+// Receivers recompute this over "{timestamp}.{body}" and reject stale timestamps
+fn sign(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Sends a `ping` straight away and logs it with the webhook's other deliveries. Pings
+/// are not retried.
+pub async fn test_fire(state: &SharedState, webhook: &Webhook) -> Result<WebhookDelivery> {
+    let pool = &state.pool;
+    let payload = json!({
+        "event": PING_EVENT,
+        "occurred_at": Utc::now(),
+        "data": { "webhook_id": webhook.id }
+    });
+
+    let (id,): (i32,) = sqlx::query_as(
+        r#"
+        INSERT INTO webhook_deliveries (webhook_id, event, payload, attempts, status)
+        VALUES ($1, $2, $3, 1, 'failed')
+        RETURNING id
+        "#,
+    )
+    .bind(webhook.id)
+    .bind(PING_EVENT)
+    .bind(Json(&payload))
+    .fetch_one(pool)
+    .await?;
+
+    let attempt = send(state, &webhook.url, &webhook.secret, id, PING_EVENT, &payload).await;
+
+    let delivery = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        UPDATE webhook_deliveries SET
+            status = CASE WHEN $2 THEN 'delivered' ELSE 'failed' END,
+            delivered_at = CASE WHEN $2 THEN NOW() END,
+            response_status = $3,
+            last_error = $4
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(attempt.succeeded())
+    .bind(attempt.response_status)
+    .bind(&attempt.error)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(delivery)
+}
@@ -4,9 +4,12 @@ import { useTranslation } from 'react-i18next'
 import { Helmet } from 'react-helmet-async'
 import Layout from './components/Layout'
 import NewsFeed from './pages/NewsFeed'
+import PostDetail from './pages/PostDetail'
 import Calendar from './pages/Calendar'
+import EventDetail from './pages/EventDetail'
 import SubmitPost from './pages/SubmitPost'
 import SubmitEvent from './pages/SubmitEvent'
+import Newsletter from './pages/Newsletter'
 import AdminLogin from './pages/AdminLogin'
 import AdminDashboard from './pages/AdminDashboard'
 import { useDirection } from './hooks/useDirection'
@@ -31,9 +34,13 @@ function App(): JSX.Element {
       <Routes>
         <Route path="/" element={<Layout />}>
           <Route index element={<NewsFeed />} />
+          <Route path="posts/:id" element={<PostDetail />} />
           <Route path="calendar" element={<Calendar />} />
+          <Route path="events/:id" element={<EventDetail />} />
           <Route path="submit/post" element={<SubmitPost />} />
           <Route path="submit/event" element={<SubmitEvent />} />
+          <Route path="newsletter" element={<Newsletter />} />
+          <Route path="newsletter/confirm" element={<Newsletter />} />
           <Route path="admin" element={<AdminLogin />} />
           <Route path="admin/dashboard" element={<AdminDashboard />} />
         </Route>
@@ -0,0 +1,74 @@
+import type React from 'react';
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { apiUrl } from '../utils/api';
+import type { AlertSeverity, LocalizedAlert } from '../types/api';
+
+const SEVERITY_RANK: Record<AlertSeverity, number> = { minor: 0, moderate: 1, severe: 2, extreme: 3 };
+
+const SEVERITY_STYLES: Record<AlertSeverity, string> = {
+  minor: 'bg-yellow-100 text-yellow-900 border-yellow-500',
+  moderate: 'bg-orange-100 text-orange-900 border-orange-500',
+  severe: 'bg-red-100 text-red-900 border-red-600',
+  extreme: 'bg-red-700 text-white border-red-900',
+};
+
+// Most severe first, then newest
+const byUrgency = (a: LocalizedAlert, b: LocalizedAlert): number =>
+  SEVERITY_RANK[b.severity] - SEVERITY_RANK[a.severity] || b.created_at.localeCompare(a.created_at);
+
+/** Active emergency alerts in the reader's language, kept current over Server-Sent Events. */
+const AlertBanner: React.FC = () => {
+  const { t, i18n } = useTranslation();
+  const [alerts, setAlerts] = useState<LocalizedAlert[]>([]);
+  const [now, setNow] = useState(() => Date.now());
+
+  useEffect(() => {
+    const source = new EventSource(apiUrl('/alerts/stream', { lang: i18n.language }));
+
+    // Every (re)connection starts with the full list of active alerts
+    source.onopen = () => setAlerts([]);
+    source.addEventListener('alert', e => {
+      const alert = JSON.parse((e as MessageEvent<string>).data) as LocalizedAlert;
+      setAlerts(prev => [...prev.filter(a => a.id !== alert.id), alert].sort(byUrgency));
+    });
+    source.addEventListener('alert_ended', e => {
+      const { id } = JSON.parse((e as MessageEvent<string>).data) as { id: number };
+      setAlerts(prev => prev.filter(a => a.id !== id));
+    });
+
+    return () => source.close();
+  }, [i18n.language]);
+
+  // Expiry isn't announced, so drop lapsed alerts locally
+  useEffect(() => {
+    const timer = window.setInterval(() => setNow(Date.now()), 30_000);
+    return () => window.clearInterval(timer);
+  }, []);
+
+  const active = alerts.filter(a => new Date(a.expires_at).getTime() > now);
+  if (active.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="container mx-auto px-3 md:px-4 pt-4 space-y-2">
+      {active.map(alert => (
+        <section
+          key={alert.id}
+          role={SEVERITY_RANK[alert.severity] >= SEVERITY_RANK.severe ? 'alert' : 'status'}
+          className={`border-s-4 rounded p-4 ${SEVERITY_STYLES[alert.severity]}`}
+          dir={alert.text_direction}
+        >
+          <p className="text-xs font-bold uppercase tracking-wide" style={{ fontFamily: 'var(--font-sans)' }}>
+            {t('alerts.label')} · {t(`alerts.severity.${alert.severity}`)}
+          </p>
+          <h2 className="text-lg font-bold">{alert.title}</h2>
+          <p className="whitespace-pre-wrap">{alert.body}</p>
+        </section>
+      ))}
+    </div>
+  );
+};
+
+export default AlertBanner;
@@ -3,6 +3,7 @@ import { useTranslation } from 'react-i18next';
 import { format } from 'date-fns';
 import { ar, de, es, fr, zhCN } from 'date-fns/locale';
 import type { EventWithTranslation } from '../types/api';
+import { assetUrl } from '../utils/api';
 import type { Locale } from 'date-fns';
 
 const locales: Record<string, Locale | undefined> = {
@@ -157,6 +158,17 @@ const EventCard: React.FC<EventCardProps> = ({ event }) => {
         </p>
       )}
 
+      {event.image_url && (
+        <div className="mb-4">
+          <img 
+            src={assetUrl(event.image_url)} 
+            alt={event.title}
+            className="w-full rounded-lg"
+            loading="lazy"
+          />
+        </div>
+      )}
+
       {event.location && (
         <div className="flex items-center gap-2 mb-3 text-sm text-neutral-600 font-medium" dir="auto" style={{ fontFamily: 'var(--font-sans)' }}>
           <svg className="w-4 h-4 flex-shrink-0" fill="none" stroke="currentColor" viewBox="0 0 24 24">
@@ -22,6 +22,7 @@ const Header: React.FC = () => {
   const navLinks: NavLink[] = [
     { path: '/', label: t('nav.news') },
     { path: '/calendar', label: t('nav.calendar') },
+    { path: '/newsletter', label: t('nav.newsletter') },
   ];
 
   return (
@@ -0,0 +1,97 @@
+import type { ChangeEvent } from 'react';
+import type React from 'react';
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import axios from 'axios';
+import { assetUrl, endpoints } from '../utils/api';
+import type { UploadedImage } from '../types/api';
+
+const ACCEPTED_TYPES = 'image/jpeg,image/png,image/webp,image/gif';
+const MAX_UPLOAD_BYTES = 10 * 1024 * 1024;
+
+interface ImageUploadProps {
+  onChange: (image: UploadedImage | null) => void;
+}
+
+const ImageUpload: React.FC<ImageUploadProps> = ({ onChange }) => {
+  const { t } = useTranslation();
+  const [image, setImage] = useState<UploadedImage | null>(null);
+  const [uploading, setUploading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleFileChange = async (e: ChangeEvent<HTMLInputElement>) => {
+    const file = e.target.files?.[0];
+    e.target.value = '';
+    if (!file) return;
+
+    if (file.size > MAX_UPLOAD_BYTES) {
+      setError(t('common.image_too_large'));
+      return;
+    }
+
+    setUploading(true);
+    setError(null);
+    try {
+      const response = await endpoints.uploadImage(file);
+      setImage(response.data);
+      onChange(response.data);
+    } catch (err) {
+      console.error('Failed to upload image:', err);
+      const status = axios.isAxiosError(err) ? err.response?.status : undefined;
+      setError(
+        status === 413 ? t('common.image_too_large') :
+        status === 415 ? t('common.image_unsupported') :
+        t('common.upload_failed')
+      );
+    } finally {
+      setUploading(false);
+    }
+  };
+
+  const handleRemove = () => {
+    setImage(null);
+    onChange(null);
+  };
+
+  return (
+    <div>
+      <label className="block text-sm font-medium mb-1">
+        {t('common.photo')}
+      </label>
+
+      {image ? (
+        <div className="flex items-center gap-3">
+          <img
+            src={assetUrl(image.thumbnail_url)}
+            alt=""
+            className="h-20 w-20 object-cover rounded-lg border border-gray-300"
+          />
+          <button
+            type="button"
+            onClick={handleRemove}
+            className="btn btn-secondary"
+          >
+            {t('common.remove')}
+          </button>
+        </div>
+      ) : (
+        <input
+          type="file"
+          accept={ACCEPTED_TYPES}
+          onChange={(e) => void handleFileChange(e)}
+          disabled={uploading}
+          className="w-full text-sm file:mr-3 file:px-3 file:py-2 file:border-0 file:rounded-lg file:bg-gray-100 file:font-medium hover:file:bg-gray-200"
+        />
+      )}
+
+      {uploading && (
+        <p className="text-sm text-gray-600 mt-1">{t('common.uploading')}</p>
+      )}
+      {error && (
+        <p className="text-sm text-red-700 mt-1">{error}</p>
+      )}
+    </div>
+  );
+};
+
+export default ImageUpload;
@@ -2,6 +2,7 @@ import type React from 'react';
 import { Outlet, Link } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
 import Header from './Header';
+import AlertBanner from './AlertBanner';
 
 const Layout: React.FC = () => {
   const { t } = useTranslation();
@@ -9,6 +10,7 @@ const Layout: React.FC = () => {
   return (
     <div className="min-h-screen bg-paper">
       <Header />
+      <AlertBanner />
 
       <main className="container mx-auto px-3 md:px-4 py-6 md:py-8">
         <Outlet />
@@ -3,6 +3,7 @@ import { useTranslation } from 'react-i18next';
 import { format } from 'date-fns';
 import { ar, de, es, fr, zhCN } from 'date-fns/locale';
 import type { PostWithTranslation } from '../types/api';
+import { assetUrl } from '../utils/api';
 import type { Locale } from 'date-fns';
 
 const locales: Record<string, Locale | undefined> = {
@@ -31,8 +32,23 @@ const PostCard: React.FC<PostCardProps> = ({ post }) => {
   };
 
   return (
-    <article className="p-6 bg-white rounded-lg shadow-sm border border-neutral-200 hover:shadow-md hover:border-primary-200 transition-all duration-200 relative overflow-hidden">
-      <div className="absolute top-0 left-0 w-full h-1 bg-gradient-to-r from-primary-600 to-primary-500"></div>
+    <article
+      className={`p-6 bg-white rounded-lg shadow-sm border hover:shadow-md transition-all duration-200 relative overflow-hidden ${
+        post.emergency ? 'border-2 border-red-600 bg-red-50' : 'border-neutral-200 hover:border-primary-200'
+      }`}
+      role={post.emergency ? 'alert' : undefined}
+    >
+      <div className={`absolute top-0 left-0 w-full ${post.emergency ? 'h-2 bg-red-600' : 'h-1 bg-gradient-to-r from-primary-600 to-primary-500'}`}></div>
+      {post.pinned && (
+        <div
+          className={`inline-flex items-center gap-1 mb-3 px-2 py-1 rounded text-xs font-bold uppercase tracking-wide ${
+            post.emergency ? 'bg-red-600 text-white' : 'bg-primary-600/10 text-primary-600'
+          }`}
+          style={{ fontFamily: 'var(--font-sans)' }}
+        >
+          {post.emergency ? t('posts.emergency') : t('posts.pinned')}
+        </div>
+      )}
       <div className="flex flex-col sm:flex-row sm:flex-row-reverse sm:justify-between gap-2 mb-4">
         <div className="flex flex-wrap items-center gap-3">
           <span 
@@ -138,7 +154,7 @@ const PostCard: React.FC<PostCardProps> = ({ post }) => {
       {post.image_url && (
         <div className="mb-4 -mx-6 sm:mx-0">
           <img 
-            src={post.image_url} 
+            src={assetUrl(post.image_url)} 
             alt={post.title}
             className="w-full rounded-lg"
             loading="lazy"
@@ -146,7 +162,39 @@ const PostCard: React.FC<PostCardProps> = ({ post }) => {
         </div>
       )}
 
-      {post.link_url && (
+      {post.link_preview && (
+        <a
+          href={post.link_preview.url}
+          target="_blank"
+          rel="noopener noreferrer"
+          className="flex gap-3 mb-4 border border-neutral-200 rounded-lg overflow-hidden hover:border-primary-600 transition-colors"
+          dir="auto"
+        >
+          {post.link_preview.image_url && (
+            <img
+              src={assetUrl(post.link_preview.image_url)}
+              alt=""
+              className="w-24 sm:w-32 object-cover flex-shrink-0"
+              loading="lazy"
+            />
+          )}
+          <div className="py-2 px-3 min-w-0">
+            {post.link_preview.site_name && (
+              <p className="text-xs uppercase tracking-wide text-neutral-500" style={{ fontFamily: 'var(--font-sans)' }}>
+                {post.link_preview.site_name}
+              </p>
+            )}
+            {post.link_preview.title && (
+              <p className="font-semibold leading-snug line-clamp-2">{post.link_preview.title}</p>
+            )}
+            {post.link_preview.description && (
+              <p className="text-sm text-neutral-600 line-clamp-2">{post.link_preview.description}</p>
+            )}
+          </div>
+        </a>
+      )}
+
+      {post.link_url && !post.link_preview && (
         <a 
           href={post.link_url}
           target="_blank"
@@ -165,7 +213,7 @@ const PostCard: React.FC<PostCardProps> = ({ post }) => {
 
       <div className="flex flex-wrap gap-2 mt-auto pt-4 border-t border-neutral-200">
         <span className="inline-block px-3 py-1 bg-primary-600 text-white text-xs sm:text-sm uppercase tracking-wide font-medium rounded-full" style={{ fontFamily: 'var(--font-sans)' }}>
-          {post.post_type}
+          {t(`posts.types.${post.post_type}`)}
         </span>
       </div>
     </article>
@@ -0,0 +1,89 @@
+import type { FormEvent } from 'react';
+import type React from 'react';
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { endpoints, errorMessage } from '../utils/api';
+import type { ReportReason } from '../types/api';
+
+const REASONS: ReportReason[] = ['misinformation', 'hate_speech', 'harassment', 'spam', 'outdated', 'other'];
+
+interface ReportButtonProps {
+  itemType: 'post' | 'event';
+  itemId: number;
+}
+
+const ReportButton: React.FC<ReportButtonProps> = ({ itemType, itemId }) => {
+  const { t } = useTranslation();
+  const [open, setOpen] = useState(false);
+  const [reason, setReason] = useState<ReportReason>('misinformation');
+  const [comment, setComment] = useState('');
+  const [sending, setSending] = useState(false);
+  const [sent, setSent] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleSubmit = async (e: FormEvent) => {
+    e.preventDefault();
+    setSending(true);
+    setError(null);
+
+    try {
+      const report = { reason, comment: comment || undefined };
+      await (itemType === 'post' ? endpoints.reportPost(itemId, report) : endpoints.reportEvent(itemId, report));
+      setSent(true);
+      setOpen(false);
+    } catch (err) {
+      console.error('Failed to send report:', err);
+      setError(errorMessage(err, 'Failed to send report'));
+    } finally {
+      setSending(false);
+    }
+  };
+
+  if (sent) {
+    return <p className="mt-4 text-sm text-neutral-600">{t('report.success')}</p>;
+  }
+
+  if (!open) {
+    return (
+      <button type="button" onClick={() => setOpen(true)} className="mt-4 text-sm text-neutral-500 underline hover:text-neutral-700">
+        {t('report.button')}
+      </button>
+    );
+  }
+
+  return (
+    <form onSubmit={handleSubmit} className="mt-4 card rounded-lg space-y-3">
+      <p className="font-medium">{t('report.title')}</p>
+      {error && <p className="text-sm text-red-600">{error}</p>}
+      <div className="space-y-1">
+        {REASONS.map(r => (
+          <label key={r} className="flex items-center gap-2 text-sm">
+            <input type="radio" name="reason" value={r} checked={reason === r} onChange={() => setReason(r)} />
+            {t(`report.reasons.${r}`)}
+          </label>
+        ))}
+      </div>
+      <label className="block text-sm">
+        {t('report.comment')}
+        <textarea
+          value={comment}
+          onChange={e => setComment(e.target.value)}
+          maxLength={1000}
+          rows={3}
+          className="mt-1 w-full px-3 py-2 border border-gray-300 rounded-lg"
+          dir="auto"
+        />
+      </label>
+      <div className="flex gap-2">
+        <button type="submit" disabled={sending} className="btn btn-primary btn-sm">
+          {sending ? t('common.loading') : t('report.submit')}
+        </button>
+        <button type="button" onClick={() => setOpen(false)} className="btn btn-secondary btn-sm">
+          {t('common.cancel')}
+        </button>
+      </div>
+    </form>
+  );
+};
+
+export default ReportButton;
@@ -0,0 +1,131 @@
+import type { RefObject } from 'react';
+import { useCallback, useEffect, useRef, useState } from 'react';
+import { endpoints } from '../utils/api';
+import type { Challenge, ChallengeRoute } from '../types/api';
+
+// The part of the Turnstile and hCaptcha browser APIs used here; both look the same
+interface CaptchaApi {
+  render: (container: HTMLElement, options: { sitekey: string; callback: (token: string) => void }) => string;
+  reset: (widgetId?: string) => void;
+}
+
+declare global {
+  interface Window {
+    turnstile?: CaptchaApi;
+    hcaptcha?: CaptchaApi;
+  }
+}
+
+const CAPTCHA_SCRIPTS = {
+  turnstile: 'https://challenges.cloudflare.com/turnstile/v0/api.js?render=explicit',
+  hcaptcha: 'https://js.hcaptcha.com/1/api.js?render=explicit',
+};
+
+const scripts = new Map<string, Promise<void>>();
+
+const loadScript = (src: string): Promise<void> => {
+  let loading = scripts.get(src);
+  if (!loading) {
+    loading = new Promise((resolve, reject) => {
+      const script = document.createElement('script');
+      script.src = src;
+      script.async = true;
+      script.onload = () => resolve();
+      script.onerror = () => reject(new Error(`Failed to load ${src}`));
+      document.head.appendChild(script);
+    });
+    scripts.set(src, loading);
+  }
+  return loading;
+};
+
+const leadingZeroBits = (hash: Uint8Array): number => {
+  let bits = 0;
+  for (const byte of hash) {
+    if (byte === 0) {
+      bits += 8;
+      continue;
+    }
+    bits += Math.clz32(byte) - 24;
+    break;
+  }
+  return bits;
+};
+
+// Finds a nonce such that sha256("{token}:{nonce}") starts with `difficulty` zero bits
+const solveProofOfWork = async (token: string, difficulty: number): Promise<string> => {
+  const encoder = new TextEncoder();
+  for (let nonce = 0; ; nonce++) {
+    const response = `${token}:${nonce}`;
+    const hash = new Uint8Array(await crypto.subtle.digest('SHA-256', encoder.encode(response)));
+    if (leadingZeroBits(hash) >= difficulty) {
+      return response;
+    }
+  }
+};
+
+interface UseChallenge {
+  containerRef: RefObject<HTMLDivElement>; // Where a captcha widget is rendered
+  getResponse: () => Promise<string | undefined>;
+  reset: () => void;
+}
+
+/** The challenge the backend requires before submitting to `route`, if any. */
+export function useChallenge(route: ChallengeRoute): UseChallenge {
+  const containerRef = useRef<HTMLDivElement>(null);
+  const [challenge, setChallenge] = useState<Challenge | null>(null);
+  const captchaToken = useRef<string | undefined>(undefined);
+  const widget = useRef<{ api: CaptchaApi; id: string } | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+
+    endpoints.getChallenge(route)
+      .then(async res => {
+        const issued = res.data.challenge;
+        if (cancelled) return;
+        setChallenge(issued);
+
+        if (issued?.type !== 'captcha' || issued.provider === 'stub') return;
+        await loadScript(CAPTCHA_SCRIPTS[issued.provider]);
+        const api = window[issued.provider];
+        if (cancelled || !api || !containerRef.current) return;
+        const id = api.render(containerRef.current, {
+          sitekey: issued.site_key,
+          callback: token => {
+            captchaToken.current = token;
+          },
+        });
+        widget.current = { api, id };
+      })
+      .catch(err => {
+        // The submission itself reports a missing challenge if one was needed
+        console.error('Failed to load challenge:', err);
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [route]);
+
+  // Called on submit; solving proof of work can take a moment
+  const getResponse = useCallback(async (): Promise<string | undefined> => {
+    if (!challenge) return undefined;
+    if (challenge.type === 'proof_of_work') {
+      // A token is only good once, so every submission gets a fresh one
+      const res = await endpoints.getChallenge(route);
+      const fresh = res.data.challenge;
+      return fresh?.type === 'proof_of_work' ? solveProofOfWork(fresh.token, fresh.difficulty) : undefined;
+    }
+    if (challenge.provider === 'stub') return 'pass';
+    return captchaToken.current;
+  }, [challenge, route]);
+
+  // Captcha tokens are spent by a submission, even a rejected one
+  const reset = useCallback(() => {
+    captchaToken.current = undefined;
+    widget.current?.api.reset(widget.current.id);
+  }, []);
+
+  return { containerRef, getResponse, reset };
+}
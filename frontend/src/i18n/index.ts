@@ -32,7 +32,9 @@ void i18n
     },
 
     detection: {
-      order: ['localStorage', 'navigator', 'htmlTag'],
+      // ?lang= comes first so shared links open in the language they were shared in
+      order: ['querystring', 'localStorage', 'navigator', 'htmlTag'],
+      lookupQuerystring: 'lang',
       caches: ['localStorage'],
     },
   });
@@ -2,18 +2,423 @@ import type React from 'react';
 import { useState, useEffect } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
-import { endpoints } from '../utils/api';
-import type { Post, Event } from '../types/api';
+import { endpoints, errorMessage } from '../utils/api';
+import type {
+  AdminPost,
+  AdminEvent,
+  Alert,
+  AlertSeverity,
+  PinPostRequest,
+  PublishSchedule,
+  ReportedItem,
+  SpamAssessment,
+  Webhook,
+  WebhookDelivery,
+  WebhookEvent,
+} from '../types/api';
 import axios from 'axios';
 
+// Score and reasons of a submission's spam assessment, shown while it waits for moderation
+const SpamBadge: React.FC<{ spam: SpamAssessment }> = ({ spam }) => {
+  const { t } = useTranslation();
+  if (spam.spam_score === 0 && !spam.quarantined) {
+    return null;
+  }
+
+  return (
+    <div className="mt-2 text-sm">
+      <span
+        className={`inline-block px-2 py-0.5 rounded font-medium ${
+          spam.quarantined ? 'bg-red-100 text-red-800' : 'bg-yellow-100 text-yellow-800'
+        }`}
+      >
+        {t('admin.spam_score', { score: spam.spam_score })}
+        {spam.quarantined && ` · ${t('admin.quarantined')}`}
+      </span>
+      {spam.spam_reasons.length > 0 && (
+        <ul className="mt-1 text-neutral-600 list-disc ps-5">
+          {spam.spam_reasons.map(reason => (
+            <li key={reason.check}>
+              {reason.detail} (+{reason.score})
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+};
+
+// datetime-local inputs hold local time without a zone
+const toIso = (local: string): string | undefined => (local ? new Date(local).toISOString() : undefined);
+
+const formatDateTime = (iso: string): string => new Date(iso).toLocaleString();
+
+const SEVERITIES: AlertSeverity[] = ['minor', 'moderate', 'severe', 'extreme'];
+
+// Composes alerts, which go live once translated into every enabled language, and ends them
+const AlertsPanel: React.FC = () => {
+  const { t, i18n } = useTranslation();
+  const [alerts, setAlerts] = useState<Alert[]>([]);
+  const [title, setTitle] = useState('');
+  const [body, setBody] = useState('');
+  const [severity, setSeverity] = useState<AlertSeverity>('severe');
+  const [expiresAt, setExpiresAt] = useState('');
+  const [sending, setSending] = useState(false);
+
+  const fetchAlerts = async () => {
+    try {
+      const res = await endpoints.getAdminAlerts();
+      setAlerts(res.data.alerts);
+    } catch (err) {
+      console.error('Failed to fetch alerts:', err);
+    }
+  };
+
+  useEffect(() => {
+    void fetchAlerts();
+  }, []);
+
+  const handleCreate = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setSending(true);
+    try {
+      await endpoints.createAlert({
+        title,
+        body,
+        severity,
+        expires_at: toIso(expiresAt) ?? '',
+        language: i18n.language,
+      });
+      setTitle('');
+      setBody('');
+      setExpiresAt('');
+      await fetchAlerts();
+    } catch (err) {
+      console.error('Failed to publish alert:', err);
+      alert(errorMessage(err, 'Failed to publish alert'));
+    } finally {
+      setSending(false);
+    }
+  };
+
+  const handleEnd = async (alertId: number) => {
+    try {
+      await endpoints.endAlert(alertId);
+      await fetchAlerts();
+    } catch (err) {
+      console.error('Failed to end alert:', err);
+      alert('Failed to end alert');
+    }
+  };
+
+  const isActive = (a: Alert) => !a.ended_at && new Date(a.expires_at) > new Date();
+
+  return (
+    <div className="space-y-6">
+      <form onSubmit={handleCreate} className="card rounded-lg shadow-sm space-y-3">
+        <h2 className="text-xl font-semibold">{t('admin.new_alert')}</h2>
+        <input
+          value={title}
+          onChange={e => setTitle(e.target.value)}
+          placeholder={t('admin.alert_title')}
+          maxLength={255}
+          required
+          className="w-full px-3 py-2 border border-gray-300 rounded-lg"
+          dir="auto"
+        />
+        <textarea
+          value={body}
+          onChange={e => setBody(e.target.value)}
+          placeholder={t('admin.alert_body')}
+          maxLength={5000}
+          rows={4}
+          required
+          className="w-full px-3 py-2 border border-gray-300 rounded-lg"
+          dir="auto"
+        />
+        <div className="flex flex-wrap gap-4 text-sm">
+          <label className="flex items-center gap-2">
+            {t('admin.alert_severity')}
+            <select
+              value={severity}
+              onChange={e => setSeverity(e.target.value as AlertSeverity)}
+              className="border rounded px-2 py-1"
+            >
+              {SEVERITIES.map(s => (
+                <option key={s} value={s}>{t(`alerts.severity.${s}`)}</option>
+              ))}
+            </select>
+          </label>
+          <label className="flex items-center gap-2">
+            {t('admin.expires_at')}
+            <input
+              type="datetime-local"
+              value={expiresAt}
+              onChange={e => setExpiresAt(e.target.value)}
+              required
+              className="px-2 py-1 border border-gray-300 rounded"
+            />
+          </label>
+        </div>
+        <p className="text-sm text-neutral-600">{t('admin.alert_translation_note')}</p>
+        <button type="submit" disabled={sending} className="btn btn-danger btn-md">
+          {sending ? t('common.loading') : t('admin.publish_alert')}
+        </button>
+      </form>
+
+      <section className="space-y-4">
+        {alerts.map(a => (
+          <div key={a.id} className={`card rounded-lg shadow-sm ${isActive(a) ? '' : 'opacity-75'}`}>
+            <div className="flex justify-between items-start">
+              <div>
+                <h3 className="font-semibold" dir="auto">{a.title}</h3>
+                <p className="text-sm text-neutral-600">
+                  {t(`alerts.severity.${a.severity}`)} |{' '}
+                  {isActive(a)
+                    ? t('admin.expires_on', { date: formatDateTime(a.expires_at) })
+                    : t('admin.alert_ended')}
+                </p>
+              </div>
+              {isActive(a) && (
+                <button onClick={() => handleEnd(a.id)} className="btn btn-secondary btn-sm">
+                  {t('admin.end_alert')}
+                </button>
+              )}
+            </div>
+            <p className="text-neutral-700 mt-2 whitespace-pre-wrap" dir="auto">{a.body}</p>
+          </div>
+        ))}
+      </section>
+    </div>
+  );
+};
+
+const WEBHOOK_EVENTS: WebhookEvent[] = [
+  'post.submitted',
+  'post.published',
+  'post.translated',
+  'post.deleted',
+  'event.submitted',
+  'event.published',
+  'event.translated',
+  'event.deleted',
+];
+
+const DELIVERY_STYLES: Record<WebhookDelivery['status'], string> = {
+  pending: 'bg-yellow-100 text-yellow-800',
+  delivered: 'bg-green-100 text-green-800',
+  failed: 'bg-red-100 text-red-800',
+};
+
+// Registers webhooks, sends them test pings and shows what was delivered to them
+const WebhooksPanel: React.FC = () => {
+  const { t } = useTranslation();
+  const [webhooks, setWebhooks] = useState<Webhook[]>([]);
+  const [url, setUrl] = useState('');
+  const [description, setDescription] = useState('');
+  const [events, setEvents] = useState<WebhookEvent[]>(['post.published', 'event.published']);
+  const [secret, setSecret] = useState<string | null>(null);
+  const [openLog, setOpenLog] = useState<number | null>(null);
+  const [deliveries, setDeliveries] = useState<WebhookDelivery[]>([]);
+
+  const fetchWebhooks = async () => {
+    try {
+      const res = await endpoints.getWebhooks();
+      setWebhooks(res.data.webhooks);
+    } catch (err) {
+      console.error('Failed to fetch webhooks:', err);
+    }
+  };
+
+  const fetchDeliveries = async (id: number) => {
+    try {
+      const res = await endpoints.getWebhookDeliveries(id);
+      setDeliveries(res.data.deliveries);
+    } catch (err) {
+      console.error('Failed to fetch deliveries:', err);
+    }
+  };
+
+  useEffect(() => {
+    void fetchWebhooks();
+  }, []);
+
+  const toggleEvent = (event: WebhookEvent) =>
+    setEvents(prev => (prev.includes(event) ? prev.filter(e => e !== event) : [...prev, event]));
+
+  const handleCreate = async (e: React.FormEvent) => {
+    e.preventDefault();
+    try {
+      const res = await endpoints.createWebhook({ url, events, description });
+      setSecret(res.data.secret);
+      setUrl('');
+      setDescription('');
+      await fetchWebhooks();
+    } catch (err) {
+      console.error('Failed to create webhook:', err);
+      alert(errorMessage(err, 'Failed to create webhook'));
+    }
+  };
+
+  const handleToggle = async (webhook: Webhook) => {
+    try {
+      await endpoints.updateWebhook(webhook.id, {
+        url: webhook.url,
+        events: webhook.events,
+        description: webhook.description ?? undefined,
+        enabled: !webhook.enabled,
+      });
+      await fetchWebhooks();
+    } catch (err) {
+      console.error('Failed to update webhook:', err);
+      alert(errorMessage(err, 'Failed to update webhook'));
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    if (!confirm('Are you sure you want to delete this webhook?')) {
+      return;
+    }
+    try {
+      await endpoints.deleteWebhook(id);
+      await fetchWebhooks();
+    } catch (err) {
+      console.error('Failed to delete webhook:', err);
+      alert('Failed to delete webhook');
+    }
+  };
+
+  const handleTest = async (id: number) => {
+    try {
+      await endpoints.testWebhook(id);
+      setOpenLog(id);
+      await fetchDeliveries(id);
+    } catch (err) {
+      console.error('Failed to test webhook:', err);
+      alert('Failed to test webhook');
+    }
+  };
+
+  const toggleLog = async (id: number) => {
+    if (openLog === id) {
+      setOpenLog(null);
+      return;
+    }
+    setOpenLog(id);
+    setDeliveries([]);
+    await fetchDeliveries(id);
+  };
+
+  return (
+    <div className="space-y-6">
+      <form onSubmit={handleCreate} className="card rounded-lg shadow-sm space-y-3">
+        <h2 className="text-xl font-semibold">{t('admin.new_webhook')}</h2>
+        <input
+          type="url"
+          value={url}
+          onChange={e => setUrl(e.target.value)}
+          placeholder={t('admin.webhook_url')}
+          maxLength={500}
+          required
+          className="w-full px-3 py-2 border border-gray-300 rounded-lg"
+          dir="ltr"
+        />
+        <input
+          value={description}
+          onChange={e => setDescription(e.target.value)}
+          placeholder={t('admin.webhook_description')}
+          maxLength={255}
+          className="w-full px-3 py-2 border border-gray-300 rounded-lg"
+          dir="auto"
+        />
+        <fieldset className="text-sm">
+          <legend className="mb-1">{t('admin.webhook_events')}</legend>
+          <div className="grid grid-cols-2 gap-1">
+            {WEBHOOK_EVENTS.map(event => (
+              <label key={event} className="flex items-center gap-2">
+                <input type="checkbox" checked={events.includes(event)} onChange={() => toggleEvent(event)} />
+                <code>{event}</code>
+              </label>
+            ))}
+          </div>
+        </fieldset>
+        <button type="submit" disabled={events.length === 0} className="btn btn-primary btn-md">
+          {t('admin.add_webhook')}
+        </button>
+        {secret && (
+          <p className="text-sm bg-yellow-50 border border-yellow-300 rounded p-2">
+            {t('admin.webhook_secret_note')} <code className="break-all" dir="ltr">{secret}</code>
+          </p>
+        )}
+      </form>
+
+      <section className="space-y-4">
+        {webhooks.map(webhook => (
+          <div key={webhook.id} className={`card rounded-lg shadow-sm ${webhook.enabled ? '' : 'opacity-75'}`}>
+            <div className="flex justify-between items-start gap-4">
+              <div className="min-w-0">
+                <h3 className="font-semibold break-all" dir="ltr">{webhook.url}</h3>
+                {webhook.description && <p className="text-sm text-neutral-600" dir="auto">{webhook.description}</p>}
+                <p className="text-sm text-neutral-600">
+                  {webhook.events.join(', ')}
+                  {!webhook.enabled && ` | ${t('admin.disabled')}`}
+                </p>
+              </div>
+              <div className="flex gap-2 shrink-0">
+                <button onClick={() => handleTest(webhook.id)} className="btn btn-secondary btn-sm">
+                  {t('admin.test_webhook')}
+                </button>
+                <button onClick={() => toggleLog(webhook.id)} className="btn btn-secondary btn-sm">
+                  {t('admin.deliveries')}
+                </button>
+                <button onClick={() => handleToggle(webhook)} className="btn btn-secondary btn-sm">
+                  {webhook.enabled ? t('admin.disable') : t('admin.enable')}
+                </button>
+                <button onClick={() => handleDelete(webhook.id)} className="btn btn-danger btn-sm">
+                  {t('common.delete')}
+                </button>
+              </div>
+            </div>
+            {openLog === webhook.id && (
+              <ul className="mt-3 text-sm divide-y">
+                {deliveries.length === 0 ? (
+                  <li className="py-2 text-neutral-600">{t('admin.no_deliveries')}</li>
+                ) : (
+                  deliveries.map(d => (
+                    <li key={d.id} className="py-2">
+                      <span className={`inline-block px-2 py-0.5 rounded font-medium ${DELIVERY_STYLES[d.status]}`}>
+                        {d.status}
+                      </span>{' '}
+                      <code>{d.event}</code> · {formatDateTime(d.created_at)} ·{' '}
+                      {t('admin.delivery_attempts', { count: d.attempts })}
+                      {d.response_status != null && ` · HTTP ${d.response_status}`}
+                      {d.last_error && <p className="text-red-700 break-all" dir="ltr">{d.last_error}</p>}
+                    </li>
+                  ))
+                )}
+              </ul>
+            )}
+          </div>
+        ))}
+      </section>
+    </div>
+  );
+};
+
 const AdminDashboard: React.FC = () => {
   const { t } = useTranslation();
   const navigate = useNavigate();
-  const [posts, setPosts] = useState<Post[]>([]);
-  const [events, setEvents] = useState<Event[]>([]);
+  const [posts, setPosts] = useState<AdminPost[]>([]);
+  const [events, setEvents] = useState<AdminEvent[]>([]);
+  const [reports, setReports] = useState<ReportedItem[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
-  const [activeTab, setActiveTab] = useState<'posts' | 'events'>('posts');
+  const [activeTab, setActiveTab] = useState<'posts' | 'events' | 'reports' | 'alerts' | 'webhooks'>('posts');
+  const [sort, setSort] = useState<'newest' | 'score'>('newest');
+  // Publish/expiry times typed in per unpublished post, as datetime-local values
+  const [schedules, setSchedules] = useState<Record<number, PublishSchedule>>({});
+  // Pin settings typed in per published post; pinned_until as a datetime-local value
+  const [pins, setPins] = useState<Record<number, PinPostRequest>>({});
 
   useEffect(() => {
     // Check if admin is logged in
@@ -23,20 +428,22 @@ const AdminDashboard: React.FC = () => {
     }
 
     void fetchData();
-  }, [navigate]);
+  }, [navigate, sort]);
 
   const fetchData = async () => {
     try {
       setLoading(true);
       setError(null);
       
-      const [postsRes, eventsRes] = await Promise.all([
-        endpoints.getAdminPosts(),
-        endpoints.getAdminEvents()
+      const [postsRes, eventsRes, reportsRes] = await Promise.all([
+        endpoints.getAdminPosts({ sort }),
+        endpoints.getAdminEvents({ sort }),
+        endpoints.getReports()
       ]);
       
       setPosts(postsRes.data.posts);
       setEvents(eventsRes.data.events);
+      setReports(reportsRes.data.items);
     } catch (err) {
       console.error('Failed to fetch admin data:', err);
       setError(axios.isAxiosError(err) ? err.message : 'Failed to fetch data');
@@ -45,13 +452,21 @@ const AdminDashboard: React.FC = () => {
     }
   };
 
+  const setSchedule = (postId: number, field: keyof PublishSchedule, value: string) => {
+    setSchedules(prev => ({ ...prev, [postId]: { ...prev[postId], [field]: value } }));
+  };
+
   const handlePublishPost = async (postId: number) => {
+    const schedule = schedules[postId];
     try {
-      await endpoints.publishPost(postId);
+      await endpoints.publishPost(
+        postId,
+        schedule && { publish_at: toIso(schedule.publish_at ?? ''), expires_at: toIso(schedule.expires_at ?? '') }
+      );
       await fetchData(); // Refresh data
     } catch (err) {
       console.error('Failed to publish post:', err);
-      alert('Failed to publish post');
+      alert(errorMessage(err, 'Failed to publish post'));
     }
   };
 
@@ -65,6 +480,31 @@ const AdminDashboard: React.FC = () => {
     }
   };
 
+  const setPin = (postId: number, pin: PinPostRequest) => {
+    setPins(prev => ({ ...prev, [postId]: { ...prev[postId], ...pin } }));
+  };
+
+  const handlePinPost = async (postId: number) => {
+    const pin = pins[postId] ?? {};
+    try {
+      await endpoints.pinPost(postId, { ...pin, pinned_until: toIso(pin.pinned_until ?? '') });
+      await fetchData(); // Refresh data
+    } catch (err) {
+      console.error('Failed to pin post:', err);
+      alert(errorMessage(err, 'Failed to pin post'));
+    }
+  };
+
+  const handleUnpinPost = async (postId: number) => {
+    try {
+      await endpoints.unpinPost(postId);
+      await fetchData(); // Refresh data
+    } catch (err) {
+      console.error('Failed to unpin post:', err);
+      alert('Failed to unpin post');
+    }
+  };
+
   const handleDeletePost = async (postId: number) => {
     if (!confirm('Are you sure you want to delete this post?')) {
       return;
@@ -93,6 +533,16 @@ const AdminDashboard: React.FC = () => {
     }
   };
 
+  const handleResolveReports = async (item: ReportedItem, action: 'dismiss' | 'uphold') => {
+    try {
+      await endpoints.resolveReports(item.item_type, item.item_id, action);
+      await fetchData(); // Refresh data
+    } catch (err) {
+      console.error('Failed to resolve reports:', err);
+      alert('Failed to resolve reports');
+    }
+  };
+
   const handleLogout = async () => {
     try {
       await endpoints.adminLogout();
@@ -137,7 +587,7 @@ const AdminDashboard: React.FC = () => {
       </div>
 
       {/* Tab Navigation */}
-      <div className="flex gap-4 mb-6 border-b">
+      <div className="flex gap-4 mb-6 border-b items-end">
         <button
           onClick={() => setActiveTab('posts')}
           className={`pb-2 px-4 font-medium transition-colors ${
@@ -158,10 +608,102 @@ const AdminDashboard: React.FC = () => {
         >
           {t('admin.events')} ({unpublishedEvents.length} unpublished)
         </button>
+        <button
+          onClick={() => setActiveTab('reports')}
+          className={`pb-2 px-4 font-medium transition-colors ${
+            activeTab === 'reports' 
+              ? 'text-primary-600 border-b-2 border-primary-600' 
+              : 'text-neutral-600 hover:text-neutral-800'
+          }`}
+        >
+          {t('admin.reports')} ({reports.length})
+        </button>
+        <button
+          onClick={() => setActiveTab('alerts')}
+          className={`pb-2 px-4 font-medium transition-colors ${
+            activeTab === 'alerts' 
+              ? 'text-primary-600 border-b-2 border-primary-600' 
+              : 'text-neutral-600 hover:text-neutral-800'
+          }`}
+        >
+          {t('admin.alerts')}
+        </button>
+        <button
+          onClick={() => setActiveTab('webhooks')}
+          className={`pb-2 px-4 font-medium transition-colors ${
+            activeTab === 'webhooks' 
+              ? 'text-primary-600 border-b-2 border-primary-600' 
+              : 'text-neutral-600 hover:text-neutral-800'
+          }`}
+        >
+          {t('admin.webhooks')}
+        </button>
+        <label className="ms-auto pb-2 text-sm text-neutral-600">
+          {t('admin.sort')}{' '}
+          <select
+            value={sort}
+            onChange={e => setSort(e.target.value as 'newest' | 'score')}
+            className="border rounded px-2 py-1"
+          >
+            <option value="newest">{t('admin.sort_newest')}</option>
+            <option value="score">{t('admin.sort_score')}</option>
+          </select>
+        </label>
       </div>
 
       {/* Content */}
-      {activeTab === 'posts' ? (
+      {activeTab === 'alerts' ? (
+        <AlertsPanel />
+      ) : activeTab === 'webhooks' ? (
+        <WebhooksPanel />
+      ) : activeTab === 'reports' ? (
+        <section className="space-y-4">
+          {reports.length === 0 ? (
+            <p className="text-neutral-600">No pending reports</p>
+          ) : (
+            reports.map(item => (
+              <div key={`${item.item_type}-${item.item_id}`} className="card rounded-lg shadow-sm">
+                <div className="flex justify-between items-start mb-2">
+                  <div>
+                    <h3 className="font-semibold">{item.title}</h3>
+                    <p className="text-sm text-neutral-600">
+                      {item.item_type === 'post' ? 'Post' : 'Event'} |
+                      {' '}{t('admin.report_count', { reports: item.report_count, reporters: item.reporter_count })}
+                      {item.hidden && ` | ${t('admin.hidden')}`}
+                    </p>
+                  </div>
+                  <div className="flex gap-2">
+                    <button
+                      onClick={() => handleResolveReports(item, 'dismiss')}
+                      className="btn btn-secondary btn-sm"
+                    >
+                      {t('admin.dismiss_reports')}
+                    </button>
+                    <button
+                      onClick={() => handleResolveReports(item, 'uphold')}
+                      className="btn btn-danger btn-sm"
+                    >
+                      {t('admin.uphold_reports')}
+                    </button>
+                  </div>
+                </div>
+                <p className="text-sm">
+                  {Object.entries(item.reasons)
+                    .map(([reason, count]) => `${t(`report.reasons.${reason}`)} (${count})`)
+                    .join(', ')}
+                </p>
+                {item.comments.length > 0 && (
+                  <ul className="mt-2 text-sm text-neutral-700 list-disc ps-5">
+                    {item.comments.map((comment, i) => (
+                      <li key={i} dir="auto">{comment}</li>
+                    ))}
+                  </ul>
+                )}
+              </div>
+            ))
+          )}
+        </section>
+      ) : activeTab === 'posts' ? (
         <div className="space-y-6">
           <section>
             <h2 className="text-xl font-semibold mb-4">
@@ -180,6 +722,7 @@ const AdminDashboard: React.FC = () => {
                           By: {post.author_name} | 
                           Language: {post.original_language} |
                           Direction: {post.text_direction}
+                          {post.publish_at && ` | ${t('admin.scheduled_for', { date: formatDateTime(post.publish_at) })}`}
                         </p>
                       </div>
                       <div className="flex gap-2">
@@ -187,7 +730,7 @@ const AdminDashboard: React.FC = () => {
                           onClick={() => handlePublishPost(post.id)}
                           className="btn btn-primary btn-sm"
                         >
-                          {t('admin.publish')}
+                          {schedules[post.id]?.publish_at ? t('admin.schedule') : t('admin.publish')}
                         </button>
                         <button
                           onClick={() => handleDeletePost(post.id)}
@@ -200,6 +743,27 @@ const AdminDashboard: React.FC = () => {
                     {post.content && (
                       <p className="text-neutral-700 mt-2">{post.content}</p>
                     )}
+                    <div className="flex flex-wrap gap-4 mt-2 text-sm">
+                      <label className="flex items-center gap-2">
+                        {t('admin.publish_at')}
+                        <input
+                          type="datetime-local"
+                          value={schedules[post.id]?.publish_at ?? ''}
+                          onChange={e => setSchedule(post.id, 'publish_at', e.target.value)}
+                          className="px-2 py-1 border border-gray-300 rounded"
+                        />
+                      </label>
+                      <label className="flex items-center gap-2">
+                        {t('admin.expires_at')}
+                        <input
+                          type="datetime-local"
+                          value={schedules[post.id]?.expires_at ?? ''}
+                          onChange={e => setSchedule(post.id, 'expires_at', e.target.value)}
+                          className="px-2 py-1 border border-gray-300 rounded"
+                        />
+                      </label>
+                    </div>
+                    <SpamBadge spam={post} />
                   </div>
                 ))}
               </div>
@@ -220,14 +784,65 @@ const AdminDashboard: React.FC = () => {
                       <div>
                         <h3 className="font-semibold">{post.title}</h3>
                         <p className="text-sm text-neutral-600">
-                          By: {post.author_name} | Published
+                          By: {post.author_name} | Published{post.hidden && ` | ${t('admin.hidden')}`}
+                          {post.archived_at
+                            ? ` | ${t('admin.archived')}`
+                            : post.expires_at && ` | ${t('admin.expires_on', { date: formatDateTime(post.expires_at) })}`}
+                          {post.pinned_at && ` | ${post.emergency ? t('posts.emergency') : t('posts.pinned')} (${post.pin_priority})`}
+                          {post.pinned_until && ` ${t('admin.until', { date: formatDateTime(post.pinned_until) })}`}
                         </p>
                       </div>
+                      <div className="flex gap-2">
+                        {post.pinned_at && (
+                          <button
+                            onClick={() => handleUnpinPost(post.id)}
+                            className="btn btn-secondary btn-sm"
+                          >
+                            {t('admin.unpin')}
+                          </button>
+                        )}
+                        <button
+                          onClick={() => handleDeletePost(post.id)}
+                          className="btn btn-danger btn-sm"
+                        >
+                          {t('admin.delete')}
+                        </button>
+                      </div>
+                    </div>
+                    <div className="flex flex-wrap items-center gap-4 mt-2 text-sm">
+                      <label className="flex items-center gap-2">
+                        {t('admin.pin_priority')}
+                        <input
+                          type="number"
+                          min={-100}
+                          max={100}
+                          value={pins[post.id]?.priority ?? post.pin_priority}
+                          onChange={e => setPin(post.id, { priority: Number(e.target.value) })}
+                          className="w-20 px-2 py-1 border border-gray-300 rounded"
+                        />
+                      </label>
+                      <label className="flex items-center gap-2">
+                        {t('admin.pinned_until')}
+                        <input
+                          type="datetime-local"
+                          value={pins[post.id]?.pinned_until ?? ''}
+                          onChange={e => setPin(post.id, { pinned_until: e.target.value })}
+                          className="px-2 py-1 border border-gray-300 rounded"
+                        />
+                      </label>
+                      <label className="flex items-center gap-2">
+                        <input
+                          type="checkbox"
+                          checked={pins[post.id]?.emergency ?? post.emergency}
+                          onChange={e => setPin(post.id, { emergency: e.target.checked })}
+                        />
+                        {t('posts.emergency')}
+                      </label>
                       <button
-                        onClick={() => handleDeletePost(post.id)}
-                        className="btn btn-danger btn-sm"
+                        onClick={() => handlePinPost(post.id)}
+                        className="btn btn-primary btn-sm"
                       >
-                        {t('admin.delete')}
+                        {t('admin.pin')}
                       </button>
                     </div>
                   </div>
@@ -275,6 +890,7 @@ const AdminDashboard: React.FC = () => {
                     {event.description && (
                       <p className="text-neutral-700 mt-2">{event.description}</p>
                     )}
+                    <SpamBadge spam={event} />
                   </div>
                 ))}
               </div>
@@ -295,7 +911,8 @@ const AdminDashboard: React.FC = () => {
                       <div>
                         <h3 className="font-semibold">{event.title}</h3>
                         <p className="text-sm text-neutral-600">
-                          Date: {event.event_date} | Published
+                          Date: {event.event_date} | Published{event.hidden && ` | ${t('admin.hidden')}`}
+                          {event.archived_at && ` | ${t('admin.archived')}`}
                         </p>
                       </div>
                       <button
@@ -3,7 +3,7 @@ import type React from 'react';
 import { useState } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
-import { endpoints } from '../utils/api';
+import { endpoints, errorMessage } from '../utils/api';
 import axios from 'axios';
 
 const AdminLogin: React.FC = () => {
@@ -24,7 +24,8 @@ const AdminLogin: React.FC = () => {
       navigate('/admin/dashboard');
     } catch (err) {
       console.error('Login failed:', err);
-      setError(axios.isAxiosError(err) && err.response?.status === 401 ? 'Invalid password' : axios.isAxiosError(err) ? err.message : 'Login failed');
+      // A lockout (429) comes with the server's explanation
+      setError(axios.isAxiosError(err) && err.response?.status === 401 ? 'Invalid password' : errorMessage(err, 'Login failed'));
     } finally {
       setLoading(false);
     }
@@ -0,0 +1,79 @@
+import type React from 'react';
+import { useState, useEffect } from 'react';
+import { Link, useParams } from 'react-router-dom';
+import { useTranslation } from 'react-i18next';
+import { Helmet } from 'react-helmet-async';
+import EventCard from '../components/EventCard';
+import ReportButton from '../components/ReportButton';
+import { endpoints } from '../utils/api';
+import type { EventWithTranslation } from '../types/api';
+import axios from 'axios';
+
+const EventDetail: React.FC = () => {
+  const { id } = useParams<{ id: string }>();
+  const { t, i18n } = useTranslation();
+  const [event, setEvent] = useState<EventWithTranslation | null>(null);
+  const [loading, setLoading] = useState(true);
+  const [notFound, setNotFound] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!id) return;
+
+    const fetchEvent = async () => {
+      try {
+        setLoading(true);
+        setError(null);
+        setNotFound(false);
+        const response = await endpoints.getEvent(id, i18n.language);
+        setEvent(response.data.event);
+      } catch (err) {
+        if (axios.isAxiosError(err) && err.response?.status === 404) {
+          setNotFound(true);
+        } else {
+          console.error('Failed to fetch event:', err);
+          setError(axios.isAxiosError(err) ? err.message : 'Failed to fetch event');
+        }
+      } finally {
+        setLoading(false);
+      }
+    };
+
+    void fetchEvent();
+  }, [id, i18n.language]);
+
+  return (
+    <div className="max-w-4xl mx-auto">
+      {event && (
+        <Helmet>
+          <title>{event.title} · Lancaster Local</title>
+        </Helmet>
+      )}
+
+      <div className="mb-6">
+        <Link to="/calendar" className="btn btn-secondary btn-sm">
+          {t('common.back')}
+        </Link>
+      </div>
+
+      {loading ? (
+        <div className="flex justify-center py-12">
+          <div className="spinner"></div>
+        </div>
+      ) : notFound ? (
+        <p className="text-center py-12 text-neutral-600 text-lg">{t('common.not_found')}</p>
+      ) : error ? (
+        <p className="text-center py-8 text-red-600 font-medium">{t('common.error')}: {error}</p>
+      ) : (
+        event && (
+          <>
+            <EventCard event={event} />
+            <ReportButton itemType="event" itemId={event.id} />
+          </>
+        )
+      )}
+    </div>
+  );
+};
+
+export default EventDetail;
@@ -4,22 +4,23 @@ import { Link } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
 import PostCard from '../components/PostCard';
 import PostCardSkeleton from '../components/PostCardSkeleton';
-import { endpoints } from '../utils/api';
+import { apiUrl, endpoints } from '../utils/api';
 import { useInfiniteScroll } from '../hooks/useInfiniteScroll';
-import type { PostWithTranslation } from '../types/api';
+import type { ContentNotice, PostWithTranslation } from '../types/api';
 import axios from 'axios';
 
 const NewsFeed: React.FC = () => {
   const { t, i18n } = useTranslation();
   const [posts, setPosts] = useState<PostWithTranslation[]>([]);
+  const [pinned, setPinned] = useState<PostWithTranslation[]>([]);
   const [loading, setLoading] = useState(true);
   const [loadingMore, setLoadingMore] = useState(false);
   const [error, setError] = useState<string | null>(null);
-  const [page, setPage] = useState(1);
+  const [cursor, setCursor] = useState<string | null>(null);
   const [hasMore, setHasMore] = useState(true);
   const [refreshing, setRefreshing] = useState(false);
 
-  const fetchPosts = useCallback(async (pageNum: number, isInitial = false) => {
+  const fetchPosts = useCallback(async (pageCursor: string | null, isInitial = false) => {
     try {
       if (isInitial) {
         setLoading(true);
@@ -30,7 +31,7 @@ const NewsFeed: React.FC = () => {
 
       const response = await endpoints.getPosts({
         lang: i18n.language,
-        page: pageNum,
+        cursor: pageCursor ?? undefined,
         limit: 10
       });
 
@@ -38,13 +39,14 @@ const NewsFeed: React.FC = () => {
       const pagination = response.data.pagination;
 
       if (isInitial) {
+        setPinned(response.data.pinned);
         setPosts(newPosts);
       } else {
         setPosts(prev => [...prev, ...newPosts]);
       }
 
-      setHasMore(pageNum < pagination.total_pages);
-      setPage(pageNum + 1);
+      setHasMore(pagination.has_more);
+      setCursor(pagination.next_cursor);
     } catch (err) {
       console.error('Failed to fetch posts:', err);
       setError(axios.isAxiosError(err) ? err.message : 'Failed to fetch posts');
@@ -58,27 +60,57 @@ const NewsFeed: React.FC = () => {
   // Reset when language changes
   useEffect(() => {
     setPosts([]);
-    setPage(1);
+    setCursor(null);
     setHasMore(true);
-    void fetchPosts(1, true);
+    void fetchPosts(null, true);
   }, [i18n.language, fetchPosts]);
 
   const loadMore = useCallback(() => {
     if (!loadingMore && hasMore) {
-      void fetchPosts(page, false);
+      void fetchPosts(cursor, false);
     }
-  }, [page, loadingMore, hasMore, fetchPosts]);
+  }, [cursor, loadingMore, hasMore, fetchPosts]);
 
   const handleRefresh = useCallback(async () => {
     setRefreshing(true);
     setPosts([]);
-    setPage(1);
+    setCursor(null);
     setHasMore(true);
-    await fetchPosts(1, true);
+    await fetchPosts(null, true);
   }, [fetchPosts]);
 
   const lastPostRef = useInfiniteScroll(loadMore, hasMore);
 
+  // Apply published, updated and deleted posts as they happen
+  useEffect(() => {
+    const source = new EventSource(apiUrl('/stream', { lang: i18n.language }));
+
+    const onChange = (e: MessageEvent<string>) => {
+      const notice = JSON.parse(e.data) as ContentNotice;
+      if (notice.type !== 'post') return;
+      const { id, item } = notice;
+
+      setPinned(prev => {
+        const rest = prev.filter(p => p.id !== id);
+        if (!item?.pinned) return rest;
+        return prev.some(p => p.id === id) ? prev.map(p => (p.id === id ? item : p)) : [item, ...rest];
+      });
+      setPosts(prev => {
+        if (!item || item.pinned) return prev.filter(p => p.id !== id);
+        if (prev.some(p => p.id === id)) return prev.map(p => (p.id === id ? item : p));
+        // Only posts within the loaded part of the feed; older ones arrive with their page
+        const oldest = prev[prev.length - 1];
+        if (oldest && item.created_at < oldest.created_at) return prev;
+        return [...prev, item].sort((a, b) => b.created_at.localeCompare(a.created_at));
+      });
+    };
+
+    for (const change of ['published', 'updated', 'deleted']) {
+      source.addEventListener(change, onChange);
+    }
+    return () => source.close();
+  }, [i18n.language]);
+
   // Pull to refresh for mobile
   useEffect(() => {
     let startY = 0;
@@ -148,7 +180,7 @@ const NewsFeed: React.FC = () => {
       <div className="text-center py-8">
         <p className="text-red-600 mb-4 font-medium">{t('common.error')}: {error}</p>
         <button
-          onClick={() => fetchPosts(1, true)}
+          onClick={() => fetchPosts(null, true)}
           className="btn btn-primary btn-md"
         >
           {t('common.retry')}
@@ -180,7 +212,15 @@ const NewsFeed: React.FC = () => {
         </div>
       )}
 
-      {posts.length === 0 && !loading ? (
+      {pinned.length > 0 && (
+        <section aria-label={t('posts.pinned')} className="space-y-4 sm:space-y-6 mb-4 sm:mb-6">
+          {pinned.map(post => (
+            <PostCard key={post.id} post={post} />
+          ))}
+        </section>
+      )}
+
+      {posts.length === 0 && pinned.length === 0 && !loading ? (
         <div className="text-center py-12">
           <p className="text-neutral-600 mb-4 text-lg" style={{ fontFamily: 'var(--font-body)' }}>{t('posts.no_posts')}</p>
           <Link to="/submit/post" className="btn btn-primary btn-md">
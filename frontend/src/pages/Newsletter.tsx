@@ -0,0 +1,280 @@
+import type { FormEvent } from 'react';
+import type React from 'react';
+import { useState, useEffect } from 'react';
+import { useLocation, useSearchParams } from 'react-router-dom';
+import { useTranslation } from 'react-i18next';
+import { Helmet } from 'react-helmet-async';
+import axios from 'axios';
+import { endpoints, errorMessage } from '../utils/api';
+import type { Category, Language, NewsletterPreferences, NewsletterSubscription, NewsletterTopic } from '../types/api';
+
+const TOPICS: NewsletterTopic[] = ['posts', 'events'];
+
+const inputClass =
+  'w-full px-3 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-primary-600/20 focus:border-primary-600 focus:outline-none bg-white transition-colors';
+
+// Subscribing, and (with the token from an email) confirming, changing preferences and
+// unsubscribing. /newsletter/confirm confirms the token on the way in.
+const Newsletter: React.FC = () => {
+  const { t, i18n } = useTranslation();
+  const location = useLocation();
+  const [searchParams] = useSearchParams();
+  const token = searchParams.get('token');
+  const confirming = location.pathname.endsWith('/confirm');
+  const askUnsubscribe = searchParams.get('unsubscribe') === '1';
+
+  const [languages, setLanguages] = useState<Language[]>([]);
+  const [categories, setCategories] = useState<Category[]>([]);
+  const [subscription, setSubscription] = useState<NewsletterSubscription | null>(null);
+  const [email, setEmail] = useState('');
+  const [preferences, setPreferences] = useState<NewsletterPreferences>({
+    language: i18n.resolvedLanguage ?? i18n.language,
+    topics: [...TOPICS],
+    categories: [],
+  });
+  const [loading, setLoading] = useState(Boolean(token));
+  const [saving, setSaving] = useState(false);
+  const [invalidLink, setInvalidLink] = useState(false);
+  const [notice, setNotice] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [unsubscribed, setUnsubscribed] = useState(false);
+
+  useEffect(() => {
+    endpoints.getLanguages()
+      .then((response) => setLanguages(response.data.languages))
+      .catch((err: unknown) => console.error('Failed to fetch languages:', err));
+  }, []);
+
+  useEffect(() => {
+    endpoints.getCategories(i18n.language)
+      .then((response) => setCategories(response.data.categories))
+      .catch((err: unknown) => console.error('Failed to fetch categories:', err));
+  }, [i18n.language]);
+
+  useEffect(() => {
+    if (!token) return;
+
+    const loadSubscription = async () => {
+      try {
+        setLoading(true);
+        const response = confirming
+          ? await endpoints.confirmNewsletter(token)
+          : await endpoints.getNewsletterSubscription(token);
+        const current = response.data.subscription;
+        setSubscription(current);
+        setPreferences({ language: current.language_code, topics: current.topics, categories: current.categories });
+        if (confirming) setNotice(t('newsletter.confirmed'));
+      } catch (err) {
+        if (axios.isAxiosError(err) && err.response?.status === 404) {
+          setInvalidLink(true);
+        } else {
+          console.error('Failed to load subscription:', err);
+          setError(errorMessage(err, 'Failed to load subscription'));
+        }
+      } finally {
+        setLoading(false);
+      }
+    };
+
+    void loadSubscription();
+  }, [token, confirming, t]);
+
+  const toggle = <T extends string>(values: T[], value: T): T[] =>
+    values.includes(value) ? values.filter((v) => v !== value) : [...values, value];
+
+  const handleSubmit = async (e: FormEvent) => {
+    e.preventDefault();
+    setSaving(true);
+    setError(null);
+    setNotice(null);
+
+    try {
+      if (subscription && token) {
+        const response = await endpoints.updateNewsletterSubscription(token, preferences);
+        setSubscription(response.data.subscription);
+        setNotice(t('newsletter.saved'));
+      } else {
+        await endpoints.subscribeNewsletter({ ...preferences, email });
+        setNotice(t('newsletter.check_inbox'));
+        setEmail('');
+      }
+    } catch (err) {
+      console.error('Failed to save newsletter subscription:', err);
+      setError(errorMessage(err, 'Failed to save subscription'));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleUnsubscribe = async () => {
+    if (!token) return;
+    setSaving(true);
+    setError(null);
+
+    try {
+      await endpoints.unsubscribeNewsletter(token);
+      setUnsubscribed(true);
+    } catch (err) {
+      console.error('Failed to unsubscribe:', err);
+      setError(errorMessage(err, 'Failed to unsubscribe'));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const unsubscribeButton = (
+    <button
+      type="button"
+      onClick={() => void handleUnsubscribe()}
+      disabled={saving}
+      className="btn btn-md bg-red-600 text-white hover:bg-red-700 rounded-lg shadow-sm disabled:opacity-50"
+    >
+      {t('newsletter.unsubscribe')}
+    </button>
+  );
+
+  return (
+    <div className="max-w-2xl mx-auto">
+      <Helmet>
+        <title>{t('newsletter.title')} · Lancaster Local</title>
+      </Helmet>
+
+      <h1 className="text-3xl sm:text-4xl font-bold mb-4 pb-4 border-b-2 border-gray-200" style={{ fontFamily: 'var(--font-serif)' }}>
+        {subscription ? t('newsletter.manage_title') : t('newsletter.title')}
+      </h1>
+
+      {loading ? (
+        <div className="flex justify-center py-12">
+          <div className="spinner"></div>
+        </div>
+      ) : unsubscribed ? (
+        <p className="py-8 text-neutral-700 text-lg">{t('newsletter.unsubscribed')}</p>
+      ) : (
+        <>
+          {invalidLink && (
+            <div className="bg-amber-50 border border-amber-500 text-amber-800 px-3 py-2 sm:px-4 sm:py-3 mb-4 text-sm sm:text-base font-medium rounded-lg">
+              {t('newsletter.invalid_link')}
+            </div>
+          )}
+
+          {notice && (
+            <div className="bg-green-50 border border-green-600 text-green-800 px-3 py-2 sm:px-4 sm:py-3 mb-4 text-sm sm:text-base font-medium rounded-lg" role="status">
+              {notice}
+            </div>
+          )}
+
+          {error && (
+            <div className="bg-red-50 border border-red-600 text-red-700 px-3 py-2 sm:px-4 sm:py-3 mb-4 text-sm sm:text-base font-medium rounded-lg">
+              {error}
+            </div>
+          )}
+
+          {subscription && askUnsubscribe && (
+            <div className="border border-red-300 bg-red-50 rounded-lg p-4 mb-6 space-y-3">
+              <p className="font-medium">{t('newsletter.unsubscribe_confirm')}</p>
+              {unsubscribeButton}
+            </div>
+          )}
+
+          {subscription ? (
+            <p className="mb-4 text-neutral-700">
+              {t('newsletter.subscribed_as', { email: subscription.email })}
+              {!subscription.confirmed_at && <span className="block text-sm text-amber-700 mt-1">{t('newsletter.pending')}</span>}
+            </p>
+          ) : (
+            <p className="mb-6 text-neutral-700">{t('newsletter.intro')}</p>
+          )}
+
+          <form onSubmit={(e) => void handleSubmit(e)} className="space-y-5">
+            {!subscription && (
+              <div>
+                <label className="block text-sm font-medium mb-1" htmlFor="newsletter-email">
+                  {t('newsletter.email')} *
+                </label>
+                <input
+                  id="newsletter-email"
+                  type="email"
+                  value={email}
+                  onChange={(e) => setEmail(e.target.value)}
+                  required
+                  dir="ltr"
+                  autoComplete="email"
+                  className={inputClass}
+                  style={{ fontFamily: 'var(--font-sans)' }}
+                />
+              </div>
+            )}
+
+            <div>
+              <label className="block text-sm font-medium mb-1" htmlFor="newsletter-language">
+                {t('newsletter.language')}
+              </label>
+              <select
+                id="newsletter-language"
+                value={preferences.language}
+                onChange={(e) => setPreferences((prev) => ({ ...prev, language: e.target.value }))}
+                className="select w-full"
+              >
+                {languages.map((language) => (
+                  <option key={language.code} value={language.code}>
+                    {language.native_name}
+                  </option>
+                ))}
+              </select>
+            </div>
+
+            <fieldset>
+              <legend className="block text-sm font-medium mb-2">{t('newsletter.topics')}</legend>
+              <div className="space-y-2">
+                {TOPICS.map((topic) => (
+                  <label key={topic} className="flex items-center gap-2">
+                    <input
+                      type="checkbox"
+                      checked={preferences.topics.includes(topic)}
+                      onChange={() => setPreferences((prev) => ({ ...prev, topics: toggle(prev.topics, topic) }))}
+                    />
+                    {t(`newsletter.topic_${topic}`)}
+                  </label>
+                ))}
+              </div>
+            </fieldset>
+
+            {preferences.topics.includes('events') && categories.length > 0 && (
+              <fieldset>
+                <legend className="block text-sm font-medium mb-1">{t('newsletter.categories')}</legend>
+                <p className="text-sm text-neutral-600 mb-2">{t('newsletter.all_categories_hint')}</p>
+                <div className="grid grid-cols-2 gap-2">
+                  {categories.map((category) => (
+                    <label key={category.slug} className="flex items-center gap-2">
+                      <input
+                        type="checkbox"
+                        checked={preferences.categories.includes(category.slug)}
+                        onChange={() =>
+                          setPreferences((prev) => ({ ...prev, categories: toggle(prev.categories, category.slug) }))
+                        }
+                      />
+                      {category.name}
+                    </label>
+                  ))}
+                </div>
+              </fieldset>
+            )}
+
+            <div className="flex flex-wrap gap-3 pt-2">
+              <button
+                type="submit"
+                disabled={saving || preferences.topics.length === 0}
+                className="btn btn-primary btn-md rounded-lg shadow-sm hover:shadow-md transition-shadow disabled:opacity-50"
+              >
+                {subscription ? t('newsletter.save') : t('newsletter.subscribe')}
+              </button>
+              {subscription && !askUnsubscribe && unsubscribeButton}
+            </div>
+          </form>
+        </>
+      )}
+    </div>
+  );
+};
+
+export default Newsletter;
@@ -0,0 +1,78 @@
+import type React from 'react';
+import { useState, useEffect } from 'react';
+import { Link, useParams } from 'react-router-dom';
+import { useTranslation } from 'react-i18next';
+import { Helmet } from 'react-helmet-async';
+import PostCard from '../components/PostCard';
+import PostCardSkeleton from '../components/PostCardSkeleton';
+import ReportButton from '../components/ReportButton';
+import { endpoints } from '../utils/api';
+import type { PostWithTranslation } from '../types/api';
+import axios from 'axios';
+
+const PostDetail: React.FC = () => {
+  const { id } = useParams<{ id: string }>();
+  const { t, i18n } = useTranslation();
+  const [post, setPost] = useState<PostWithTranslation | null>(null);
+  const [loading, setLoading] = useState(true);
+  const [notFound, setNotFound] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!id) return;
+
+    const fetchPost = async () => {
+      try {
+        setLoading(true);
+        setError(null);
+        setNotFound(false);
+        const response = await endpoints.getPost(id, i18n.language);
+        setPost(response.data.post);
+      } catch (err) {
+        if (axios.isAxiosError(err) && err.response?.status === 404) {
+          setNotFound(true);
+        } else {
+          console.error('Failed to fetch post:', err);
+          setError(axios.isAxiosError(err) ? err.message : 'Failed to fetch post');
+        }
+      } finally {
+        setLoading(false);
+      }
+    };
+
+    void fetchPost();
+  }, [id, i18n.language]);
+
+  return (
+    <div className="max-w-4xl mx-auto">
+      {post && (
+        <Helmet>
+          <title>{post.title} · Lancaster Local</title>
+        </Helmet>
+      )}
+
+      <div className="mb-6">
+        <Link to="/" className="btn btn-secondary btn-sm">
+          {t('common.back')}
+        </Link>
+      </div>
+
+      {loading ? (
+        <PostCardSkeleton />
+      ) : notFound ? (
+        <p className="text-center py-12 text-neutral-600 text-lg">{t('common.not_found')}</p>
+      ) : error ? (
+        <p className="text-center py-8 text-red-600 font-medium">{t('common.error')}: {error}</p>
+      ) : (
+        post && (
+          <>
+            <PostCard post={post} />
+            <ReportButton itemType="post" itemId={post.id} />
+          </>
+        )
+      )}
+    </div>
+  );
+};
+
+export default PostDetail;
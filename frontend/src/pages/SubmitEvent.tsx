@@ -3,8 +3,9 @@ import type React from 'react';
 import { useState } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
-import { endpoints } from '../utils/api';
-import axios from 'axios';
+import { endpoints, errorMessage } from '../utils/api';
+import ImageUpload from '../components/ImageUpload';
+import { useChallenge } from '../hooks/useChallenge';
 
 interface EventFormData {
   organizer_name: string;
@@ -18,6 +19,7 @@ interface EventFormData {
   is_free: boolean;
   ticket_price: string;
   ticket_url: string;
+  website: string; // Honeypot
 }
 
 const SubmitEvent: React.FC = () => {
@@ -26,6 +28,8 @@ const SubmitEvent: React.FC = () => {
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [textDirection, setTextDirection] = useState<'ltr' | 'rtl'>('ltr');
+  const [imageId, setImageId] = useState<string | undefined>(undefined);
+  const challenge = useChallenge('events');
   
   const [formData, setFormData] = useState<EventFormData>({
     organizer_name: '',
@@ -38,7 +42,8 @@ const SubmitEvent: React.FC = () => {
     category: 'community',
     is_free: true,
     ticket_price: '',
-    ticket_url: ''
+    ticket_url: '',
+    website: ''
   });
 
   // RTL detection helper
@@ -68,9 +73,11 @@ const SubmitEvent: React.FC = () => {
     try {
       const eventData = {
         ...formData,
-        language: i18n.language,
+        language: i18n.resolvedLanguage ?? i18n.language,
         text_direction: textDirection,
-        ticket_price: formData.is_free ? undefined : parseFloat(formData.ticket_price) || undefined
+        image_id: imageId,
+        ticket_price: formData.is_free ? undefined : parseFloat(formData.ticket_price) || undefined,
+        challenge_response: await challenge.getResponse()
       };
 
       await endpoints.createEvent(eventData);
@@ -79,11 +86,8 @@ const SubmitEvent: React.FC = () => {
       navigate('/calendar');
     } catch (err) {
       console.error('Failed to submit event:', err);
-      setError(
-        axios.isAxiosError(err) && err.response?.data ? 
-          String((err.response.data as { message?: string }).message || 'Failed to submit event') : 
-          axios.isAxiosError(err) ? err.message : 'Failed to submit event'
-      );
+      setError(errorMessage(err, 'Failed to submit event'));
+      challenge.reset();
     } finally {
       setLoading(false);
     }
@@ -100,6 +104,21 @@ const SubmitEvent: React.FC = () => {
       )}
 
       <form onSubmit={handleSubmit} className="space-y-4">
+        {/* Honeypot: hidden from people, filled in by bots */}
+        <div aria-hidden="true" style={{ position: 'absolute', left: '-10000px', width: '1px', height: '1px', overflow: 'hidden' }}>
+          <label>
+            Website
+            <input
+              type="text"
+              name="website"
+              value={formData.website}
+              onChange={handleInputChange}
+              tabIndex={-1}
+              autoComplete="off"
+            />
+          </label>
+        </div>
+
         <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
           <div>
             <label className="block text-sm font-medium mb-1">
@@ -278,6 +297,10 @@ const SubmitEvent: React.FC = () => {
           )}
         </div>
 
+        <ImageUpload onChange={(image) => setImageId(image?.image_id)} />
+
+        <div ref={challenge.containerRef} />
+
         <div className="flex flex-col sm:flex-row gap-3 pt-4">
           <button
             type="submit"
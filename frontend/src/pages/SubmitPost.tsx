@@ -3,8 +3,10 @@ import type React from 'react';
 import { useState } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
-import { endpoints } from '../utils/api';
-import axios from 'axios';
+import { endpoints, errorMessage } from '../utils/api';
+import ImageUpload from '../components/ImageUpload';
+import { useChallenge } from '../hooks/useChallenge';
+import type { PostType } from '../types/api';
 
 interface PostFormData {
   author_name: string;
@@ -13,7 +15,8 @@ interface PostFormData {
   content: string;
   link_url: string;
   image_url: string;
-  post_type: string;
+  post_type: PostType;
+  website: string; // Honeypot
 }
 
 const SubmitPost: React.FC = () => {
@@ -22,6 +25,8 @@ const SubmitPost: React.FC = () => {
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [textDirection, setTextDirection] = useState<'ltr' | 'rtl'>('ltr');
+  const [imageId, setImageId] = useState<string | undefined>(undefined);
+  const challenge = useChallenge('posts');
   
   const [formData, setFormData] = useState<PostFormData>({
     author_name: '',
@@ -30,7 +35,8 @@ const SubmitPost: React.FC = () => {
     content: '',
     link_url: '',
     image_url: '',
-    post_type: 'text'
+    post_type: 'text',
+    website: ''
   });
 
   // RTL detection helper
@@ -57,19 +63,18 @@ const SubmitPost: React.FC = () => {
     try {
       await endpoints.createPost({
         ...formData,
-        language: i18n.language,
-        text_direction: textDirection
+        image_id: imageId,
+        language: i18n.resolvedLanguage ?? i18n.language,
+        text_direction: textDirection,
+        challenge_response: await challenge.getResponse()
       });
       
       alert(t('posts.submit_success'));
       navigate('/');
     } catch (err) {
       console.error('Failed to submit post:', err);
-      setError(
-        axios.isAxiosError(err) && err.response?.data ? 
-          String((err.response.data as { message?: string }).message || 'Failed to submit post') : 
-          axios.isAxiosError(err) ? err.message : 'Failed to submit post'
-      );
+      setError(errorMessage(err, 'Failed to submit post'));
+      challenge.reset();
     } finally {
       setLoading(false);
     }
@@ -86,6 +91,21 @@ const SubmitPost: React.FC = () => {
       )}
 
       <form onSubmit={handleSubmit} className="space-y-4">
+        {/* Honeypot: hidden from people, filled in by bots */}
+        <div aria-hidden="true" style={{ position: 'absolute', left: '-10000px', width: '1px', height: '1px', overflow: 'hidden' }}>
+          <label>
+            Website
+            <input
+              type="text"
+              name="website"
+              value={formData.website}
+              onChange={handleInputChange}
+              tabIndex={-1}
+              autoComplete="off"
+            />
+          </label>
+        </div>
+
         <div>
           <label className="block text-sm font-medium mb-1">
             {t('posts.author_name')} *
@@ -138,12 +158,13 @@ const SubmitPost: React.FC = () => {
 
         <div>
           <label className="block text-sm font-medium mb-1">
-            {t('posts.content')}
+            {t('posts.content')}{formData.post_type !== 'link' && ' *'}
           </label>
           <textarea
             name="content"
             value={formData.content}
             onChange={handleInputChange}
+            required={formData.post_type !== 'link'}
             rows={6}
             className="w-full px-3 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-primary-600/20 focus:border-primary-600 focus:outline-none bg-white transition-colors"
             dir={textDirection}
@@ -168,18 +189,20 @@ const SubmitPost: React.FC = () => {
             <option value="text">{t('posts.types.text')}</option>
             <option value="link">{t('posts.types.link')}</option>
             <option value="announcement">{t('posts.types.announcement')}</option>
+            <option value="article">{t('posts.types.article')}</option>
           </select>
         </div>
 
         <div>
           <label className="block text-sm font-medium mb-1">
-            {t('posts.link_url')}
+            {t('posts.link_url')}{formData.post_type === 'link' && ' *'}
           </label>
           <input
             type="url"
             name="link_url"
             value={formData.link_url}
             onChange={handleInputChange}
+            required={formData.post_type === 'link'}
             className="w-full px-3 py-2 border border-gray-300 rounded-lg focus:ring-2 focus:ring-primary-600/20 focus:border-primary-600 focus:outline-none bg-white transition-colors"
             style={{ fontFamily: 'var(--font-sans)' }}
             dir="ltr"
@@ -201,6 +224,10 @@ const SubmitPost: React.FC = () => {
           />
         </div>
 
+        <ImageUpload onChange={(image) => setImageId(image?.image_id)} />
+
+        <div ref={challenge.containerRef} />
+
         <div className="flex flex-col sm:flex-row gap-3 pt-4">
           <button
             type="submit"
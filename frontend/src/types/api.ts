@@ -9,24 +9,151 @@ export interface Language {
   enabled: boolean;
 }
 
+export type PostType = 'text' | 'link' | 'announcement' | 'article';
+
 export interface Post {
   id: number;
+  slug?: string | null;
   author_name: string;
   author_email?: string;
   title: string;
   content?: string;
   link_url?: string;
   image_url?: string;
-  post_type: 'text' | 'link' | 'announcement' | 'article';
+  image_id?: string | null;
+  post_type: PostType;
   original_language: string;
   text_direction: 'ltr' | 'rtl';
   published: boolean;
+  publish_at?: string | null; // Approved, but not shown before this
+  expires_at?: string | null;
+  archived_at?: string | null;
+  pinned_at?: string | null;
+  pinned_until?: string | null;
+  pin_priority: number;
+  emergency: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export type AlertSeverity = 'minor' | 'moderate' | 'severe' | 'extreme';
+
+// An active alert in the reader's language
+export interface LocalizedAlert {
+  id: number;
+  title: string;
+  body: string;
+  severity: AlertSeverity;
+  original_language: string;
+  text_direction: 'ltr' | 'rtl';
+  expires_at: string;
+  created_at: string;
+}
+
+export interface Alert extends LocalizedAlert {
+  ended_at?: string | null; // Set when a moderator ends it early
+}
+
+export interface CreateAlertRequest {
+  title: string;
+  body: string;
+  severity: AlertSeverity;
+  expires_at: string;
+  language?: string;
+  text_direction?: 'ltr' | 'rtl' | 'auto';
+}
+
+export type WebhookEvent =
+  | 'post.submitted'
+  | 'post.published'
+  | 'post.translated'
+  | 'post.deleted'
+  | 'event.submitted'
+  | 'event.published'
+  | 'event.translated'
+  | 'event.deleted';
+
+export interface Webhook {
+  id: number;
+  url: string;
+  events: WebhookEvent[];
+  description?: string | null;
+  enabled: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface WebhookRequest {
+  url: string;
+  events: WebhookEvent[];
+  description?: string;
+  enabled?: boolean;
+  secret?: string; // Generated on create when left out; kept on update
+}
+
+export interface WebhookDelivery {
+  id: number;
+  webhook_id: number;
+  event: WebhookEvent | 'ping';
+  payload: unknown;
+  status: 'pending' | 'delivered' | 'failed';
+  attempts: number;
+  next_attempt_at: string;
+  response_status?: number | null;
+  last_error?: string | null;
+  created_at: string;
+  delivered_at?: string | null;
+}
+
+export interface Category {
+  slug: string;
+  name: string; // In the requested language, falling back to English
+  color?: string | null;
+  icon?: string | null;
+  sort_order: number;
+  is_translated: boolean;
+}
+
+export type NewsletterTopic = 'posts' | 'events';
+
+export interface NewsletterPreferences {
+  language: string;
+  topics: NewsletterTopic[];
+  categories: string[]; // Event category slugs; empty for all
+}
+
+export interface NewsletterSubscribeRequest extends NewsletterPreferences {
+  email: string;
+}
+
+export interface NewsletterSubscription {
+  id: number;
+  email: string;
+  language_code: string;
+  topics: NewsletterTopic[];
+  categories: string[];
+  confirmed_at?: string | null;
+  confirmation_sent_at: string;
+  last_digest_at?: string | null;
   created_at: string;
   updated_at: string;
 }
 
+export interface PinPostRequest {
+  priority?: number; // -100 to 100, higher first
+  pinned_until?: string;
+  emergency?: boolean;
+}
+
+// Omitting publish_at publishes right away; omitting expires_at keeps the post up
+export interface PublishSchedule {
+  publish_at?: string;
+  expires_at?: string;
+}
+
 export interface PostWithTranslation {
   id: number;
+  slug?: string | null;
   author_name: string;
   title: string;
   content?: string;
@@ -34,14 +161,27 @@ export interface PostWithTranslation {
   original_content?: string;
   link_url?: string;
   image_url?: string;
-  post_type: string;
+  thumbnail_url?: string | null;
+  link_preview?: LinkPreview | null;
+  post_type: PostType;
   original_language: string;
   original_text_direction: 'ltr' | 'rtl';
   text_direction: 'ltr' | 'rtl';
   is_translated: boolean;
+  pinned: boolean;
+  emergency: boolean; // Only ever set on pinned posts
   created_at: string;
 }
 
+export interface LinkPreview {
+  url: string;
+  title?: string | null;
+  description?: string | null;
+  image_url?: string | null;
+  site_name?: string | null;
+  is_translated: boolean;
+}
+
 export interface CreatePostRequest {
   author_name: string;
   author_email?: string;
@@ -49,13 +189,17 @@ export interface CreatePostRequest {
   content?: string;
   link_url?: string;
   image_url?: string;
-  post_type: string;
+  image_id?: string;
+  post_type: PostType;
   language?: string;
-  text_direction?: 'ltr' | 'rtl';
+  text_direction?: 'ltr' | 'rtl' | 'auto';
+  website?: string; // Honeypot, left empty by people
+  challenge_response?: string;
 }
 
 export interface Event {
   id: number;
+  slug?: string | null;
   organizer_name: string;
   organizer_email?: string;
   title: string;
@@ -67,14 +211,17 @@ export interface Event {
   is_free: boolean;
   ticket_price?: number;
   ticket_url?: string;
+  image_id?: string | null;
   original_language: string;
   text_direction: 'ltr' | 'rtl';
   published: boolean;
+  archived_at?: string | null; // Set once the event (or its series) is over
   created_at: string;
 }
 
 export interface EventWithTranslation {
   id: number;
+  slug?: string | null;
   organizer_name: string;
   title: string;
   description?: string;
@@ -87,6 +234,8 @@ export interface EventWithTranslation {
   is_free: boolean;
   ticket_price?: number;
   ticket_url?: string;
+  image_url?: string | null;
+  thumbnail_url?: string | null;
   original_language: string;
   original_text_direction: 'ltr' | 'rtl';
   text_direction: 'ltr' | 'rtl';
@@ -106,8 +255,73 @@ export interface CreateEventRequest {
   is_free: boolean;
   ticket_price?: number;
   ticket_url?: string;
+  image_id?: string;
   language?: string;
-  text_direction?: 'ltr' | 'rtl';
+  text_direction?: 'ltr' | 'rtl' | 'auto';
+  website?: string; // Honeypot, left empty by people
+  challenge_response?: string;
+}
+
+export interface UploadedImage {
+  image_id: string;
+  image_url: string;
+  thumbnail_url: string;
+  width: number;
+  height: number;
+}
+
+export interface SpamReason {
+  check: string;
+  score: number;
+  detail: string;
+}
+
+// What moderators see of a submission's spam assessment
+export interface SpamAssessment {
+  spam_score: number;
+  spam_reasons: SpamReason[];
+  quarantined: boolean;
+}
+
+export type AdminPost = Post & SpamAssessment & { hidden: boolean };
+export type AdminEvent = Event & SpamAssessment & { hidden: boolean };
+
+export interface AdminQueueParams {
+  sort?: 'newest' | 'score';
+  quarantined?: boolean;
+}
+
+export type ChallengeRoute = 'posts' | 'events';
+
+export type Challenge =
+  | { type: 'proof_of_work'; token: string; difficulty: number; expires_at: string }
+  | { type: 'captcha'; provider: 'turnstile' | 'hcaptcha' | 'stub'; site_key: string };
+
+export interface ChallengeResponse {
+  required: boolean;
+  challenge: Challenge | null;
+}
+
+export type ReportReason = 'misinformation' | 'hate_speech' | 'harassment' | 'spam' | 'outdated' | 'other';
+
+export interface ReportRequest {
+  reason: ReportReason;
+  comment?: string;
+}
+
+// Pending reports about one post or event, taken together
+export interface ReportedItem {
+  item_type: 'post' | 'event';
+  item_id: number;
+  title: string;
+  slug?: string | null;
+  hidden: boolean;
+  report_count: number;
+  reporter_count: number;
+  reasons: Partial<Record<ReportReason, number>>;
+  comments: string[];
+  first_reported_at: string;
+  last_reported_at: string;
 }
 
 export interface AdminLoginRequest {
@@ -120,13 +334,14 @@ export interface AdminLoginResponse {
 
 // API Response Types
 export interface PaginationInfo {
-  page: number;
   limit: number;
-  total: number;
-  total_pages: number;
+  next_cursor: string | null;
+  has_more: boolean;
+  total?: number;
 }
 
 export interface PostsResponse {
+  pinned: PostWithTranslation[]; // Emergencies first, then by priority; empty after the first page
   posts: PostWithTranslation[];
   pagination: PaginationInfo;
 }
@@ -135,7 +350,41 @@ export interface EventsResponse {
   events: EventWithTranslation[];
 }
 
+export interface PostResponse {
+  post: PostWithTranslation;
+  available_languages: string[];
+}
+
+export interface EventOccurrence {
+  event_date: string;
+  event_time?: string;
+  location?: string;
+  is_cancelled: boolean;
+}
+
+export interface EventResponse {
+  event: EventWithTranslation;
+  available_languages: string[];
+  occurrences: EventOccurrence[] | null;
+}
+
+// Data of the published, updated and deleted events on /api/stream; deletions carry no item
+export type ContentNotice =
+  | { type: 'post'; id: number; item?: PostWithTranslation }
+  | { type: 'event'; id: number; item?: EventWithTranslation };
+
+export type FieldErrorCode = 'required' | 'invalid' | 'too_long' | 'out_of_range' | 'unknown' | 'taken';
+
+export interface FieldError {
+  field: string;
+  code: FieldErrorCode;
+  message: string;
+}
+
+// Body of every error response; message is localized, code is stable
 export interface ApiError {
+  code: string;
   message: string;
-  status?: number;
+  request_id?: string;
+  fields?: FieldError[];
 }
\ No newline at end of file
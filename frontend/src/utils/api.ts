@@ -3,14 +3,38 @@ import axios from 'axios';
 import type {
   Language,
   PostsResponse,
+  PostResponse,
   EventsResponse,
+  EventResponse,
   CreatePostRequest,
   CreateEventRequest,
   AdminLoginRequest,
   AdminLoginResponse,
+  AdminPost,
+  AdminEvent,
+  AdminQueueParams,
   Post,
   Event,
+  UploadedImage,
+  PostType,
+  ApiError,
+  ChallengeRoute,
+  ChallengeResponse,
+  ReportRequest,
+  ReportedItem,
+  PublishSchedule,
+  PinPostRequest,
+  Alert,
+  CreateAlertRequest,
+  Webhook,
+  WebhookRequest,
+  WebhookDelivery,
+  Category,
+  NewsletterPreferences,
+  NewsletterSubscribeRequest,
+  NewsletterSubscription,
 } from '../types/api';
+import i18n from '../i18n';
 
 const API_URL = import.meta.env.VITE_API_URL || 'http://localhost:3000/api';
 
@@ -27,6 +51,10 @@ api.interceptors.request.use((config: InternalAxiosRequestConfig) => {
   if (token && config.headers) {
     config.headers.Authorization = `Bearer ${token}`;
   }
+  // Error messages come back in the language picked in the UI
+  if (i18n.language && config.headers) {
+    config.headers['Accept-Language'] = i18n.language;
+  }
   return config;
 });
 
@@ -44,11 +72,39 @@ api.interceptors.response.use(
 
 export default api;
 
+// Uploaded images come back as server-relative paths (/api/images/...); in development
+// the API lives on another origin, so resolve them against it
+export const assetUrl = (path: string): string =>
+  path.startsWith('/api/') ? new URL(API_URL, window.location.origin).origin + path : path;
+
+// Absolute URL of an API endpoint, for clients other than axios such as EventSource
+export const apiUrl = (path: string, params?: Record<string, string>): string => {
+  const url = new URL(`${API_URL}${path}`, window.location.origin);
+  for (const [key, value] of Object.entries(params ?? {})) {
+    url.searchParams.set(key, value);
+  }
+  return url.toString();
+};
+
+// A displayable message for a failed request: the server's localized message, the
+// fields it rejected, and the request id to quote when reporting a problem
+export const errorMessage = (err: unknown, fallback: string): string => {
+  if (!axios.isAxiosError(err)) return fallback;
+  const data = err.response?.data as Partial<ApiError> | undefined;
+  if (!data?.message) return err.message || fallback;
+
+  const fields = data.fields?.map((f) => `${f.field}: ${f.message}`).join('; ');
+  const reference = data.request_id ? ` (${data.request_id})` : '';
+  return `${data.message}${fields ? ` — ${fields}` : ''}${reference}`;
+};
+
 // Query parameter types
 interface GetPostsParams {
   lang?: string;
-  page?: number;
+  cursor?: string;
   limit?: number;
+  include_total?: boolean;
+  type?: PostType;
 }
 
 interface GetEventsParams {
@@ -62,34 +118,100 @@ export const endpoints = {
   // Language
   getLanguages: (): Promise<AxiosResponse<{ languages: Language[] }>> => 
     api.get('/languages'),
+  getChallenge: (route: ChallengeRoute): Promise<AxiosResponse<ChallengeResponse>> => 
+    api.get('/challenge', { params: { route } }),
   
   // Posts
   getPosts: (params?: GetPostsParams): Promise<AxiosResponse<PostsResponse>> => 
     api.get('/posts', { params }),
+  getPost: (idOrSlug: number | string, lang?: string): Promise<AxiosResponse<PostResponse>> => 
+    api.get(`/posts/${encodeURIComponent(idOrSlug)}`, { params: { lang } }),
   createPost: (data: CreatePostRequest): Promise<AxiosResponse<{ post: Post; message: string }>> => 
     api.post('/posts', data),
-  publishPost: (id: number): Promise<AxiosResponse<{ message: string }>> => 
-    api.post(`/posts/${id}/publish`),
+  publishPost: (id: number, schedule?: PublishSchedule): Promise<AxiosResponse<{ message: string; publish_at?: string }>> => 
+    api.post(`/admin/posts/${id}/publish`, schedule),
+  reportPost: (id: number, data: ReportRequest): Promise<AxiosResponse<{ message: string }>> => 
+    api.post(`/posts/${id}/report`, data),
   
   // Events
   getEvents: (params?: GetEventsParams): Promise<AxiosResponse<EventsResponse>> => 
     api.get('/events', { params }),
+  getEvent: (idOrSlug: number | string, lang?: string): Promise<AxiosResponse<EventResponse>> => 
+    api.get(`/events/${encodeURIComponent(idOrSlug)}`, { params: { lang } }),
   createEvent: (data: CreateEventRequest): Promise<AxiosResponse<{ event: Event; message: string }>> => 
     api.post('/events', data),
   publishEvent: (id: number): Promise<AxiosResponse<{ message: string }>> => 
-    api.post(`/events/${id}/publish`),
+    api.post(`/admin/events/${id}/publish`),
+  reportEvent: (id: number, data: ReportRequest): Promise<AxiosResponse<{ message: string }>> => 
+    api.post(`/events/${id}/report`, data),
+  
+  // Categories
+  getCategories: (lang?: string): Promise<AxiosResponse<{ categories: Category[] }>> =>
+    api.get('/categories', { params: { lang } }),
+  
+  // Newsletter; the token comes from the link in a newsletter email
+  subscribeNewsletter: (data: NewsletterSubscribeRequest): Promise<AxiosResponse<{ message: string }>> =>
+    api.post('/newsletter/subscribe', data),
+  confirmNewsletter: (token: string): Promise<AxiosResponse<{ subscription: NewsletterSubscription }>> =>
+    api.post('/newsletter/confirm', null, { params: { token } }),
+  getNewsletterSubscription: (token: string): Promise<AxiosResponse<{ subscription: NewsletterSubscription }>> =>
+    api.get('/newsletter/subscription', { params: { token } }),
+  updateNewsletterSubscription: (
+    token: string,
+    data: NewsletterPreferences
+  ): Promise<AxiosResponse<{ subscription: NewsletterSubscription }>> =>
+    api.put('/newsletter/subscription', data, { params: { token } }),
+  unsubscribeNewsletter: (token: string): Promise<AxiosResponse<{ message: string }>> =>
+    api.post('/newsletter/unsubscribe', null, { params: { token } }),
+  
+  // Images
+  uploadImage: (file: File): Promise<AxiosResponse<UploadedImage>> => {
+    const data = new FormData();
+    data.append('image', file);
+    return api.post('/uploads/images', data, { headers: { 'Content-Type': 'multipart/form-data' } });
+  },
   
   // Admin
   adminLogin: (data: AdminLoginRequest): Promise<AxiosResponse<AdminLoginResponse>> => 
     api.post('/admin/login', data),
   adminLogout: (): Promise<AxiosResponse<void>> => 
     api.post('/admin/logout'),
-  getAdminPosts: (): Promise<AxiosResponse<{ posts: Post[] }>> => 
-    api.get('/admin/posts'),
-  getAdminEvents: (): Promise<AxiosResponse<{ events: Event[] }>> => 
-    api.get('/admin/events'),
+  getAdminPosts: (params?: AdminQueueParams): Promise<AxiosResponse<{ posts: AdminPost[] }>> => 
+    api.get('/admin/posts', { params }),
+  getAdminEvents: (params?: AdminQueueParams): Promise<AxiosResponse<{ events: AdminEvent[] }>> => 
+    api.get('/admin/events', { params }),
   deletePost: (id: number): Promise<AxiosResponse<{ message: string }>> => 
     api.delete(`/admin/posts/${id}`),
+  getAdminAlerts: (): Promise<AxiosResponse<{ alerts: Alert[] }>> =>
+    api.get('/admin/alerts'),
+  createAlert: (alert: CreateAlertRequest): Promise<AxiosResponse<{ message: string; alert: Alert; languages: number }>> =>
+    api.post('/admin/alerts', alert),
+  endAlert: (id: number): Promise<AxiosResponse<{ message: string }>> =>
+    api.delete(`/admin/alerts/${id}`),
+  getWebhooks: (): Promise<AxiosResponse<{ webhooks: Webhook[] }>> =>
+    api.get('/admin/webhooks'),
+  createWebhook: (webhook: WebhookRequest): Promise<AxiosResponse<{ webhook: Webhook; secret: string }>> =>
+    api.post('/admin/webhooks', webhook),
+  updateWebhook: (id: number, webhook: WebhookRequest): Promise<AxiosResponse<{ webhook: Webhook; secret: string | null }>> =>
+    api.put(`/admin/webhooks/${id}`, webhook),
+  deleteWebhook: (id: number): Promise<AxiosResponse<{ message: string }>> =>
+    api.delete(`/admin/webhooks/${id}`),
+  getWebhookDeliveries: (id: number): Promise<AxiosResponse<{ deliveries: WebhookDelivery[] }>> =>
+    api.get(`/admin/webhooks/${id}/deliveries`),
+  testWebhook: (id: number): Promise<AxiosResponse<{ delivery: WebhookDelivery }>> =>
+    api.post(`/admin/webhooks/${id}/test`),
+  pinPost: (id: number, pin: PinPostRequest): Promise<AxiosResponse<{ message: string }>> =>
+    api.put(`/admin/posts/${id}/pin`, pin),
+  unpinPost: (id: number): Promise<AxiosResponse<{ message: string }>> =>
+    api.delete(`/admin/posts/${id}/pin`),
   deleteEvent: (id: number): Promise<AxiosResponse<{ message: string }>> => 
     api.delete(`/admin/events/${id}`),
+  getReports: (): Promise<AxiosResponse<{ items: ReportedItem[] }>> => 
+    api.get('/admin/reports'),
+  resolveReports: (
+    itemType: 'post' | 'event',
+    id: number,
+    action: 'dismiss' | 'uphold'
+  ): Promise<AxiosResponse<{ message: string; resolved: number; hidden: boolean }>> => 
+    api.post(`/admin/reports/${itemType}s/${id}/resolve`, { action }),
 };
\ No newline at end of file